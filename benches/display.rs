@@ -0,0 +1,55 @@
+//! Benchmarks of the formatting of values, run them with `cargo bench --bench display`
+use std::fmt::Write;
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use dbase::{Date, FieldValue};
+
+/// Returns rows of values like the ones of a table being exported to CSV
+fn rows() -> Vec<Vec<FieldValue>> {
+    (0..1_000)
+        .map(|i| {
+            vec![
+                FieldValue::Character(Some(format!("name {}", i))),
+                FieldValue::Numeric(Some(f64::from(i) * 1.25)),
+                FieldValue::Logical(Some(i % 2 == 0)),
+                FieldValue::Date(Some(Date::new(1 + i % 28, 1 + i % 12, 2000))),
+                FieldValue::Integer(i as i32),
+                FieldValue::Character(None),
+            ]
+        })
+        .collect()
+}
+
+fn format_rows(c: &mut Criterion) {
+    let rows = rows();
+    let mut group = c.benchmark_group("format rows");
+    group.bench_function("to_string", |b| {
+        let mut line = String::new();
+        b.iter(|| {
+            for row in &rows {
+                line.clear();
+                for value in row {
+                    line.push_str(&value.to_string());
+                    line.push(';');
+                }
+                black_box(&line);
+            }
+        })
+    });
+    group.bench_function("as_display", |b| {
+        let mut line = String::new();
+        b.iter(|| {
+            for row in &rows {
+                line.clear();
+                for value in row {
+                    write!(line, "{};", value.as_display()).unwrap();
+                }
+                black_box(&line);
+            }
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, format_rows);
+criterion_main!(benches);
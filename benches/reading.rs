@@ -0,0 +1,107 @@
+//! Benchmarks of the reading of records, run them with `cargo bench --bench reading`
+use std::io::{Cursor, Write};
+
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+use dbase::{Date, FieldName, FieldValue, Reader, Record, TableWriterBuilder};
+
+const NUM_RECORDS: usize = 10_000;
+
+/// Returns the bytes of a table with a few fields of common types
+fn table_bytes() -> Vec<u8> {
+    let records = (0..NUM_RECORDS)
+        .map(|i| {
+            let mut record = Record::default();
+            record.insert(
+                "name".to_owned(),
+                FieldValue::Character(Some(format!("record number {}", i))),
+            );
+            record.insert(
+                "amount".to_owned(),
+                FieldValue::Numeric(Some(i as f64 / 8.0)),
+            );
+            record.insert("flag".to_owned(), FieldValue::Logical(Some(i % 2 == 0)));
+            record.insert(
+                "day".to_owned(),
+                FieldValue::Date(Some(Date::new(
+                    1 + (i % 28) as u32,
+                    1 + (i % 12) as u32,
+                    2000,
+                ))),
+            );
+            record
+        })
+        .collect::<Vec<_>>();
+    let mut cursor = Cursor::new(Vec::<u8>::new());
+    TableWriterBuilder::new()
+        .add_character_field(FieldName::try_from("name").unwrap(), 40)
+        .add_numeric_field(FieldName::try_from("amount").unwrap(), 12, 3)
+        .add_logical_field(FieldName::try_from("flag").unwrap())
+        .add_date_field(FieldName::try_from("day").unwrap())
+        .build_with_dest(&mut cursor)
+        .write_records(&records)
+        .unwrap();
+    cursor.into_inner()
+}
+
+fn read_records(c: &mut Criterion) {
+    let bytes = table_bytes();
+    c.bench_function("read records from memory", |b| {
+        b.iter_batched(
+            || Reader::new(Cursor::new(bytes.clone())).unwrap(),
+            |mut reader| reader.read().unwrap(),
+            BatchSize::SmallInput,
+        )
+    });
+
+    // Each read of a std::fs::File is a system call,
+    // so this measures the number of reads done per record
+    let mut file = tempfile::NamedTempFile::new().unwrap();
+    file.write_all(&bytes).unwrap();
+    c.bench_function("read records from an unbuffered file", |b| {
+        b.iter_batched(
+            || Reader::new(std::fs::File::open(file.path()).unwrap()).unwrap(),
+            |mut reader| reader.read().unwrap(),
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+/// Compares [dbase::TableInfo::index_of] with a linear scan of the fields,
+/// looking up the last field of a 20-field table once per row of a 100k-row file
+fn look_up_fields(c: &mut Criterion) {
+    const NUM_ROWS: usize = 100_000;
+    let table_info = (0..20)
+        .fold(TableWriterBuilder::new(), |builder, i| {
+            builder.add_character_field(
+                FieldName::try_from(format!("field{}", i).as_str()).unwrap(),
+                10,
+            )
+        })
+        .build_table_info();
+    let name = "field19";
+
+    let mut group = c.benchmark_group("look up a field in a 20-field table");
+    group.bench_function("index_of", |b| {
+        b.iter(|| {
+            for _ in 0..NUM_ROWS {
+                black_box(table_info.index_of(black_box(name)));
+            }
+        })
+    });
+    group.bench_function("linear scan", |b| {
+        b.iter(|| {
+            for _ in 0..NUM_ROWS {
+                black_box(
+                    table_info
+                        .fields()
+                        .iter()
+                        .position(|info| info.name().eq_ignore_ascii_case(black_box(name))),
+                );
+            }
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, read_records, look_up_fields);
+criterion_main!(benches);
@@ -0,0 +1,118 @@
+//! Benchmarks of the writing of records, run them with `cargo bench --bench writing`
+use std::io::Cursor;
+
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use dbase::{FieldName, FieldValue, Record, TableWriterBuilder};
+
+const NUM_RECORDS: usize = 10_000;
+
+fn numeric_table() -> TableWriterBuilder {
+    TableWriterBuilder::new()
+        .add_numeric_field(FieldName::try_from("count").unwrap(), 10, 0)
+        .add_numeric_field(FieldName::try_from("amount").unwrap(), 18, 4)
+        .add_float_field(FieldName::try_from("ratio").unwrap(), 20, 10)
+}
+
+fn numeric_records() -> Vec<Record> {
+    (0..NUM_RECORDS)
+        .map(|i| {
+            let i = i as f64;
+            let mut record = Record::default();
+            record.insert("count".to_owned(), FieldValue::Numeric(Some(i)));
+            record.insert(
+                "amount".to_owned(),
+                FieldValue::Numeric(Some(i * 1234.5678 - 50_000.0)),
+            );
+            record.insert(
+                "ratio".to_owned(),
+                FieldValue::Float(Some((i / 7.0) as f32)),
+            );
+            record
+        })
+        .collect()
+}
+
+fn write_numeric_values(c: &mut Criterion) {
+    let records = numeric_records();
+    c.bench_function("write numeric values", |b| {
+        b.iter_batched(
+            || numeric_table().build_with_dest(Cursor::new(Vec::<u8>::new())),
+            |writer| writer.write_records(&records).unwrap(),
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+fn mixed_table() -> TableWriterBuilder {
+    TableWriterBuilder::new()
+        .add_character_field(FieldName::try_from("name").unwrap(), 20)
+        .add_numeric_field(FieldName::try_from("amount").unwrap(), 10, 2)
+        .add_logical_field(FieldName::try_from("flag").unwrap())
+        .add_integer_field(FieldName::try_from("count").unwrap())
+}
+
+fn mixed_rows() -> Vec<Vec<FieldValue>> {
+    (0..NUM_RECORDS)
+        .map(|i| {
+            vec![
+                FieldValue::Character(Some(format!("row {}", i))),
+                FieldValue::Numeric(Some(i as f64 / 4.0)),
+                FieldValue::Logical(Some(i % 3 == 0)),
+                FieldValue::Integer(i as i32),
+            ]
+        })
+        .collect()
+}
+
+fn prepared_writer_against_records(c: &mut Criterion) {
+    let rows = mixed_rows();
+    let names = ["name", "amount", "flag", "count"];
+    let records = rows
+        .iter()
+        .map(|row| {
+            let mut record = Record::default();
+            for (name, value) in names.iter().zip(row) {
+                record.insert(name.to_string(), value.clone());
+            }
+            record
+        })
+        .collect::<Vec<_>>();
+
+    let mut group = c.benchmark_group("write mixed values");
+    group.bench_function("PreparedWriter::push", |b| {
+        b.iter_batched(
+            || {
+                mixed_table()
+                    .build_prepared(Cursor::new(Vec::<u8>::new()))
+                    .unwrap()
+            },
+            |mut writer| {
+                for row in &rows {
+                    writer.push(row).unwrap();
+                }
+                writer.finish().unwrap()
+            },
+            BatchSize::SmallInput,
+        )
+    });
+    group.bench_function("TableWriter::write_record", |b| {
+        b.iter_batched(
+            || mixed_table().build_with_dest(Cursor::new(Vec::<u8>::new())),
+            |mut writer| {
+                for record in &records {
+                    writer.write_record(record).unwrap();
+                }
+                writer.finish().unwrap()
+            },
+            BatchSize::SmallInput,
+        )
+    });
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    write_numeric_values,
+    prepared_writer_against_records
+);
+criterion_main!(benches);
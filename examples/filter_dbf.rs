@@ -0,0 +1,58 @@
+//! Prints the records of a dBase file where a field has the given value
+//!
+//! `cargo run --example filter_dbf -- tests/data/stations.dbf line blue`
+use std::path::Path;
+
+use dbase::{FieldValue, Record, RecordFilter};
+
+fn filter_table<P: AsRef<Path>>(
+    path: P,
+    field_name: &str,
+    value: &str,
+) -> Result<Vec<Record>, Box<dyn std::error::Error>> {
+    let mut reader = dbase::Reader::from_path(path)?;
+    let field = reader
+        .fields()
+        .iter()
+        .find(|field| field.name().eq_ignore_ascii_case(field_name))
+        .ok_or_else(|| format!("There is no field named '{}'", field_name))?;
+    // The value is parsed as the type of the field, so that e.g. '1.50' matches 1.5
+    let value = FieldValue::parse(value, field.field_type()).map_err(|kind| kind.to_string())?;
+    let filter = RecordFilter::Eq(field.name().to_string(), value);
+
+    let records = reader.filter_with(filter).collect::<Result<Vec<_>, _>>()?;
+    Ok(records)
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut args = std::env::args().skip(1);
+    let (Some(dbf_path), Some(field_name), Some(value)) = (args.next(), args.next(), args.next())
+    else {
+        return Err("Usage: filter_dbf <path> <field name> <value>".into());
+    };
+
+    for record in filter_table(dbf_path, &field_name, &value)? {
+        let mut values = record
+            .into_iter()
+            .map(|(name, value)| format!("{}: {}", name, value.as_display()))
+            .collect::<Vec<_>>();
+        values.sort();
+        println!("{}", values.join(", "));
+    }
+    Ok(())
+}
+
+#[test]
+fn filter_stations() {
+    let records = filter_table("tests/data/stations.dbf", "LINE", "blue").unwrap();
+    assert!(!records.is_empty());
+    assert!(
+        records
+            .iter()
+            .all(|record| record.get("line")
+                == Some(&FieldValue::Character(Some("blue".to_string()))))
+    );
+
+    let error = filter_table("tests/data/stations.dbf", "color", "blue").unwrap_err();
+    assert_eq!(error.to_string(), "There is no field named 'color'");
+}
@@ -0,0 +1,55 @@
+//! Prints the fields and the records of a dBase file
+//!
+//! `cargo run --example read_dbf -- tests/data/stations.dbf`
+use std::io::Write;
+use std::path::Path;
+
+fn print_table<P: AsRef<Path>, W: Write>(
+    path: P,
+    out: &mut W,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut reader = dbase::Reader::from_path(path)?;
+
+    writeln!(out, "{} records", reader.header().num_records)?;
+    for field in reader.fields() {
+        writeln!(
+            out,
+            "{}: {:?} ({} bytes)",
+            field.name(),
+            field.field_type(),
+            field.length()
+        )?;
+    }
+
+    let names = reader
+        .fields()
+        .iter()
+        .map(|field| field.name().to_string())
+        .collect::<Vec<_>>();
+    for (i, record) in reader.iter_records().enumerate() {
+        let record = record?;
+        writeln!(out, "Record {}", i)?;
+        // The values are printed in the order of the fields
+        for name in &names {
+            if let Some(value) = record.get(name) {
+                writeln!(out, "\t{}: {}", name, value.as_display())?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let dbf_path = std::env::args().nth(1).expect("Path to file as first arg");
+    print_table(dbf_path, &mut std::io::stdout().lock())
+}
+
+#[test]
+fn print_stations() {
+    let mut out = Vec::new();
+    print_table("tests/data/stations.dbf", &mut out).unwrap();
+    let out = String::from_utf8(out).unwrap();
+    assert!(out.starts_with("86 records\nname: Character (254 bytes)\n"));
+    assert!(out.contains("Record 0\n\tname: Van Dorn Street\n"));
+    assert!(out.contains("Record 85\n"));
+}
@@ -0,0 +1,76 @@
+//! Writes a small dBase file with fields of different types, then reads it back
+//!
+//! `cargo run --example write_dbf -- people.dbf`
+use std::convert::TryFrom;
+use std::io::{Seek, Write};
+
+use dbase::{Date, FieldName, FieldValue, Record, TableWriterBuilder};
+
+const NAMES: [&str; 5] = ["Ferrys", "Alex", "Jamie", "Kim", "Sam"];
+
+fn write_people<W: Write + Seek>(dst: W) -> Result<W, dbase::Error> {
+    let mut writer = TableWriterBuilder::new()
+        .add_character_field(FieldName::try_from("name").unwrap(), 20)
+        .add_numeric_field(FieldName::try_from("height").unwrap(), 6, 2)
+        .add_logical_field(FieldName::try_from("member").unwrap())
+        .add_date_field(FieldName::try_from("birthday").unwrap())
+        .add_integer_field(FieldName::try_from("visits").unwrap())
+        .build_with_dest(dst);
+
+    for (i, name) in NAMES.iter().enumerate() {
+        let mut record = Record::default();
+        record.insert(
+            "name".to_string(),
+            FieldValue::Character(Some(name.to_string())),
+        );
+        record.insert(
+            "height".to_string(),
+            FieldValue::Numeric(Some(1.5 + i as f64 / 10.0)),
+        );
+        record.insert("member".to_string(), FieldValue::Logical(Some(i % 2 == 0)));
+        record.insert(
+            "birthday".to_string(),
+            FieldValue::Date(Some(Date::new(i as u32 + 1, 6, 1990))),
+        );
+        record.insert("visits".to_string(), FieldValue::Integer(i as i32 * 3));
+        writer.write_record(&record)?;
+    }
+    writer.finish()
+}
+
+/// Reads the records back, checking that they are the ones written
+fn check_people<T: std::io::Read + Seek>(source: T) -> Result<Vec<Record>, dbase::Error> {
+    let mut reader = dbase::Reader::new(source)?;
+    let records = reader.read()?;
+    assert_eq!(records.len(), NAMES.len());
+    for (record, name) in records.iter().zip(NAMES) {
+        assert_eq!(
+            record.get("name"),
+            Some(&FieldValue::Character(Some(name.to_string())))
+        );
+    }
+    Ok(records)
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let dbf_path = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| "people.dbf".to_string());
+    write_people(std::fs::File::create(&dbf_path)?)?;
+
+    let records = check_people(std::io::BufReader::new(std::fs::File::open(&dbf_path)?))?;
+    println!("Wrote {} records to {}", records.len(), dbf_path);
+    Ok(())
+}
+
+#[test]
+fn write_and_read_back() {
+    let mut cursor = write_people(std::io::Cursor::new(Vec::<u8>::new())).unwrap();
+    cursor.set_position(0);
+    let records = check_people(cursor).unwrap();
+    assert_eq!(records[4].get("visits"), Some(&FieldValue::Integer(12)));
+    assert_eq!(
+        records[1].get("member"),
+        Some(&FieldValue::Logical(Some(false)))
+    );
+}
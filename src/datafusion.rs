@@ -1,8 +1,9 @@
+use crate::field::types::currency_from_scaled;
 use crate::{file::BufReadWriteFile, FieldType, FieldValue, File as DbaseFile};
 use async_trait::async_trait;
 use datafusion::arrow::array::{
-    ArrayBuilder, ArrayRef, BooleanBuilder, Date32Builder, Float32Builder, Float64Builder,
-    Int32Builder, Int64Builder, StringBuilder,
+    ArrayBuilder, ArrayRef, BinaryBuilder, BooleanBuilder, Date32Builder, Float32Builder,
+    Float64Builder, Int32Builder, Int64Builder, StringBuilder,
 };
 use datafusion::arrow::datatypes::{DataType, Field, Schema, SchemaRef};
 use datafusion::arrow::record_batch::RecordBatch;
@@ -92,8 +93,12 @@ impl TableProvider for DbaseTable {
                     FieldType::Double => DataType::Float64,
                     FieldType::Float => DataType::Float32,
                     FieldType::Integer => DataType::Int32,
+                    FieldType::AutoIncrement => DataType::Int32,
+                    FieldType::Timestamp => DataType::Int64,
+                    FieldType::DBase7Double => DataType::Float64,
                     FieldType::Logical => DataType::Boolean,
                     FieldType::Memo => DataType::Utf8,
+                    FieldType::Picture => DataType::Binary,
                     FieldType::Numeric => DataType::Float64,
                 };
                 Field::new(field.name().to_lowercase(), ftype, true)
@@ -231,6 +236,10 @@ impl ExecutionPlan for DbaseExec {
                 DataType::Boolean => {
                     column_builders.push(Box::new(BooleanBuilder::with_capacity(num_records)))
                 }
+                DataType::Binary => column_builders.push(Box::new(BinaryBuilder::with_capacity(
+                    num_records,
+                    num_records * 1024,
+                ))), // assume 1KiB per picture
                 _ => panic!("Unsupported field type"),
             };
         }
@@ -271,7 +280,7 @@ impl ExecutionPlan for DbaseExec {
                         .as_any_mut()
                         .downcast_mut::<Float64Builder>()
                         .unwrap()
-                        .append_value(f),
+                        .append_value(currency_from_scaled(f)),
                     FieldValue::Date(d) => match d {
                         Some(d) => column_builders[j]
                             .as_any_mut()
@@ -331,6 +340,11 @@ impl ExecutionPlan for DbaseExec {
                         .downcast_mut::<StringBuilder>()
                         .unwrap()
                         .append_value(m.escape_default().to_string()),
+                    FieldValue::Picture(p) => column_builders[j]
+                        .as_any_mut()
+                        .downcast_mut::<BinaryBuilder>()
+                        .unwrap()
+                        .append_value(p),
                     FieldValue::Numeric(n) => match n {
                         Some(n) => column_builders[j]
                             .as_any_mut()
@@ -0,0 +1,146 @@
+//! Module with the definition of the [DatabaseContainer], the Visual FoxPro
+//! database (.dbc) that tables can belong to.
+use std::collections::HashMap;
+use std::io::{Read, Seek};
+
+use crate::error::{Error, ErrorKind};
+use crate::field::types::FieldValue;
+use crate::reading::{FieldIterator, ReadableRecord, Reader};
+use crate::FieldIOError;
+
+/// Visual FoxPro database container (.dbc)
+///
+/// Tables that belong to a database have a link to their .dbc file in their header.
+/// The .dbc is itself a dBase file, that stores the properties of the tables
+/// that the field descriptors cannot hold, like field names longer than 10 characters.
+///
+/// Only the names of the tables and of their fields are read,
+/// so the memo file of the database (.dct) is not needed.
+///
+/// [Reader::from_path] reads the database of the table when it finds it,
+/// and sets the [long names](crate::FieldInfo::long_name) of the fields.
+///
+/// # Example
+///
+/// ```
+/// # fn main() -> Result<(), dbase::Error> {
+/// let database = dbase::DatabaseContainer::from_path("tests/data/sales.dbc")?;
+/// let names = database.field_names("customers").unwrap();
+/// assert_eq!(names[0], "customer_identifier");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct DatabaseContainer {
+    /// Name of the tables, in lowercase, and the names of their fields in order
+    tables: HashMap<String, Vec<String>>,
+}
+
+impl DatabaseContainer {
+    /// Reads the database container at the given path
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn from_path<P: AsRef<std::path::Path>>(path: P) -> Result<Self, Error> {
+        let file = std::fs::File::open(path).map_err(|error| Error::io_error(error, 0))?;
+        Self::from_reader(Reader::new(std::io::BufReader::new(file))?)
+    }
+
+    /// Reads the database container from the reader of the .dbc file
+    pub fn from_reader<T: Read + Seek>(mut reader: Reader<T>) -> Result<Self, Error> {
+        let objects = reader.read_as::<DatabaseObject>()?;
+
+        let mut table_names = HashMap::new();
+        for object in objects
+            .iter()
+            .filter(|object| object.object_type == "Table")
+        {
+            table_names.insert(object.id, object.name.to_lowercase());
+        }
+        let mut tables = HashMap::<String, Vec<String>>::new();
+        for name in table_names.values() {
+            tables.insert(name.clone(), vec![]);
+        }
+        // The fields of a table are stored in the order of the columns
+        for object in objects
+            .iter()
+            .filter(|object| object.object_type == "Field")
+        {
+            let table_name = table_names.get(&object.parent_id).ok_or_else(|| Error {
+                record_num: 0,
+                field: None,
+                kind: ErrorKind::Message(format!(
+                    "The field '{}' of the database does not belong to a table",
+                    object.name
+                )),
+            })?;
+            if let Some(fields) = tables.get_mut(table_name) {
+                fields.push(object.name.clone());
+            }
+        }
+        Ok(Self { tables })
+    }
+
+    /// Returns the names of the tables of the database, in lowercase
+    pub fn table_names(&self) -> impl Iterator<Item = &str> {
+        self.tables.keys().map(String::as_str)
+    }
+
+    /// Returns the names of the fields of the table, in the order of the columns,
+    /// `None` if the table is not in the database
+    ///
+    /// The name of the table is not case sensitive.
+    pub fn field_names(&self, table_name: &str) -> Option<&[String]> {
+        self.tables
+            .get(&table_name.to_lowercase())
+            .map(Vec::as_slice)
+    }
+}
+
+/// A record of the .dbc file, which describes an object of the database
+#[derive(Debug, Default)]
+struct DatabaseObject {
+    id: i32,
+    parent_id: i32,
+    object_type: String,
+    name: String,
+}
+
+impl ReadableRecord for DatabaseObject {
+    fn read_using<Source, MemoSource>(
+        field_iterator: &mut FieldIterator<Source, MemoSource>,
+    ) -> Result<Self, FieldIOError>
+    where
+        Source: Read + Seek,
+        MemoSource: Read + Seek,
+    {
+        let mut object = Self::default();
+        while let Some(field_info) = field_iterator.fields_info.peek().copied() {
+            let name = field_info.name().to_ascii_uppercase();
+            if !matches!(
+                name.as_str(),
+                "OBJECTID" | "PARENTID" | "OBJECTTYPE" | "OBJECTNAME"
+            ) {
+                // The other fields are memos, the .dct file is not read
+                field_iterator.skip_next_field()?;
+                continue;
+            }
+            let value = field_iterator.read_next_field()?.value;
+            match (name.as_str(), value) {
+                ("OBJECTID", FieldValue::Integer(id)) => object.id = id,
+                ("PARENTID", FieldValue::Integer(id)) => object.parent_id = id,
+                ("OBJECTTYPE", FieldValue::Character(text)) => {
+                    object.object_type = text.unwrap_or_default()
+                }
+                ("OBJECTNAME", FieldValue::Character(text)) => {
+                    object.name = text.unwrap_or_default()
+                }
+                _ => {
+                    return Err(FieldIOError::new(
+                        ErrorKind::IncompatibleType,
+                        Some(field_info.clone()),
+                    ))
+                }
+            }
+        }
+        Ok(object)
+    }
+}
@@ -1,8 +1,11 @@
 //! Support for working with different codepages / encodings.
 
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::string::String;
+use core::fmt::Debug;
+
 use crate::error::{DecodeError, EncodeError};
-use std::borrow::Cow;
-use std::fmt::Debug;
 
 pub trait AsCodePageMark {
     fn code_page_mark(&self) -> crate::CodePageMark;
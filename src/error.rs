@@ -1,21 +1,24 @@
-use crate::{CodePageMark, FieldConversionError, FieldInfo};
-use std::string::FromUtf8Error;
+use alloc::string::{FromUtf8Error, String};
+
+use crate::{CodePageMark, FieldConversionError, FieldInfo, FieldType, Version};
 
 #[derive(Debug)]
 #[non_exhaustive]
 pub enum ErrorKind {
     /// Wrapper of `std::io::Error` to forward any reading/writing error
+    #[cfg(feature = "std")]
     IoError(std::io::Error),
     /// Wrapper to forward errors whe trying to parse a float from the file
-    ParseFloatError(std::num::ParseFloatError),
+    ParseFloatError(core::num::ParseFloatError),
     /// Wrapper to forward errors whe trying to parse an integer value from the file
-    ParseIntError(std::num::ParseIntError),
+    ParseIntError(core::num::ParseIntError),
     /// The Field as an invalid FieldType
     InvalidFieldType(char),
     /// Happens when at least one field is a Memo type
     /// and the that additional memo file could not be found / was not given
     MissingMemoFile,
     /// Something went wrong when we tried to open the associated memo file
+    #[cfg(feature = "std")]
     ErrorOpeningMemoFile(std::io::Error),
     /// The conversion from a FieldValue to another type could not be made
     BadConversion(FieldConversionError),
@@ -34,6 +37,102 @@ pub enum ErrorKind {
     StringDecodeError(DecodeError),
     /// A string from the database could not be encoded
     StringEncodeError(EncodeError),
+    /// The size of a record written in the header does not match
+    /// the size computed from the fields
+    RecordSizeMismatch {
+        header: u16,
+        computed: usize,
+    },
+    /// There is no field with the given name
+    UnknownFieldName(String),
+    /// Two fields have the same name
+    DuplicateFieldName {
+        name: String,
+    },
+    /// The month or day of a date is not valid
+    InvalidDate,
+    /// The date cannot be stored in a dBase file
+    DateOutOfRange {
+        year: u32,
+        month: u32,
+        day: u32,
+    },
+    /// The value cannot be coerced to the requested field type
+    IncompatibleCoercion {
+        from: FieldType,
+        to: FieldType,
+    },
+    /// The string is not a valid representation of a value of the field type
+    ParseError {
+        value: String,
+        field_type: FieldType,
+    },
+    /// The index file is not valid
+    InvalidIndexFile(String),
+    /// There is no tag with the given name in the index file
+    UnknownIndexTag(String),
+    /// The version byte of the file is not one of the versions supported,
+    /// see [Version::is_supported](crate::Version::is_supported)
+    UnsupportedVersion(u8),
+    /// The number does not fit in the field: its text representation is longer
+    /// than a Numeric or Float field, or it is too large for a Currency field
+    ///
+    /// For Numeric and Float fields, see [NumericOverflowPolicy](crate::NumericOverflowPolicy)
+    /// to fill the field with asterisks instead, like dBase does.
+    ///
+    /// When reading, a Numeric or Float field only made of asterisks is an overflow,
+    /// whose value is unknown (`None`), see [OverflowPolicy](crate::OverflowPolicy).
+    NumericOverflow {
+        value: Option<f64>,
+        width: u8,
+    },
+    /// A block of the memo file is compressed or encoded in a way that is not supported,
+    /// holds the type of the block
+    UnsupportedMemoCompression(u32),
+    /// The file ends inside the record at `index`
+    TruncatedRecord {
+        index: usize,
+    },
+    /// The header of the file does not describe any field
+    NoFields,
+    /// The type of a field cannot be stored in files of the version of the table,
+    /// see [Version::supports_field_type](crate::Version::supports_field_type)
+    UnsupportedFieldTypeForVersion {
+        field_type: FieldType,
+        version: Version,
+    },
+    /// The number of decimal places of a Numeric or Float field does not leave room
+    /// for the decimal point and at least one digit before it
+    InvalidDecimalPlaces {
+        length: u8,
+        num_decimal_places: u8,
+    },
+    /// The field descriptors of the header are not terminated by a `0x0D` byte
+    /// before the first record, or do not fit before it
+    MalformedDescriptors,
+    /// The fields of the table make a value of the header that does not fit in
+    /// its 16 bits, the size of the records or the offset of the first record
+    HeaderFieldOverflow {
+        name: &'static str,
+        value: u32,
+    },
+    /// There is no record at `index`, the table has `num_records` records
+    RecordIndexOutOfRange {
+        index: usize,
+        num_records: u32,
+    },
+    /// The value cannot be stored in a field, like a NaN or infinite number
+    /// in a Numeric, Float or Double field, holds the text of the value
+    InvalidFieldValue(String),
+    /// The data ends before the end of the header and field descriptors
+    TruncatedHeader,
+    /// The bytes given for a field are not as long as the field
+    FieldLengthMismatch {
+        expected: usize,
+        actual: usize,
+    },
+    /// The name cannot be the name of a field, see [FieldInfo::with_name](crate::FieldInfo::with_name)
+    InvalidFieldName(String),
     Message(String),
 }
 
@@ -54,6 +153,7 @@ impl Error {
         }
     }
 
+    #[cfg(feature = "std")]
     pub(crate) fn io_error(error: std::io::Error, current_record: usize) -> Self {
         Self {
             record_num: current_record,
@@ -92,6 +192,7 @@ impl FieldIOError {
         Self { field, kind }
     }
 
+    #[cfg(feature = "std")]
     pub(crate) fn end_of_record() -> Self {
         Self {
             field: None,
@@ -104,20 +205,21 @@ impl FieldIOError {
     }
 }
 
+#[cfg(feature = "std")]
 impl From<std::io::Error> for ErrorKind {
     fn from(e: std::io::Error) -> Self {
         ErrorKind::IoError(e)
     }
 }
 
-impl From<std::num::ParseFloatError> for ErrorKind {
-    fn from(p: std::num::ParseFloatError) -> Self {
+impl From<core::num::ParseFloatError> for ErrorKind {
+    fn from(p: core::num::ParseFloatError) -> Self {
         ErrorKind::ParseFloatError(p)
     }
 }
 
-impl From<std::num::ParseIntError> for ErrorKind {
-    fn from(p: std::num::ParseIntError) -> Self {
+impl From<core::num::ParseIntError> for ErrorKind {
+    fn from(p: core::num::ParseIntError) -> Self {
         ErrorKind::ParseIntError(p)
     }
 }
@@ -146,8 +248,8 @@ impl From<FieldConversionError> for FieldIOError {
     }
 }
 
-impl std::fmt::Display for Error {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         if let Some(field_info) = &self.field {
             write!(
                 f,
@@ -170,11 +272,13 @@ impl std::fmt::Display for Error {
 //     }
 // }
 
+#[cfg(feature = "std")]
 impl std::error::Error for Error {}
 
-impl std::fmt::Display for ErrorKind {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
+            #[cfg(feature = "std")]
             ErrorKind::IoError(err) => write!(f, "An I/O error happened: {}", err),
             ErrorKind::ParseFloatError(err) => {
                 write!(f, "Float value could not be obtained: {}", err)
@@ -186,6 +290,7 @@ impl std::fmt::Display for ErrorKind {
                 write!(f, "The FieldType code '{}' is note a valid one", c)
             }
             ErrorKind::MissingMemoFile => write!(f, "The memo file could not be found"),
+            #[cfg(feature = "std")]
             ErrorKind::ErrorOpeningMemoFile(err) => {
                 write!(
                     f,
@@ -218,13 +323,110 @@ impl std::fmt::Display for ErrorKind {
             ErrorKind::UnsupportedCodePage(code) => {
                 write!(f, "The code page '{:?}' is not supported", code)
             }
+            ErrorKind::RecordSizeMismatch { header, computed } => write!(
+                f,
+                "The record size in the header ({}) does not match the size computed from the fields ({})",
+                header, computed
+            ),
+            ErrorKind::UnknownFieldName(name) => write!(f, "There is no field named '{}'", name),
+            ErrorKind::DuplicateFieldName { name } => {
+                write!(f, "There is more than one field named '{}'", name)
+            }
+            ErrorKind::InvalidDate => write!(f, "The date is not a valid date"),
+            ErrorKind::DateOutOfRange { year, month, day } => write!(
+                f,
+                "The date {:04}-{:02}-{:02} cannot be stored in a dBase file",
+                year, month, day
+            ),
+            ErrorKind::IncompatibleCoercion { from, to } => {
+                write!(f, "A {} value cannot be coerced to {}", from, to)
+            }
+            ErrorKind::ParseError { value, field_type } => {
+                write!(f, "'{}' is not a valid {} value", value, field_type)
+            }
+            ErrorKind::InvalidIndexFile(msg) => write!(f, "The index file is not valid: {}", msg),
+            ErrorKind::UnknownIndexTag(name) => {
+                write!(f, "There is no tag named '{}' in the index", name)
+            }
+            ErrorKind::UnsupportedVersion(version) => {
+                write!(f, "The file version 0x{:02x} is not supported", version)
+            }
+            ErrorKind::NumericOverflow {
+                value: Some(value),
+                width,
+            } => write!(
+                f,
+                "The number {} does not fit in a field of {} bytes",
+                value, width
+            ),
+            ErrorKind::NumericOverflow { value: None, width } => write!(
+                f,
+                "The number stored in a field of {} bytes overflowed it",
+                width
+            ),
+            ErrorKind::UnsupportedMemoCompression(block_type) => write!(
+                f,
+                "The memo block of type {} is compressed or encoded in an unsupported way",
+                block_type
+            ),
+            ErrorKind::TruncatedRecord { index } => {
+                write!(f, "The file ends inside the record {}", index)
+            }
+            ErrorKind::UnsupportedFieldTypeForVersion {
+                field_type,
+                version,
+            } => write!(
+                f,
+                "{} fields cannot be stored in files of version 0x{:02x}",
+                field_type,
+                u8::from(*version)
+            ),
+            ErrorKind::InvalidDecimalPlaces {
+                length,
+                num_decimal_places,
+            } => write!(
+                f,
+                "A numeric field of {} bytes cannot have {} decimal places",
+                length, num_decimal_places
+            ),
+            ErrorKind::NoFields => write!(f, "The file does not have any field"),
+            ErrorKind::MalformedDescriptors => write!(
+                f,
+                "The field descriptors are not terminated before the first record"
+            ),
+            ErrorKind::HeaderFieldOverflow { name, value } => write!(
+                f,
+                "The {} of the header would be {}, more than {}",
+                name,
+                value,
+                u16::MAX
+            ),
+            ErrorKind::RecordIndexOutOfRange { index, num_records } => write!(
+                f,
+                "There is no record {}, the table has {} records",
+                index, num_records
+            ),
+            ErrorKind::InvalidFieldValue(value) => {
+                write!(f, "The value {} cannot be stored in a dBase field", value)
+            }
+            ErrorKind::TruncatedHeader => {
+                write!(f, "The data ends before the end of the header")
+            }
+            ErrorKind::FieldLengthMismatch { expected, actual } => write!(
+                f,
+                "The field is {} bytes long, but {} bytes were given",
+                expected, actual
+            ),
+            ErrorKind::InvalidFieldName(name) => {
+                write!(f, "'{}' is not a valid field name", name)
+            }
             ErrorKind::Message(ref msg) => write!(f, "{}", msg),
         }
     }
 }
 
-impl std::fmt::Display for FieldIOError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for FieldIOError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         if let Some(field_info) = &self.field {
             write!(f, "FieldIOError {{ kind: {}, {} }}", self.kind, field_info)
         } else {
@@ -233,6 +435,7 @@ impl std::fmt::Display for FieldIOError {
     }
 }
 
+#[cfg(feature = "std")]
 impl std::error::Error for FieldIOError {}
 
 #[derive(Debug)]
@@ -259,12 +462,13 @@ impl From<yore::DecodeError> for DecodeError {
     }
 }
 
-impl std::fmt::Display for DecodeError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "{:?}", self)
     }
 }
 
+#[cfg(feature = "std")]
 impl std::error::Error for DecodeError {}
 
 #[derive(Debug)]
@@ -289,10 +493,11 @@ impl From<yore::EncodeError> for EncodeError {
     }
 }
 
-impl std::fmt::Display for EncodeError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for EncodeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "{:?}", self)
     }
 }
 
+#[cfg(feature = "std")]
 impl std::error::Error for EncodeError {}
@@ -1,3 +1,5 @@
+use alloc::string::String;
+
 use super::{types, FieldType, FieldValue};
 
 /// Errors that can happen when trying to convert a FieldValue into
@@ -18,8 +20,8 @@ pub enum FieldConversionError {
     NoneValue,
 }
 
-impl std::fmt::Display for FieldConversionError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for FieldConversionError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             FieldConversionError::FieldTypeNotAsExpected { expected, actual } => {
                 write!(f, "Cannot convert from {} to {}", expected, actual)
@@ -32,6 +34,7 @@ impl std::fmt::Display for FieldConversionError {
     }
 }
 
+#[cfg(feature = "std")]
 impl std::error::Error for FieldConversionError {}
 
 macro_rules! impl_try_from_field_value_for_ {
@@ -92,7 +95,7 @@ impl TryFrom<FieldValue> for f64 {
         match value {
             FieldValue::Numeric(Some(v)) => Ok(v),
             FieldValue::Numeric(None) => Err(FieldConversionError::NoneValue),
-            FieldValue::Currency(c) => Ok(c),
+            FieldValue::Currency(c) => Ok(types::currency_from_scaled(c)),
             FieldValue::Double(d) => Ok(d),
             _ => Err(FieldConversionError::IncompatibleType),
         }
@@ -1,19 +1,30 @@
-use std::convert::TryFrom;
+use alloc::borrow::ToOwned;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::ops::Index;
+use core::slice::SliceIndex;
+#[cfg(feature = "std")]
 use std::io::{Read, Write};
-use std::ops::Index;
-use std::slice::SliceIndex;
 
+#[cfg(feature = "std")]
 use byteorder::{ReadBytesExt, WriteBytesExt};
 
 mod conversion;
 pub mod types;
 
 use self::types::FieldType;
-use crate::{Encoding, ErrorKind, FieldValue};
+use crate::header::{Header, Version};
+use crate::{Encoding, Error, ErrorKind, FieldValue};
 pub use conversion::FieldConversionError;
 
 pub(crate) const DELETION_FLAG_SIZE: usize = 1; // 1 byte
-const FIELD_NAME_LENGTH: usize = 11;
+/// Value of the byte between the last RecordFieldInfo and the first record
+pub(crate) const TERMINATOR_VALUE: u8 = 0x0D;
+pub(crate) const FIELD_NAME_LENGTH: usize = 11;
+pub(crate) const LEVEL7_FIELD_NAME_LENGTH: usize = 32;
+/// Name of the hidden field where Visual FoxPro stores which values are null
+const NULL_FLAGS_FIELD_NAME: &str = "_NullFlags";
 
 #[derive(Debug)]
 /// Wrapping struct to create a FieldName from a String.
@@ -29,7 +40,7 @@ const FIELD_NAME_LENGTH: usize = 11;
 /// let name = FieldName::try_from("Small Name");
 /// assert!(name.is_ok())
 /// ```
-pub struct FieldName(String);
+pub struct FieldName(pub(crate) String);
 
 impl TryFrom<&str> for FieldName {
     type Error = &'static str;
@@ -52,19 +63,54 @@ pub struct FieldInfo {
     pub(crate) field_type: FieldType,
     pub(crate) displacement_field: [u8; 4],
     pub(crate) field_length: u8,
+    /// Length of Character fields longer than 255 bytes,
+    /// whose length is stored in the length and number of decimal places bytes
+    pub(crate) wide_record_length: Option<u16>,
     pub(crate) num_decimal_places: u8,
     pub(crate) flags: FieldFlags,
     pub(crate) autoincrement_next_val: [u8; 5],
     pub(crate) autoincrement_step: u8,
+    /// Position of the field in the records, computed from the fields before it
+    pub(crate) offset_in_record: u16,
+    /// Name of the field found in the database container of the table
+    pub(crate) long_name: Option<String>,
 }
 
 impl FieldInfo {
     pub(crate) const SIZE: usize = 32;
+    pub(crate) const LEVEL7_SIZE: usize = 48;
+
+    /// Returns the size of a field descriptor in files of the given version
+    pub(crate) fn size_for(file_type: Version) -> usize {
+        if file_type.is_dbase7() {
+            Self::LEVEL7_SIZE
+        } else {
+            Self::SIZE
+        }
+    }
 
     pub fn name(&self) -> &str {
         &self.name
     }
 
+    /// Renames the field
+    ///
+    /// The [long name](Self::long_name) of the field is forgotten.
+    pub fn set_name(&mut self, name: FieldName) {
+        self.name = name.0;
+        self.long_name = None;
+    }
+
+    /// Returns the name of the field stored in the
+    /// [database container](crate::DatabaseContainer) of the table, if it is known
+    ///
+    /// Visual FoxPro tables that belong to a database can have field names
+    /// longer than 10 characters, the field descriptor only holds their
+    /// first characters, returned by [Self::name].
+    pub fn long_name(&self) -> Option<&str> {
+        self.long_name.as_deref()
+    }
+
     pub fn field_type(&self) -> FieldType {
         self.field_type
     }
@@ -73,73 +119,226 @@ impl FieldInfo {
         self.field_length
     }
 
-    pub(crate) fn new(name: FieldName, field_type: FieldType, length: u8) -> Self {
+    /// Returns the length of Character fields longer than 255 bytes,
+    /// `None` for other fields
+    ///
+    /// The length of such fields does not fit in the length byte of the field descriptor,
+    /// Clipper and FoxPro store it using the number of decimal places byte as high byte.
+    /// [Self::length] only returns the low byte for these fields.
+    pub fn wide_record_length(&self) -> Option<u16> {
+        self.wide_record_length
+    }
+
+    /// Returns the number of decimal places of Numeric and Float fields
+    ///
+    /// 0 for Character fields longer than 255 bytes, which use this byte
+    /// to store their [length](Self::wide_record_length).
+    pub fn num_decimal_places(&self) -> u8 {
+        if self.wide_record_length.is_some() {
+            0
+        } else {
+            self.num_decimal_places
+        }
+    }
+
+    /// Returns the number of bytes of the field in the records
+    ///
+    /// This is the [length](Self::length), or the [wide length](Self::wide_record_length)
+    /// of Character fields longer than 255 bytes.
+    pub fn record_length(&self) -> usize {
+        self.wide_record_length
+            .map_or(self.field_length as usize, usize::from)
+    }
+
+    /// Returns the position, in bytes, of the field in the records
+    ///
+    /// It is computed from the lengths of the fields before it,
+    /// the first field is at 1, after the deletion flag.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let reader = dbase::Reader::from_path("tests/data/stations.dbf")?;
+    /// let fields = reader.fields();
+    /// assert_eq!(fields[0].offset_within_record(), 1);
+    /// assert_eq!(
+    ///     fields[1].offset_within_record() as usize,
+    ///     1 + fields[0].record_length()
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn offset_within_record(&self) -> u16 {
+        self.offset_in_record
+    }
+
+    /// Returns the next value that will be given to an
+    /// [AutoIncrement](FieldType::AutoIncrement) field,
+    /// `None` if the field is not an auto increment field.
+    pub fn autoincrement_next_value(&self) -> Option<u32> {
+        if self.field_type == FieldType::AutoIncrement {
+            let mut le_bytes = [0u8; core::mem::size_of::<u32>()];
+            le_bytes.copy_from_slice(&self.autoincrement_next_val[..core::mem::size_of::<u32>()]);
+            Some(u32::from_le_bytes(le_bytes))
+        } else {
+            None
+        }
+    }
+
+    /// Creates the information of a field like [FieldInfo::new], checking that
+    /// the name is valid for dBase III
+    ///
+    /// [FieldName] only limits the number of bytes, which lets other programs' names through.
+    /// Here the name must be at most 10 bytes, made of ASCII letters, digits
+    /// and underscores, must not start with a digit, and must not be `_NullFlags`,
+    /// the name Visual FoxPro gives to its hidden field.
+    /// Otherwise this fails with [ErrorKind::InvalidFieldName].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dbase::{ErrorKind, FieldInfo, FieldType};
+    ///
+    /// let info = FieldInfo::with_name("CUSTOMER", FieldType::Character, 20).unwrap();
+    /// assert_eq!(info.name(), "CUSTOMER");
+    ///
+    /// let error = FieldInfo::with_name("1ST_NAME", FieldType::Character, 20).unwrap_err();
+    /// assert!(matches!(error.kind(), ErrorKind::InvalidFieldName(name) if name == "1ST_NAME"));
+    /// ```
+    pub fn with_name(name: &str, field_type: FieldType, length: u8) -> Result<Self, Error> {
+        let is_valid = !name.is_empty()
+            && name.len() < FIELD_NAME_LENGTH
+            && name.bytes().all(|c| c.is_ascii_alphanumeric() || c == b'_')
+            && !name.starts_with(|c: char| c.is_ascii_digit())
+            && !name.eq_ignore_ascii_case(NULL_FLAGS_FIELD_NAME);
+        if !is_valid {
+            return Err(Error {
+                record_num: 0,
+                field: None,
+                kind: ErrorKind::InvalidFieldName(name.to_string()),
+            });
+        }
+        Ok(Self::new(FieldName(name.to_string()), field_type, length))
+    }
+
+    /// Creates the information of a field, without decimal places,
+    /// for example to add a column with [File::add_column](crate::File::add_column)
+    ///
+    /// `length` is the number of bytes of the field in the records.
+    pub fn new(name: FieldName, field_type: FieldType, length: u8) -> Self {
         Self {
             name: name.0,
             field_type,
             displacement_field: [0u8; 4],
             field_length: length,
+            wide_record_length: None,
             num_decimal_places: 0,
             flags: FieldFlags::default(),
             autoincrement_next_val: [0u8; 5],
             autoincrement_step: 0u8,
+            offset_in_record: 0,
+            long_name: None,
         }
     }
 
-    pub(crate) fn read_from<T: Read>(source: &mut T) -> Result<Self, ErrorKind> {
-        Self::read_with_encoding(source, &crate::encoding::Ascii)
-    }
-
-    /// Reads with the given encoding.
-    ///
-    /// The encoding is used only for the name
-    fn read_with_encoding<T: Read, E: Encoding>(
-        source: &mut T,
-        encoding: &E,
-    ) -> Result<Self, ErrorKind> {
-        let mut name = [0u8; FIELD_NAME_LENGTH];
-        source.read_exact(&mut name)?;
-        let field_type = source.read_u8()?;
+    /// Parses a field descriptor, `bytes` must hold at least [Self::SIZE] bytes
+    pub(crate) fn from_bytes(bytes: &[u8]) -> Result<Self, ErrorKind> {
+        let name = &bytes[..FIELD_NAME_LENGTH];
+        let field_type = bytes[11];
 
         let mut displacement_field = [0u8; 4];
-        source.read_exact(&mut displacement_field)?;
+        displacement_field.copy_from_slice(&bytes[12..16]);
 
-        let record_length = source.read_u8()?;
-        let num_decimal_places = source.read_u8()?;
+        let record_length = bytes[16];
+        let num_decimal_places = bytes[17];
 
-        let flags = FieldFlags(source.read_u8()?);
+        let flags = FieldFlags(bytes[18]);
 
         let mut autoincrement_next_val = [0u8; 5];
+        autoincrement_next_val.copy_from_slice(&bytes[19..24]);
+        let autoincrement_step = bytes[24];
+        // 7 reserved bytes
 
-        source.read_exact(&mut autoincrement_next_val)?;
-        let autoincrement_step = source.read_u8()?;
-
-        let mut _reserved = [0u8; 7];
-        source.read_exact(&mut _reserved)?;
-
-        let s = encoding
-            .decode(&name)?
+        let s = crate::encoding::Ascii
+            .decode(name)?
             .trim_matches(|c| c == '\u{0}')
             .to_owned();
 
         let field_type = FieldType::try_from(field_type as char)?;
+        // Clipper and FoxPro store the length of Character fields longer
+        // than 255 bytes on two bytes, using the number of decimal places as high byte
+        let wide_record_length = if field_type == FieldType::Character && num_decimal_places != 0 {
+            Some(u16::from_le_bytes([record_length, num_decimal_places]))
+        } else {
+            None
+        };
 
         Ok(Self {
             name: s,
             field_type,
             displacement_field,
             field_length: record_length,
+            wide_record_length,
             num_decimal_places,
             flags,
             autoincrement_next_val,
             autoincrement_step,
+            offset_in_record: 0,
+            long_name: None,
         })
     }
 
+    /// Parses a field descriptor as found in dBase Level 7 files,
+    /// `bytes` must hold at least [Self::LEVEL7_SIZE] bytes
+    ///
+    /// Compared to the other versions, the name is longer (32 bytes)
+    /// and the next value of auto increment fields is stored differently.
+    pub(crate) fn from_level7_bytes(bytes: &[u8]) -> Result<Self, ErrorKind> {
+        let name = &bytes[..LEVEL7_FIELD_NAME_LENGTH];
+        let field_type = bytes[32];
+        let record_length = bytes[33];
+        let num_decimal_places = bytes[34];
+        // 2 reserved bytes, the production mdx flag, 2 reserved bytes
+
+        let mut autoincrement_next_val = [0u8; 5];
+        autoincrement_next_val[..core::mem::size_of::<u32>()].copy_from_slice(&bytes[40..44]);
+        // 4 reserved bytes
+
+        let s = crate::encoding::Ascii
+            .decode(name)?
+            .trim_matches(|c| c == '\u{0}')
+            .to_owned();
+
+        let field_type = FieldType::try_from(field_type as char)?;
+
+        Ok(Self {
+            name: s,
+            field_type,
+            displacement_field: [0u8; 4],
+            field_length: record_length,
+            wide_record_length: None,
+            num_decimal_places,
+            flags: FieldFlags::default(),
+            autoincrement_next_val,
+            autoincrement_step: 0u8,
+            offset_in_record: 0,
+            long_name: None,
+        })
+    }
+
+    /// Returns the bytes of the name written in a descriptor,
+    /// which holds at most `max_length` bytes
+    #[cfg(feature = "std")]
+    pub(crate) fn descriptor_name(&self, max_length: usize) -> &[u8] {
+        let bytes = self.name.as_bytes();
+        &bytes[..bytes.len().min(max_length)]
+    }
+    #[cfg(feature = "std")]
     pub(crate) fn write_to<T: Write>(&self, dest: &mut T) -> std::io::Result<()> {
-        let num_bytes = self.name.as_bytes().len();
+        let name = self.descriptor_name(FIELD_NAME_LENGTH);
         let mut name_bytes = [0u8; FIELD_NAME_LENGTH];
-        name_bytes[..num_bytes.min(FIELD_NAME_LENGTH)].copy_from_slice(self.name.as_bytes());
+        name_bytes[..name.len()].copy_from_slice(name);
         dest.write_all(&name_bytes)?;
 
         dest.write_u8(u8::from(self.field_type))?;
@@ -155,6 +354,89 @@ impl FieldInfo {
 
         Ok(())
     }
+
+    /// Writes the field descriptor the way dBase Level 7 files expects it
+    #[cfg(feature = "std")]
+    pub(crate) fn write_level7_to<T: Write>(&self, dest: &mut T) -> std::io::Result<()> {
+        let name = self.descriptor_name(LEVEL7_FIELD_NAME_LENGTH);
+        let mut name_bytes = [0u8; LEVEL7_FIELD_NAME_LENGTH];
+        name_bytes[..name.len()].copy_from_slice(name);
+        dest.write_all(&name_bytes)?;
+
+        dest.write_u8(u8::from(self.field_type))?;
+        dest.write_u8(self.field_length)?;
+        dest.write_u8(self.num_decimal_places)?;
+
+        let reserved = [0u8; 5];
+        dest.write_all(&reserved)?;
+        dest.write_all(&self.autoincrement_next_val[..core::mem::size_of::<u32>()])?;
+        let reserved = [0u8; 4];
+        dest.write_all(&reserved)?;
+
+        Ok(())
+    }
+}
+
+/// Returns true if `table_name`, the name of a field of a table,
+/// is the one of the struct field `field_name`
+///
+/// The names are compared ignoring the case, and a `_` in `field_name`
+/// also matches a space or a `-`, so that `first_name` matches `First Name`.
+/// As the descriptors truncate the names, a table name that is as long as they allow
+/// also matches the longer struct field names that start with it,
+/// so that `station_name` matches `STATION_NA`.
+#[cfg(feature = "std")]
+pub(crate) fn field_name_matches(table_name: &str, field_name: &str) -> bool {
+    let field_name = field_name.strip_prefix("r#").unwrap_or(field_name);
+    let is_truncated = [FIELD_NAME_LENGTH, LEVEL7_FIELD_NAME_LENGTH]
+        .iter()
+        .any(|&max_length| table_name.len() + 1 >= max_length && table_name.len() <= max_length);
+    let compared_length = if is_truncated {
+        field_name.len().min(table_name.len())
+    } else {
+        field_name.len()
+    };
+    table_name.len() == compared_length
+        && table_name
+            .bytes()
+            .zip(field_name.bytes())
+            .all(|(c, f)| c.eq_ignore_ascii_case(&f) || (f == b'_' && matches!(c, b' ' | b'-')))
+}
+
+/// Sets the position of each field in the records from the lengths of the fields before it
+pub(crate) fn compute_offsets_in_record(fields_info: &mut [FieldInfo]) {
+    let mut offset = DELETION_FLAG_SIZE;
+    for info in fields_info {
+        info.offset_in_record = offset as u16;
+        offset += info.record_length();
+    }
+}
+
+/// Size of a record as computed from the fields, deletion flag included
+pub(crate) fn computed_record_size(fields_info: &[FieldInfo]) -> usize {
+    DELETION_FLAG_SIZE
+        + fields_info
+            .iter()
+            .map(FieldInfo::record_length)
+            .sum::<usize>()
+}
+
+/// Some files seem not to include the DELETION_FLAG_SIZE into the record size,
+/// we accept them as we know how to read them.
+pub(crate) fn validate_record_size(
+    header: &Header,
+    fields_info: &[FieldInfo],
+) -> Result<(), ErrorKind> {
+    let computed = computed_record_size(fields_info);
+    let in_header = header.size_of_record as usize;
+    if in_header == computed || in_header + DELETION_FLAG_SIZE == computed {
+        Ok(())
+    } else {
+        Err(ErrorKind::RecordSizeMismatch {
+            header: header.size_of_record,
+            computed,
+        })
+    }
 }
 
 pub struct FieldsInfo {
@@ -162,41 +444,118 @@ pub struct FieldsInfo {
 }
 
 impl FieldsInfo {
-    pub(crate) fn read_from<R: Read>(source: &mut R, num_fields: usize) -> Result<Self, ErrorKind> {
-        let mut fields_info = Vec::<FieldInfo>::with_capacity(num_fields);
-        for _ in 0..num_fields {
-            let info = FieldInfo::read_from(source)?;
+    /// Reads the field descriptors and the terminator that follows them
+    ///
+    /// At most `max_num_fields` descriptors are read, the terminator
+    /// must be found before or right after them, unless `require_terminator` is false.
+    #[cfg(feature = "std")]
+    pub(crate) fn read_from<R: Read>(
+        source: &mut R,
+        max_num_fields: usize,
+        file_type: Version,
+        require_terminator: bool,
+    ) -> Result<Self, ErrorKind> {
+        let descriptor_size = FieldInfo::size_for(file_type);
+        let max_size = max_num_fields * descriptor_size;
+        let mut bytes = Vec::<u8>::with_capacity(max_size + 1);
+        // The descriptors are read one by one, as the terminator
+        // may come before the space reserved for them is filled
+        loop {
+            if !require_terminator && bytes.len() == max_size {
+                break;
+            }
+            let first_byte = source.read_u8()?;
+            bytes.push(first_byte);
+            if first_byte == TERMINATOR_VALUE || bytes.len() > max_size {
+                break;
+            }
+            let start = bytes.len();
+            bytes.resize(start + descriptor_size - 1, 0);
+            source.read_exact(&mut bytes[start..])?;
+        }
+        Self::from_bytes(&bytes, max_num_fields, file_type, require_terminator)
+    }
+
+    /// Parses the field descriptors and the terminator that follows them
+    /// from the bytes after the header
+    ///
+    /// At most `max_num_fields` descriptors are parsed, the terminator
+    /// must be found before or right after them, unless `require_terminator` is false.
+    pub(crate) fn from_bytes(
+        mut bytes: &[u8],
+        max_num_fields: usize,
+        file_type: Version,
+        require_terminator: bool,
+    ) -> Result<Self, ErrorKind> {
+        let descriptor_size = FieldInfo::size_for(file_type);
+        let mut fields_info = Vec::<FieldInfo>::with_capacity(max_num_fields);
+        let mut terminated = false;
+        for _ in 0..=max_num_fields {
+            if !require_terminator && fields_info.len() == max_num_fields {
+                break;
+            }
+            let Some(&first_byte) = bytes.first() else {
+                return Err(ErrorKind::TruncatedHeader);
+            };
+            if first_byte == TERMINATOR_VALUE {
+                terminated = true;
+                break;
+            }
+            if fields_info.len() == max_num_fields {
+                break;
+            }
+            let descriptor = bytes
+                .get(..descriptor_size)
+                .ok_or(ErrorKind::TruncatedHeader)?;
+            let info = if file_type.is_dbase7() {
+                FieldInfo::from_level7_bytes(descriptor)?
+            } else {
+                FieldInfo::from_bytes(descriptor)?
+            };
             fields_info.push(info);
+            bytes = &bytes[descriptor_size..];
         }
 
+        if !terminated && require_terminator {
+            return Err(ErrorKind::MalformedDescriptors);
+        }
+        if fields_info.is_empty() {
+            return Err(ErrorKind::NoFields);
+        }
+        compute_offsets_in_record(&mut fields_info);
         Ok(Self { inner: fields_info })
     }
 
+    #[cfg(feature = "std")]
     pub(crate) fn field_position_in_record(&self, index: usize) -> Option<usize> {
         self.inner
             .get(..index)
-            .map(|slc| slc.iter().map(|i| i.field_length as usize).sum::<usize>())
+            .map(|slc| slc.iter().map(FieldInfo::record_length).sum::<usize>())
             .map(|s| s + DELETION_FLAG_SIZE)
     }
 
+    #[cfg(feature = "std")]
     pub(crate) fn size_of_all_fields(&self) -> usize {
         self.inner
             .iter()
-            .map(|i| i.field_length as usize)
+            .map(FieldInfo::record_length)
             .sum::<usize>()
     }
 
+    #[cfg(all(feature = "std", not(target_arch = "wasm32")))]
     pub(crate) fn at_least_one_field_is_memo(&self) -> bool {
         self.inner
             .iter()
-            .any(|f_info| f_info.field_type == FieldType::Memo)
+            .any(|f_info| matches!(f_info.field_type, FieldType::Memo | FieldType::Picture))
     }
 
+    #[cfg(feature = "std")]
     pub fn len(&self) -> usize {
         self.inner.len()
     }
 
-    pub fn iter(&self) -> std::slice::Iter<'_, FieldInfo> {
+    #[cfg(feature = "std")]
+    pub fn iter(&self) -> core::slice::Iter<'_, FieldInfo> {
         self.inner.iter()
     }
 }
@@ -218,8 +577,8 @@ where
     }
 }
 
-impl std::fmt::Display for FieldInfo {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for FieldInfo {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(
             f,
             "FieldInfo {{ Name: {}, Field Type: {} }}",
@@ -235,6 +594,7 @@ pub(crate) enum DeletionFlag {
 }
 
 impl DeletionFlag {
+    #[cfg(feature = "std")]
     pub(crate) const fn to_byte(self) -> u8 {
         match self {
             Self::NotDeleted => 0x20,
@@ -251,10 +611,12 @@ impl DeletionFlag {
         }
     }
 
+    #[cfg(feature = "std")]
     pub(crate) fn read_from<T: Read>(source: &mut T) -> std::io::Result<Self> {
         source.read_u8().map(Self::from_byte)
     }
 
+    #[cfg(feature = "std")]
     pub(crate) fn write_to<T: Write>(self, dst: &mut T) -> std::io::Result<()> {
         dst.write_u8(self.to_byte())
     }
@@ -268,6 +630,36 @@ mod test {
     use super::*;
     use std::io::Cursor;
 
+    #[test]
+    fn field_info_with_name() {
+        for name in ["NAME", "first_name", "_hidden", "A1234567_9"] {
+            let info = FieldInfo::with_name(name, FieldType::Numeric, 5).unwrap();
+            assert_eq!(info.name(), name);
+            assert_eq!(info.field_type(), FieldType::Numeric);
+            assert_eq!(info.length(), 5);
+        }
+
+        let invalid_names = [
+            "",
+            // 11 bytes
+            "CUSTOMER_ID",
+            "First Name",
+            "marker-col",
+            "prénom",
+            "2ND_LINE",
+            "_NULLFLAGS",
+            "_NullFlags",
+        ];
+        for name in invalid_names {
+            let error = FieldInfo::with_name(name, FieldType::Character, 10).unwrap_err();
+            assert!(
+                matches!(error.kind(), ErrorKind::InvalidFieldName(invalid) if invalid == name),
+                "{}",
+                name
+            );
+        }
+    }
+
     #[test]
     fn write_read_field_info() {
         let field_info = FieldInfo::new(
@@ -278,10 +670,54 @@ mod test {
         let mut cursor = Cursor::new(Vec::<u8>::with_capacity(FieldInfo::SIZE));
         field_info.write_to(&mut cursor).unwrap();
 
-        cursor.set_position(0);
+        let read_field_info = FieldInfo::from_bytes(cursor.get_ref()).unwrap();
+
+        assert_eq!(read_field_info, field_info);
+    }
+
+    #[test]
+    fn write_read_level7_field_info() {
+        let mut field_info = FieldInfo::new(
+            FieldName::try_from("ID").unwrap(),
+            FieldType::AutoIncrement,
+            4,
+        );
+        field_info.autoincrement_next_val[..4].copy_from_slice(&42u32.to_le_bytes());
+        let mut cursor = Cursor::new(Vec::<u8>::with_capacity(FieldInfo::LEVEL7_SIZE));
+        field_info.write_level7_to(&mut cursor).unwrap();
+        assert_eq!(cursor.get_ref().len(), FieldInfo::LEVEL7_SIZE);
 
-        let read_field_info = FieldInfo::read_from(&mut cursor).unwrap();
+        let read_field_info = FieldInfo::from_level7_bytes(cursor.get_ref()).unwrap();
 
         assert_eq!(read_field_info, field_info);
+        assert_eq!(read_field_info.autoincrement_next_value(), Some(42));
+    }
+    #[test]
+    fn offsets_and_decimal_places() {
+        let mut amount = FieldInfo::new(
+            FieldName::try_from("AMOUNT").unwrap(),
+            FieldType::Numeric,
+            10,
+        );
+        amount.num_decimal_places = 2;
+        let mut comment = FieldInfo::new(
+            FieldName::try_from("COMMENT").unwrap(),
+            FieldType::Character,
+            0x2c,
+        );
+        comment.num_decimal_places = 0x01;
+        comment.wide_record_length = Some(0x012c);
+        let flag = FieldInfo::new(FieldName::try_from("FLAG").unwrap(), FieldType::Logical, 1);
+        let mut fields = vec![amount, comment, flag];
+        compute_offsets_in_record(&mut fields);
+
+        let offsets = fields
+            .iter()
+            .map(FieldInfo::offset_within_record)
+            .collect::<Vec<_>>();
+        assert_eq!(offsets, vec![1, 11, 11 + 300]);
+        assert_eq!(fields[0].num_decimal_places(), 2);
+        assert_eq!(fields[1].num_decimal_places(), 0);
+        assert_eq!(fields[1].record_length(), 300);
     }
 }
@@ -1,18 +1,32 @@
-use std::convert::{TryFrom, TryInto};
-use std::fmt;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::convert::{TryFrom, TryInto};
+use core::fmt;
+use core::str::FromStr;
+#[cfg(feature = "std")]
 use std::io::{Read, Seek, Write};
-use std::str::FromStr;
 
 use crate::Encoding;
-use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+#[cfg(feature = "std")]
+use byteorder::WriteBytesExt;
+use byteorder::{ByteOrder, LittleEndian};
 
-use crate::error::{DecodeError, ErrorKind};
+use crate::error::{DecodeError, Error, ErrorKind, FieldIOError};
 use crate::field::FieldInfo;
+use crate::header::Dialect;
+#[cfg(feature = "std")]
 use crate::memo::MemoReader;
+#[cfg(feature = "std")]
 use crate::writing::WritableAsDbaseField;
 
 /// Enum listing all the field types we know of
+///
+/// New field types may be added, matches on this enum
+/// outside of this crate need a wildcard arm.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum FieldType {
     // dBASE III
     Character,
@@ -24,6 +38,11 @@ pub enum FieldType {
     Currency,
     DateTime,
     Integer,
+    Picture,
+    // dBase Level 7
+    AutoIncrement,
+    Timestamp,
+    DBase7Double,
     // Unknown
     Double,
     Memo,
@@ -43,6 +62,10 @@ impl From<FieldType> for u8 {
             FieldType::Currency => 'Y',
             FieldType::DateTime => 'T',
             FieldType::Integer => 'I',
+            FieldType::Picture => 'P',
+            FieldType::AutoIncrement => '+',
+            FieldType::Timestamp => '@',
+            FieldType::DBase7Double => 'O',
             FieldType::Double => 'B',
             FieldType::Memo => 'M',
         };
@@ -65,6 +88,11 @@ impl FieldType {
             'Y' => Some(FieldType::Currency),
             'T' => Some(FieldType::DateTime),
             'I' => Some(FieldType::Integer),
+            'P' => Some(FieldType::Picture),
+            // dBase Level 7 field types
+            '+' => Some(FieldType::AutoIncrement),
+            '@' => Some(FieldType::Timestamp),
+            'O' => Some(FieldType::DBase7Double),
             // unknown version
             'B' => Some(FieldType::Double),
             'M' => Some(FieldType::Memo),
@@ -81,14 +109,18 @@ impl FieldType {
     /// (the in-file size depends on the field data)
     ///
     /// This could/should be a const fn but they are not stable yet
+    #[cfg(feature = "std")]
     pub(crate) fn size(self) -> Option<u8> {
         match self {
             FieldType::Logical => Some(1),
             FieldType::Date => Some(8),
-            FieldType::Integer => Some(std::mem::size_of::<i32>() as u8),
-            FieldType::Currency => Some(std::mem::size_of::<f64>() as u8),
-            FieldType::DateTime => Some(2 * std::mem::size_of::<i32>() as u8),
-            FieldType::Double => Some(std::mem::size_of::<f64>() as u8),
+            FieldType::Integer => Some(core::mem::size_of::<i32>() as u8),
+            FieldType::AutoIncrement => Some(core::mem::size_of::<i32>() as u8),
+            FieldType::Timestamp => Some(2 * core::mem::size_of::<i32>() as u8),
+            FieldType::DBase7Double => Some(core::mem::size_of::<f64>() as u8),
+            FieldType::Currency => Some(core::mem::size_of::<f64>() as u8),
+            FieldType::DateTime => Some(2 * core::mem::size_of::<i32>() as u8),
+            FieldType::Double => Some(core::mem::size_of::<f64>() as u8),
             _ => None,
         }
     }
@@ -105,14 +137,18 @@ impl TryFrom<char> for FieldType {
     }
 }
 
-impl std::fmt::Display for FieldType {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+impl core::fmt::Display for FieldType {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> Result<(), core::fmt::Error> {
         write!(f, "dbase::{:?}", self)
     }
 }
 
 /// Enum where each variant stores the record value
+///
+/// New variants may be added along with new field types, matches on this enum
+/// outside of this crate need a wildcard arm.
 #[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
 pub enum FieldValue {
     // dBase III fields
     // Stored as strings, fully padded (ie only space char) strings
@@ -132,9 +168,18 @@ pub enum FieldValue {
     Float(Option<f32>),
     //Visual FoxPro fields
     Integer(i32),
-    Currency(f64),
+    /// Visual FoxPro type for amounts of money, holds the `i64` number
+    /// of ten-thousandths stored in the file: `Currency(123_400)` is `12.34`
+    ///
+    /// Use [FieldValue::currency_from_f64] to create one from a `f64`,
+    /// and [FieldValue::to_decimal_string] to format it without losing precision.
+    Currency(i64),
     DateTime(DateTime),
     Double(f64),
+    /// Visual FoxPro type for images, stored in the `Memo file`
+    ///
+    /// The value holds the raw bytes of the image
+    Picture(Vec<u8>),
 
     /// Memo is a dBase type that allows to store Strings
     /// that are longer than 255 bytes.
@@ -144,24 +189,70 @@ pub enum FieldValue {
 }
 
 impl FieldValue {
+    #[cfg(feature = "std")]
     pub(crate) fn read_from<T: Read + Seek, E: Encoding>(
-        mut field_bytes: &[u8],
+        field_bytes: &[u8],
         memo_reader: &mut Option<MemoReader<T>>,
         field_info: &FieldInfo,
+        dialect: Dialect,
+        encoding: &E,
+        character_trim: CharacterTrim,
+        overflow_policy: OverflowPolicy,
+    ) -> Result<Self, ErrorKind> {
+        let memo_reader = match (field_info.field_type, memo_reader) {
+            (FieldType::Memo | FieldType::Picture, Some(memo_reader)) => memo_reader,
+            _ => {
+                return Self::decode(
+                    field_bytes,
+                    field_info,
+                    dialect,
+                    encoding,
+                    character_trim,
+                    overflow_policy,
+                )
+            }
+        };
+        let index_in_memo = read_memo_block_index(field_bytes, dialect, encoding)?;
+        let value = match (field_info.field_type, index_in_memo) {
+            (FieldType::Memo, None) => FieldValue::Memo(String::from("")),
+            (FieldType::Memo, Some(index)) => {
+                let data_from_memo = memo_reader.read_data_at(index)?;
+                FieldValue::Memo(encoding.decode(data_from_memo)?.to_string())
+            }
+            // Block 0 is the header of the memo file, it means there is no picture
+            (_, None | Some(0)) => FieldValue::Picture(vec![]),
+            (_, Some(index)) => {
+                FieldValue::Picture(memo_reader.read_binary_data_at(index)?.to_vec())
+            }
+        };
+        Ok(value)
+    }
+
+    /// Decodes the value of a field from its bytes in a record
+    ///
+    /// The data of Memo and Picture fields is in the memo file, so they
+    /// can only be decoded when they are empty, otherwise this fails
+    /// with [ErrorKind::MissingMemoFile].
+    pub(crate) fn decode<E: Encoding>(
+        field_bytes: &[u8],
+        field_info: &FieldInfo,
+        dialect: Dialect,
         encoding: &E,
-        character_option: TrimOption,
+        character_trim: CharacterTrim,
+        overflow_policy: OverflowPolicy,
     ) -> Result<Self, ErrorKind> {
-        debug_assert_eq!(field_bytes.len(), field_info.length() as usize);
+        debug_assert_eq!(field_bytes.len(), field_info.record_length());
         let value = match field_info.field_type {
-            FieldType::Logical => match field_bytes[0] as char {
-                ' ' | '?' => FieldValue::Logical(None),
-                '1' | '0' | 'T' | 't' | 'Y' | 'y' => FieldValue::Logical(Some(true)),
-                'N' | 'n' | 'F' | 'f' => FieldValue::Logical(Some(false)),
-                _ => FieldValue::Logical(None),
-            },
+            // Bytes that are not a logical value are read as an unknown value
+            FieldType::Logical => FieldValue::Logical(
+                core::str::from_utf8(field_bytes)
+                    .ok()
+                    .and_then(|text| parse_logical(text).ok())
+                    .flatten(),
+            ),
             FieldType::Character => {
                 // let value = read_string_of_len(&mut source, field_info.field_length)?;
-                let value = trim_field_data(field_bytes, character_option);
+                let value = trim_character_data(field_bytes, character_trim);
                 if value.is_empty() {
                     FieldValue::Character(None)
                 } else {
@@ -176,8 +267,10 @@ impl FieldValue {
             FieldType::Numeric => {
                 // let value = read_string_of_len(&mut source, field_info.field_length)?;
                 let value = trim_field_data(field_bytes, TrimOption::BeginEnd);
-                if value.is_empty() || value.iter().all(|c| c == &b'*') {
+                if value.is_empty() {
                     FieldValue::Numeric(None)
+                } else if value.iter().all(|c| c == &b'*') {
+                    FieldValue::Numeric(read_overflowed_number(field_info, overflow_policy)?)
                 } else {
                     let value_str = encoding.decode(value)?;
                     FieldValue::Numeric(Some(value_str.parse::<f64>()?))
@@ -186,68 +279,186 @@ impl FieldValue {
             FieldType::Float => {
                 // let value = read_string_of_len(&mut source, field_info.field_length)?;
                 let value = trim_field_data(field_bytes, TrimOption::BeginEnd);
-                if value.is_empty() || value.iter().all(|c| c == &b'*') {
+                if value.is_empty() {
                     FieldValue::Float(None)
+                } else if value.iter().all(|c| c == &b'*') {
+                    let value = read_overflowed_number(field_info, overflow_policy)?;
+                    FieldValue::Float(value.map(|value| value as f32))
                 } else {
                     let value_str = encoding.decode(value)?;
                     FieldValue::Float(Some(value_str.parse::<f32>()?))
                 }
             }
             FieldType::Date => {
-                // let value = read_string_of_len(&mut source, field_info.field_length)?;
                 let value = trim_field_data(field_bytes, TrimOption::BeginEnd);
-                if value.iter().all(|c| c == &b' ') {
-                    FieldValue::Date(None)
-                } else {
-                    let value_str = encoding.decode(value)?;
-                    FieldValue::Date(Some(value_str.parse::<Date>()?))
-                }
+                FieldValue::Date(parse_date(&encoding.decode(value)?)?)
             }
             FieldType::Integer => {
-                let mut le_bytes = [0u8; std::mem::size_of::<i32>()];
-                le_bytes.copy_from_slice(&field_bytes[..std::mem::size_of::<i32>()]);
+                let mut le_bytes = [0u8; core::mem::size_of::<i32>()];
+                le_bytes.copy_from_slice(&field_bytes[..core::mem::size_of::<i32>()]);
                 FieldValue::Integer(i32::from_le_bytes(le_bytes))
             }
+            FieldType::AutoIncrement => {
+                let mut be_bytes = [0u8; core::mem::size_of::<i32>()];
+                be_bytes.copy_from_slice(&field_bytes[..core::mem::size_of::<i32>()]);
+                FieldValue::Integer(level7_long_from_be_bytes(be_bytes))
+            }
             FieldType::Double => {
-                let mut le_bytes = [0u8; std::mem::size_of::<f64>()];
-                le_bytes.copy_from_slice(&field_bytes[..std::mem::size_of::<f64>()]);
+                let mut le_bytes = [0u8; core::mem::size_of::<f64>()];
+                le_bytes.copy_from_slice(&field_bytes[..core::mem::size_of::<f64>()]);
                 FieldValue::Double(f64::from_le_bytes(le_bytes))
             }
             FieldType::Currency => {
-                let mut le_bytes = [0u8; std::mem::size_of::<f64>()];
-                le_bytes.copy_from_slice(&field_bytes[..std::mem::size_of::<f64>()]);
-                FieldValue::Currency(f64::from_le_bytes(le_bytes))
+                let mut le_bytes = [0u8; core::mem::size_of::<i64>()];
+                le_bytes.copy_from_slice(&field_bytes[..core::mem::size_of::<i64>()]);
+                FieldValue::Currency(i64::from_le_bytes(le_bytes))
             }
-            FieldType::DateTime => {
-                let mut source = std::io::Cursor::new(&mut field_bytes);
-                FieldValue::DateTime(DateTime::read_from(&mut source)?)
+            FieldType::DateTime => FieldValue::DateTime(DateTime::from_bytes(field_bytes)),
+            FieldType::Timestamp => FieldValue::DateTime(DateTime::from_level7_bytes(field_bytes)),
+            FieldType::DBase7Double => {
+                let mut be_bytes = [0u8; core::mem::size_of::<f64>()];
+                be_bytes.copy_from_slice(&field_bytes[..core::mem::size_of::<f64>()]);
+                FieldValue::Double(level7_double_from_be_bytes(be_bytes))
             }
-            FieldType::Memo => {
-                let index_in_memo = if field_info.field_length > 4 {
-                    // let string = read_string_of_len(&mut source, field_info.field_length)?;
-                    let trimmed_value = trim_field_data(field_bytes, TrimOption::BeginEnd);
-                    if trimmed_value.is_empty() {
-                        return Ok(FieldValue::Memo(String::from("")));
-                    } else {
-                        encoding.decode(trimmed_value)?.parse::<u32>()?
-                    }
-                } else {
-                    let mut le_bytes = [0u8; std::mem::size_of::<u32>()];
-                    le_bytes.copy_from_slice(&field_bytes[..std::mem::size_of::<u32>()]);
-                    u32::from_le_bytes(le_bytes)
-                };
-
-                if let Some(memo_reader) = memo_reader {
-                    let data_from_memo = memo_reader.read_data_at(index_in_memo)?;
-                    FieldValue::Memo(encoding.decode(data_from_memo)?.to_string())
-                } else {
-                    return Err(ErrorKind::MissingMemoFile);
+            FieldType::Memo => match read_memo_block_index(field_bytes, dialect, encoding)? {
+                Some(_) => return Err(ErrorKind::MissingMemoFile),
+                None => FieldValue::Memo(String::from("")),
+            },
+            FieldType::Picture => {
+                // Block 0 is the header of the memo file, it means there is no picture
+                match read_memo_block_index(field_bytes, dialect, encoding)? {
+                    Some(index) if index != 0 => return Err(ErrorKind::MissingMemoFile),
+                    _ => FieldValue::Picture(vec![]),
                 }
             }
         };
         Ok(value)
     }
 
+    /// Decodes the value of a field from its bytes in a record,
+    /// the inverse of [FieldValue::to_bytes]
+    ///
+    /// Strings are decoded with [UnicodeLossy](crate::UnicodeLossy) and trimmed
+    /// at the beginning and the end, see [FieldValue::from_bytes_with_encoding]
+    /// to use another encoding.
+    ///
+    /// Fails with [ErrorKind::FieldLengthMismatch] if `bytes` is not
+    /// [FieldInfo::record_length] bytes long, and with [ErrorKind::MissingMemoFile]
+    /// for Memo and Picture fields that are not empty.
+    pub fn from_bytes(bytes: &[u8], field_info: &FieldInfo) -> Result<Self, Error> {
+        Self::from_bytes_with_encoding(bytes, field_info, crate::encoding::UnicodeLossy)
+    }
+
+    /// Decodes the value of a field from its bytes in a record, with the given encoding
+    ///
+    /// See [FieldValue::from_bytes].
+    pub fn from_bytes_with_encoding<E: Encoding>(
+        bytes: &[u8],
+        field_info: &FieldInfo,
+        encoding: E,
+    ) -> Result<Self, Error> {
+        if bytes.len() != field_info.record_length() {
+            return Err(field_error(
+                ErrorKind::FieldLengthMismatch {
+                    expected: field_info.record_length(),
+                    actual: bytes.len(),
+                },
+                field_info,
+            ));
+        }
+        Self::decode(
+            bytes,
+            field_info,
+            Dialect::DBase,
+            &encoding,
+            CharacterTrim::default(),
+            OverflowPolicy::default(),
+        )
+        .map_err(|kind| field_error(kind, field_info))
+    }
+
+    /// Encodes the value as it is written in a record, in exactly
+    /// [FieldInfo::record_length] bytes
+    ///
+    /// Strings are encoded with [UnicodeLossy](crate::UnicodeLossy), see
+    /// [FieldValue::to_bytes_with_encoding] to use another encoding.
+    /// The value is padded and truncated the same way a
+    /// [TableWriter](crate::TableWriter) does.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dbase::{FieldInfo, FieldType, FieldValue};
+    ///
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let field = FieldInfo::new("count".try_into().unwrap(), FieldType::Numeric, 5);
+    /// let bytes = FieldValue::Numeric(Some(42.0)).to_bytes(&field)?;
+    /// assert_eq!(bytes, b"   42");
+    /// assert_eq!(
+    ///     FieldValue::from_bytes(&bytes, &field)?,
+    ///     FieldValue::Numeric(Some(42.0))
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn to_bytes(&self, field_info: &FieldInfo) -> Result<Vec<u8>, Error> {
+        self.to_bytes_with_encoding(field_info, crate::encoding::UnicodeLossy)
+    }
+
+    /// Encodes the value as it is written in a record, with the given encoding
+    ///
+    /// See [FieldValue::to_bytes].
+    #[cfg(feature = "std")]
+    pub fn to_bytes_with_encoding<E: Encoding + 'static>(
+        &self,
+        field_info: &FieldInfo,
+        encoding: E,
+    ) -> Result<Vec<u8>, Error> {
+        let fields_info = core::slice::from_ref(field_info);
+        let encoding = crate::encoding::DynEncoding::new(encoding);
+        let mut buffer = vec![0u8; crate::writing::field_buffer_size(fields_info)];
+        let mut bytes = Vec::with_capacity(field_info.record_length());
+        let mut field_writer = crate::writing::FieldWriter {
+            dst: &mut bytes,
+            fields_info: fields_info.iter().peekable(),
+            field_buffer: &mut std::io::Cursor::new(&mut buffer),
+            encoding: &encoding,
+            numeric_overflow: Default::default(),
+        };
+        field_writer
+            .write_next_field_value(self)
+            .map_err(|error| Error::new(error, 0))?;
+        Ok(bytes)
+    }
+
+    /// Returns an adaptor that displays the contained value
+    /// without allocating (unlike `to_string`, which uses the `Debug` representation)
+    ///
+    /// See [FieldValueDisplay] for how each type is written.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dbase::FieldValue;
+    /// use std::fmt::Write;
+    ///
+    /// let values = [
+    ///     FieldValue::Character(Some("Paris".to_string())),
+    ///     FieldValue::Numeric(Some(2.5)),
+    ///     FieldValue::Logical(None),
+    ///     FieldValue::Date(Some(dbase::Date::new(14, 7, 1789))),
+    /// ];
+    /// let mut line = String::new();
+    /// for value in &values {
+    ///     write!(line, "{};", value.as_display()).unwrap();
+    /// }
+    /// assert_eq!(line, "Paris;2.5;;17890714;");
+    /// ```
+    pub fn as_display(&self) -> FieldValueDisplay<'_> {
+        FieldValueDisplay { value: self }
+    }
+
     /// Returns the corresponding field type of the contained value
     pub fn field_type(&self) -> FieldType {
         match self {
@@ -260,10 +471,339 @@ impl FieldValue {
             FieldValue::Double(_) => FieldType::Double,
             FieldValue::Date(_) => FieldType::Date,
             FieldValue::Memo(_) => FieldType::Memo,
+            FieldValue::Picture(_) => FieldType::Picture,
             FieldValue::Currency(_) => FieldType::Currency,
             FieldValue::DateTime(_) => FieldType::DateTime,
         }
     }
+
+    /// Returns the value used for a field of the given type when no value is given
+    ///
+    /// - Character: an empty string
+    /// - Numeric, Float, Integer, Currency and Double: zero
+    /// - Logical: false
+    /// - Date: no date (`None`), as there is no meaningful default date
+    /// - DateTime: the Unix epoch, 1970-01-01 00:00:00
+    /// - Memo and Picture: an empty text and no bytes
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dbase::{FieldType, FieldValue};
+    ///
+    /// assert_eq!(
+    ///     FieldValue::default_for(FieldType::Numeric),
+    ///     FieldValue::Numeric(Some(0.0))
+    /// );
+    /// assert_eq!(
+    ///     FieldValue::default_for(FieldType::Logical),
+    ///     FieldValue::Logical(Some(false))
+    /// );
+    /// ```
+    pub fn default_for(field_type: FieldType) -> FieldValue {
+        match field_type {
+            FieldType::Character => FieldValue::Character(Some(String::new())),
+            FieldType::Numeric => FieldValue::Numeric(Some(0.0)),
+            FieldType::Float => FieldValue::Float(Some(0.0)),
+            FieldType::Logical => FieldValue::Logical(Some(false)),
+            FieldType::Date => FieldValue::Date(None),
+            FieldType::Integer | FieldType::AutoIncrement => FieldValue::Integer(0),
+            FieldType::Currency => FieldValue::Currency(0),
+            FieldType::Double | FieldType::DBase7Double => FieldValue::Double(0.0),
+            FieldType::DateTime | FieldType::Timestamp => {
+                FieldValue::DateTime(DateTime::new(Date::new(1, 1, 1970), Time::new(0, 0, 0)))
+            }
+            FieldType::Memo => FieldValue::Memo(String::new()),
+            FieldType::Picture => FieldValue::Picture(Vec::new()),
+        }
+    }
+
+    /// Creates a Currency value from an amount, rounded to the nearest ten-thousandth
+    /// (halfway values are rounded away from zero)
+    ///
+    /// As `f64` cannot represent most decimal amounts exactly, `0.1` is stored as `1000`.
+    /// Fails with [ErrorKind::NumericOverflow] if the amount is not finite
+    /// or does not fit in an `i64` once scaled.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let value = dbase::FieldValue::currency_from_f64(12.34).unwrap();
+    /// assert_eq!(value, dbase::FieldValue::Currency(123400));
+    /// ```
+    pub fn currency_from_f64(value: f64) -> Result<Self, ErrorKind> {
+        currency_to_scaled(value).map(FieldValue::Currency)
+    }
+
+    /// Returns the number of ten-thousandths of a Currency value, as stored in the file,
+    /// `None` if the value is not a Currency
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let value = dbase::FieldValue::Currency(123400);
+    /// assert_eq!(value.currency_ten_thousandths(), Some(123400));
+    /// ```
+    pub fn currency_ten_thousandths(&self) -> Option<i64> {
+        match self {
+            FieldValue::Currency(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Returns a Currency value formatted with exactly 4 decimal places
+    ///
+    /// The text is computed from the number of ten-thousandths
+    /// (see [Self::currency_ten_thousandths]) with integer arithmetic,
+    /// so it has none of the rounding artifacts of formatting a `f64`.
+    ///
+    /// Returns `None` if the value is not a Currency.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let value = dbase::FieldValue::Currency(123400);
+    /// assert_eq!(value.to_decimal_string().as_deref(), Some("12.3400"));
+    /// let value = dbase::FieldValue::Currency(-1000);
+    /// assert_eq!(value.to_decimal_string().as_deref(), Some("-0.1000"));
+    /// ```
+    pub fn to_decimal_string(&self) -> Option<String> {
+        self.currency_ten_thousandths().map(format_ten_thousandths)
+    }
+
+    /// Converts the value to a value that can be written in a field of the `target` type
+    ///
+    /// The following coercions are lossless:
+    ///
+    /// - any value to its own type
+    /// - `Integer` to `Numeric`, `Double` or `Currency`
+    /// - `Float` to `Numeric` or `Double`
+    /// - `Character` to `Memo` and `Memo` to `Character`
+    /// - `Date` to `DateTime` (the time is set to midnight)
+    /// - `Logical` to `Numeric`, `Integer`, `Float`, `Double` or `Currency` (`1` or `0`)
+    ///
+    /// The following coercions may lose information:
+    ///
+    /// - `Numeric`, `Double` or `Currency` to `Float` (precision is lost)
+    /// - numbers to `Logical` (any non zero number is `true`)
+    /// - `DateTime` to `Date` (the time is dropped)
+    ///
+    /// Values can also be parsed from `Character` (numbers, and `Logical` and `Date`
+    /// as [FieldValue::parse] reads them) and formatted as `Character`.
+    ///
+    /// Numbers are only converted to `Integer` when they have no fractional part and fit in an i32.
+    ///
+    /// # Errors
+    ///
+    /// - [ErrorKind::IncompatibleCoercion] when the types cannot be converted
+    ///   (e.g. `Date` to `Numeric`) or when the value does not fit the target type
+    ///   (e.g. an empty `Numeric` to `Integer`, which cannot represent empty values)
+    /// - [ErrorKind::ParseFloatError] when a `Character` is not a valid number
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dbase::{FieldType, FieldValue};
+    ///
+    /// let value = FieldValue::Character(Some("42.5".to_string()));
+    /// assert_eq!(
+    ///     value.coerce_to(FieldType::Numeric).unwrap(),
+    ///     FieldValue::Numeric(Some(42.5))
+    /// );
+    ///
+    /// let value = FieldValue::Character(Some("20230615".to_string()));
+    /// assert_eq!(
+    ///     value.coerce_to(FieldType::Date).unwrap(),
+    ///     FieldValue::Date(Some(dbase::Date::new(15, 6, 2023)))
+    /// );
+    ///
+    /// assert!(FieldValue::Logical(Some(true)).coerce_to(FieldType::Date).is_err());
+    /// ```
+    pub fn coerce_to(&self, target: FieldType) -> Result<FieldValue, ErrorKind> {
+        let incompatible = || ErrorKind::IncompatibleCoercion {
+            from: self.field_type(),
+            to: target,
+        };
+
+        let value = match target {
+            FieldType::Character => match self {
+                FieldValue::Character(_) | FieldValue::BinCharacter(_) => self.clone(),
+                FieldValue::Memo(text) => FieldValue::Character(Some(text.clone())),
+                FieldValue::Numeric(value) => FieldValue::Character(value.map(|v| v.to_string())),
+                FieldValue::Float(value) => FieldValue::Character(value.map(|v| v.to_string())),
+                FieldValue::Logical(value) => {
+                    FieldValue::Character(value.map(|v| if v { "T" } else { "F" }.to_string()))
+                }
+                FieldValue::Date(value) => FieldValue::Character(value.map(|v| v.to_string())),
+                FieldValue::Integer(value) => FieldValue::Character(Some(value.to_string())),
+                FieldValue::Double(value) => FieldValue::Character(Some(value.to_string())),
+                FieldValue::Currency(value) => {
+                    FieldValue::Character(Some(format_ten_thousandths(*value)))
+                }
+                FieldValue::DateTime(_) | FieldValue::Picture(_) => return Err(incompatible()),
+            },
+            FieldType::Memo => match self.coerce_to(FieldType::Character)? {
+                FieldValue::Character(text) => FieldValue::Memo(text.unwrap_or_default()),
+                _ => return Err(incompatible()),
+            },
+            FieldType::Numeric => FieldValue::Numeric(self.coerce_to_number(incompatible)?),
+            FieldType::Float => {
+                FieldValue::Float(self.coerce_to_number(incompatible)?.map(|v| v as f32))
+            }
+            FieldType::Double | FieldType::DBase7Double => FieldValue::Double(
+                self.coerce_to_number(incompatible)?
+                    .ok_or_else(incompatible)?,
+            ),
+            FieldType::Currency => match self {
+                FieldValue::Currency(_) => self.clone(),
+                _ => FieldValue::currency_from_f64(
+                    self.coerce_to_number(incompatible)?
+                        .ok_or_else(incompatible)?,
+                )?,
+            },
+            FieldType::Integer | FieldType::AutoIncrement => match self {
+                FieldValue::Integer(_) => self.clone(),
+                _ => {
+                    let value = self
+                        .coerce_to_number(incompatible)?
+                        .ok_or_else(incompatible)?;
+                    // Also rejects NaN, which is neither in range nor equal to itself
+                    if !(i32::MIN as f64..=i32::MAX as f64).contains(&value)
+                        || value as i32 as f64 != value
+                    {
+                        return Err(incompatible());
+                    }
+                    FieldValue::Integer(value as i32)
+                }
+            },
+            FieldType::Picture => match self {
+                FieldValue::Picture(_) => self.clone(),
+                _ => return Err(incompatible()),
+            },
+            FieldType::Logical => match self {
+                FieldValue::Logical(_) => self.clone(),
+                FieldValue::Character(None) => FieldValue::Logical(None),
+                FieldValue::Character(Some(text)) => {
+                    FieldValue::Logical(parse_logical(text).map_err(|_| incompatible())?)
+                }
+                _ => FieldValue::Logical(self.coerce_to_number(incompatible)?.map(|v| v != 0.0)),
+            },
+            FieldType::Date => match self {
+                FieldValue::Date(_) => self.clone(),
+                FieldValue::DateTime(date_time) => FieldValue::Date(Some(date_time.date())),
+                FieldValue::Character(None) => FieldValue::Date(None),
+                FieldValue::Character(Some(text)) => match parse_date(text) {
+                    Ok(date) => FieldValue::Date(date),
+                    Err(ErrorKind::ParseError { .. }) => return Err(incompatible()),
+                    Err(error) => return Err(error),
+                },
+                _ => return Err(incompatible()),
+            },
+            FieldType::DateTime | FieldType::Timestamp => match self {
+                FieldValue::DateTime(_) => self.clone(),
+                FieldValue::Date(Some(date)) => {
+                    FieldValue::DateTime(DateTime::new(*date, Time::new(0, 0, 0)))
+                }
+                _ => return Err(incompatible()),
+            },
+        };
+        Ok(value)
+    }
+
+    /// Parses the string representation of a value of the given field type
+    ///
+    /// - `Character` and `Memo` values are the string itself
+    /// - `Numeric`, `Float`, `Double` and `Currency` values are parsed as numbers,
+    ///   `Integer` values are parsed as i32
+    /// - `Logical` values are one of `T`, `Y`, `1`, `true`, `F`, `N`, `0`, `false`
+    ///   (case insensitive), or `?` for an unknown value
+    /// - `Date` values are in the `YYYYMMDD` format, `00000000` is an empty date
+    ///
+    /// Surrounding whitespaces are ignored, except for `Character` and `Memo`, and an empty
+    /// string gives an empty value for the types that can hold one.
+    ///
+    /// # Errors
+    ///
+    /// - [ErrorKind::ParseFloatError] or [ErrorKind::ParseIntError] for invalid numbers
+    /// - [ErrorKind::ParseError] for other invalid values or types that cannot be parsed
+    ///   (e.g. `DateTime`)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dbase::{FieldType, FieldValue};
+    ///
+    /// assert_eq!(
+    ///     FieldValue::parse("12.5", FieldType::Numeric).unwrap(),
+    ///     FieldValue::Numeric(Some(12.5))
+    /// );
+    /// assert_eq!(
+    ///     FieldValue::parse("true", FieldType::Logical).unwrap(),
+    ///     FieldValue::Logical(Some(true))
+    /// );
+    /// assert!(FieldValue::parse("12.5", FieldType::Integer).is_err());
+    /// ```
+    pub fn parse(s: &str, field_type: FieldType) -> Result<FieldValue, ErrorKind> {
+        let parse_error = || ErrorKind::ParseError {
+            value: s.to_string(),
+            field_type,
+        };
+        let trimmed = s.trim();
+
+        let value = match field_type {
+            FieldType::Character if s.is_empty() => FieldValue::Character(None),
+            FieldType::Character => FieldValue::Character(Some(s.to_string())),
+            FieldType::Memo => FieldValue::Memo(s.to_string()),
+            FieldType::Numeric if trimmed.is_empty() => FieldValue::Numeric(None),
+            FieldType::Numeric => FieldValue::Numeric(Some(trimmed.parse::<f64>()?)),
+            FieldType::Float if trimmed.is_empty() => FieldValue::Float(None),
+            FieldType::Float => FieldValue::Float(Some(trimmed.parse::<f32>()?)),
+            FieldType::Double | FieldType::DBase7Double => {
+                FieldValue::Double(trimmed.parse::<f64>()?)
+            }
+            FieldType::Currency => match parse_ten_thousandths(trimmed) {
+                Some(scaled) => FieldValue::Currency(scaled),
+                None => FieldValue::currency_from_f64(trimmed.parse::<f64>()?)?,
+            },
+            FieldType::Integer | FieldType::AutoIncrement => {
+                FieldValue::Integer(trimmed.parse::<i32>()?)
+            }
+            FieldType::Logical => FieldValue::Logical(parse_logical(s)?),
+            FieldType::Date => FieldValue::Date(parse_date(s)?),
+            FieldType::DateTime | FieldType::Timestamp | FieldType::Picture => {
+                return Err(parse_error())
+            }
+        };
+        Ok(value)
+    }
+
+    fn coerce_to_number<F>(&self, incompatible: F) -> Result<Option<f64>, ErrorKind>
+    where
+        F: Fn() -> ErrorKind,
+    {
+        match self {
+            FieldValue::Character(None) => Ok(None),
+            FieldValue::Character(Some(text)) => {
+                let text = text.trim();
+                if text.is_empty() {
+                    Ok(None)
+                } else {
+                    Ok(Some(text.parse::<f64>()?))
+                }
+            }
+            FieldValue::Numeric(value) => Ok(*value),
+            FieldValue::Float(value) => Ok(value.map(f64::from)),
+            FieldValue::Logical(value) => Ok(value.map(|v| if v { 1.0 } else { 0.0 })),
+            FieldValue::Integer(value) => Ok(Some(f64::from(*value))),
+            FieldValue::Double(value) => Ok(Some(*value)),
+            FieldValue::Currency(value) => Ok(Some(currency_from_scaled(*value))),
+            FieldValue::BinCharacter(_)
+            | FieldValue::Date(_)
+            | FieldValue::DateTime(_)
+            | FieldValue::Memo(_)
+            | FieldValue::Picture(_) => Err(incompatible()),
+        }
+    }
 }
 
 impl fmt::Display for FieldValue {
@@ -272,6 +812,138 @@ impl fmt::Display for FieldValue {
     }
 }
 
+/// Adaptor to display the value contained in a [FieldValue]
+///
+/// Created by [FieldValue::as_display], the value is written directly to the formatter
+/// without allocating intermediate Strings:
+///
+/// - empty values (`None`) are written as an empty string
+/// - `Character` and `Memo` are written as is
+/// - numbers use their `Display` implementation
+/// - `Logical` is written as `T` or `F`
+/// - `Date` is written as `YYYYMMDD` and `DateTime` as `YYYYMMDD HH:MM:SS`
+/// - binary data (`BinCharacter` and `Picture`) is written in lowercase hexadecimal
+#[derive(Debug, Copy, Clone)]
+pub struct FieldValueDisplay<'a> {
+    value: &'a FieldValue,
+}
+
+impl<'a> fmt::Display for FieldValueDisplay<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.value {
+            FieldValue::Character(Some(text)) => f.write_str(text),
+            FieldValue::Memo(text) => f.write_str(text),
+            FieldValue::BinCharacter(Some(bytes)) | FieldValue::Picture(bytes) => {
+                bytes.iter().try_for_each(|b| write!(f, "{:02x}", b))
+            }
+            FieldValue::Numeric(Some(value)) => write!(f, "{}", value),
+            FieldValue::Float(Some(value)) => write!(f, "{}", value),
+            FieldValue::Logical(Some(value)) => f.write_str(if *value { "T" } else { "F" }),
+            FieldValue::Date(Some(date)) => write_date(f, date),
+            FieldValue::Integer(value) => write!(f, "{}", value),
+            FieldValue::Double(value) => write!(f, "{}", value),
+            FieldValue::Currency(value) => f.write_str(&format_ten_thousandths(*value)),
+            FieldValue::DateTime(date_time) => {
+                write_date(f, &date_time.date)?;
+                write!(
+                    f,
+                    " {:02}:{:02}:{:02}",
+                    date_time.time.hours, date_time.time.minutes, date_time.time.seconds
+                )
+            }
+            FieldValue::Character(None)
+            | FieldValue::BinCharacter(None)
+            | FieldValue::Numeric(None)
+            | FieldValue::Float(None)
+            | FieldValue::Logical(None)
+            | FieldValue::Date(None) => Ok(()),
+        }
+    }
+}
+
+fn write_date(f: &mut fmt::Formatter, date: &Date) -> fmt::Result {
+    write!(f, "{:04}{:02}{:02}", date.year, date.month, date.day)
+}
+
+/// Parses the text of a Logical value, used by all the ways values are read
+/// so that they accept the same inputs
+///
+/// `T`, `Y`, `1` and `true` are true, `F`, `N`, `0` and `false` are false,
+/// ignoring the case, an empty text or `?` is an unknown value.
+fn parse_logical(text: &str) -> Result<Option<bool>, ErrorKind> {
+    let text = text.trim();
+    let is_any_of = |words: &[&str]| words.iter().any(|w| text.eq_ignore_ascii_case(w));
+    if text.is_empty() || text == "?" {
+        Ok(None)
+    } else if is_any_of(&["t", "y", "1", "true"]) {
+        Ok(Some(true))
+    } else if is_any_of(&["f", "n", "0", "false"]) {
+        Ok(Some(false))
+    } else {
+        Err(ErrorKind::ParseError {
+            value: text.to_string(),
+            field_type: FieldType::Logical,
+        })
+    }
+}
+
+/// Parses the `YYYYMMDD` text of a Date value, used by all the ways values are read
+/// so that they accept the same inputs
+///
+/// An empty text, or one made of zeros as some programs write, is an empty date.
+/// Fails with [ErrorKind::ParseError] if the text is not 8 digits,
+/// and with [ErrorKind::InvalidDate] if the day or month does not exist.
+fn parse_date(text: &str) -> Result<Option<Date>, ErrorKind> {
+    let text = text.trim();
+    if text.bytes().all(|c| c == b'0') {
+        return Ok(None);
+    }
+    if text.len() != 8 || !text.bytes().all(|c| c.is_ascii_digit()) {
+        return Err(ErrorKind::ParseError {
+            value: text.to_string(),
+            field_type: FieldType::Date,
+        });
+    }
+    let date = text.parse::<Date>()?;
+    if !date.has_valid_day_and_month() {
+        return Err(ErrorKind::InvalidDate);
+    }
+    Ok(Some(date))
+}
+
+/// dBase Level 7 stores its 4 bytes integers in big endian,
+/// with the sign bit flipped so that the bytes sort like the values do.
+fn level7_long_from_be_bytes(be_bytes: [u8; 4]) -> i32 {
+    (u32::from_be_bytes(be_bytes) ^ 0x8000_0000) as i32
+}
+
+#[cfg(feature = "std")]
+fn level7_long_to_be_bytes(value: i32) -> [u8; 4] {
+    ((value as u32) ^ 0x8000_0000).to_be_bytes()
+}
+
+/// dBase Level 7 stores its doubles in big endian, also ordered so that the bytes
+/// sort like the values: positive values have their sign bit flipped,
+/// negative values have all their bits flipped.
+fn level7_double_from_be_bytes(be_bytes: [u8; 8]) -> f64 {
+    let bits = u64::from_be_bytes(be_bytes);
+    if bits & 0x8000_0000_0000_0000 != 0 {
+        f64::from_bits(bits ^ 0x8000_0000_0000_0000)
+    } else {
+        f64::from_bits(!bits)
+    }
+}
+
+#[cfg(feature = "std")]
+fn level7_double_to_be_bytes(value: f64) -> [u8; 8] {
+    let bits = value.to_bits();
+    if bits & 0x8000_0000_0000_0000 != 0 {
+        (!bits).to_be_bytes()
+    } else {
+        (bits ^ 0x8000_0000_0000_0000).to_be_bytes()
+    }
+}
+
 /// dBase representation of date
 ///
 /// # Note
@@ -321,6 +993,158 @@ impl Date {
         self.day
     }
 
+    /// Checks that the date can be stored anywhere in a dBase file
+    ///
+    /// The month and day must be valid, and as the header stores the year of the
+    /// last update as an offset from 1900 on one byte, the year must be between
+    /// 1900 and 2155.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let date = dbase::Date::new(1, 1, 1899);
+    /// assert!(date.validate().is_err());
+    /// ```
+    pub fn validate(&self) -> Result<(), ErrorKind> {
+        if self.has_valid_day_and_month() && (1900..=2155).contains(&self.year) {
+            Ok(())
+        } else {
+            Err(ErrorKind::DateOutOfRange {
+                year: self.year,
+                month: self.month,
+                day: self.day,
+            })
+        }
+    }
+
+    /// Returns true if the year is a leap year in the Gregorian calendar
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// assert!(dbase::Date::new(1, 1, 2000).is_leap_year());
+    /// assert!(!dbase::Date::new(1, 1, 1900).is_leap_year());
+    /// ```
+    pub fn is_leap_year(&self) -> bool {
+        match (self.year % 4, self.year % 100, self.year % 400) {
+            (_, _, 0) => true,
+            (_, 0, _) => false,
+            (0, _, _) => true,
+            _ => false,
+        }
+    }
+
+    /// Returns the number of days in the month of the date
+    ///
+    /// 0 is returned if the month is not valid.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// assert_eq!(dbase::Date::new(1, 2, 2024).days_in_month(), 29);
+    /// assert_eq!(dbase::Date::new(1, 4, 2023).days_in_month(), 30);
+    /// ```
+    pub fn days_in_month(&self) -> u32 {
+        match self.month {
+            1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+            4 | 6 | 9 | 11 => 30,
+            2 if self.is_leap_year() => 29,
+            2 => 28,
+            _ => 0,
+        }
+    }
+
+    fn has_valid_day_and_month(&self) -> bool {
+        self.day >= 1 && self.day <= self.days_in_month()
+    }
+
+    /// Returns the date `n` days after this one (before it if `n` is negative)
+    ///
+    /// Fails with [ErrorKind::InvalidDate] if this date is not valid,
+    /// or if the result would not be between the years 1 and 9999.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let date = dbase::Date::new(31, 12, 2023);
+    /// assert_eq!(date.add_days(1).unwrap(), dbase::Date::new(1, 1, 2024));
+    /// assert_eq!(date.add_days(-31 - 30).unwrap(), dbase::Date::new(31, 10, 2023));
+    /// ```
+    pub fn add_days(&self, n: i32) -> Result<Date, ErrorKind> {
+        let jdn = self
+            .checked_julian_day_number()?
+            .checked_add(n)
+            .ok_or(ErrorKind::InvalidDate)?;
+        let min = Date::new(1, 1, 1).to_julian_day_number();
+        let max = Date::new(31, 12, 9999).to_julian_day_number();
+        if (min..=max).contains(&jdn) {
+            Ok(Self::julian_day_number_to_gregorian_date(jdn))
+        } else {
+            Err(ErrorKind::InvalidDate)
+        }
+    }
+
+    /// Returns the number of days from `other` to this date,
+    /// negative if `other` is after this date
+    ///
+    /// The days are counted in the proleptic Gregorian calendar, with the year 0
+    /// being the year before 1. The result is exact for any year a `Date` can hold,
+    /// a day or month out of range is counted as an offset from the start of the year
+    /// (e.g. February 30th is March 1st or 2nd).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let start = dbase::Date::new(1, 2, 2024);
+    /// let end = dbase::Date::new(1, 3, 2024);
+    /// assert_eq!(end.diff_days(&start), 29);
+    /// assert_eq!(start.diff_days(&end), -29);
+    /// ```
+    pub fn diff_days(&self, other: &Date) -> i64 {
+        self.julian_day_number() - other.julian_day_number()
+    }
+
+    /// Returns the date `n` months after this one (before it if `n` is negative)
+    ///
+    /// When the day does not exist in the resulting month, the last day
+    /// of that month is used (e.g. one month after January 31st is February 28th or 29th).
+    ///
+    /// Fails with [ErrorKind::InvalidDate] if this date is not valid,
+    /// or if the result would not be between the years 1 and 9999.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let date = dbase::Date::new(31, 1, 2024);
+    /// assert_eq!(date.add_months(1).unwrap(), dbase::Date::new(29, 2, 2024));
+    /// assert_eq!(date.add_months(-2).unwrap(), dbase::Date::new(30, 11, 2023));
+    /// ```
+    pub fn add_months(&self, n: i32) -> Result<Date, ErrorKind> {
+        self.checked_julian_day_number()?;
+        let months = i64::from(self.year) * 12 + i64::from(self.month - 1) + i64::from(n);
+        let year = months.div_euclid(12);
+        if !(1..=9999).contains(&year) {
+            return Err(ErrorKind::InvalidDate);
+        }
+        let mut date = Date {
+            year: year as u32,
+            month: months.rem_euclid(12) as u32 + 1,
+            day: 1,
+        };
+        date.day = self.day.min(date.days_in_month());
+        Ok(date)
+    }
+
+    /// Returns the julian day number, if the date is valid and supported
+    /// by the arithmetic functions
+    fn checked_julian_day_number(&self) -> Result<i32, ErrorKind> {
+        if self.has_valid_day_and_month() && (1..=9999).contains(&self.year) {
+            Ok(self.to_julian_day_number())
+        } else {
+            Err(ErrorKind::InvalidDate)
+        }
+    }
+
     pub fn to_unix_days(&self) -> i32 {
         let julian_day = self.to_julian_day_number();
         return julian_day - 2440588;
@@ -358,26 +1182,74 @@ impl Date {
         }
     }
 
+    /// Returns the julian day number as stored in the files,
+    /// which only fits an `i32` for the years up to about 5 800 000
     fn to_julian_day_number(&self) -> i32 {
+        self.julian_day_number() as i32
+    }
+
+    /// Returns the julian day number, computed on `i64`
+    /// so that it does not overflow for any year, month and day
+    fn julian_day_number(&self) -> i64 {
         let (month, year) = if self.month > 2 {
-            (self.month - 3, self.year)
+            (i64::from(self.month) - 3, i64::from(self.year))
         } else {
-            (self.month + 9, self.year - 1)
+            (i64::from(self.month) + 9, i64::from(self.year) - 1)
         };
 
-        let century = year / 100;
+        // The year before the year 0 is -1, so the divisions round down
+        let century = year.div_euclid(100);
         let decade = year - 100 * century;
 
-        ((146_097 * century) / 4
+        (146_097 * century).div_euclid(4)
             + (1461 * decade) / 4
             + (153 * month + 2) / 5
-            + self.day
-            + 1_721_119) as i32
+            + i64::from(self.day)
+            + 1_721_119
+    }
+
+    /// Decodes a date stored as 3 bytes: the year since 1900, the month and the day
+    ///
+    /// This is how the date of the last update is stored in the header.
+    pub(crate) fn from_header_format(bytes: [u8; 3]) -> Self {
+        Self {
+            year: 1900u32 + u32::from(bytes[0]),
+            month: u32::from(bytes[1]),
+            day: u32::from(bytes[2]),
+        }
+    }
+
+    /// Writes the date as 3 bytes: the year since 1900, the month and the day
+    ///
+    /// This is how the date of the last update is stored in the header,
+    /// Date fields use [Self::write_field_format].
+    #[cfg(feature = "std")]
+    pub(crate) fn write_header_format<W: Write>(&self, dest: &mut W) -> std::io::Result<()> {
+        dest.write_u8((self.year - 1900) as u8)?;
+        dest.write_u8(self.month as u8)?;
+        dest.write_u8(self.day as u8)
+    }
+
+    /// Writes the date as 8 ASCII digits, `YYYYMMDD`,
+    /// which is how the values of Date fields are stored
+    #[cfg(feature = "std")]
+    pub(crate) fn write_field_format<E: Encoding, W: Write>(
+        &self,
+        encoding: &E,
+        dest: &mut W,
+    ) -> Result<(), ErrorKind> {
+        if !self.has_valid_day_and_month() {
+            return Err(ErrorKind::InvalidDate);
+        }
+        let string = format!("{:04}{:02}{:02}", self.year, self.month, self.day);
+        let encoded_string = encoding.encode(&string)?;
+        dest.write_all(&encoded_string)?;
+        Ok(())
     }
 }
 
 impl FromStr for Date {
-    type Err = std::num::ParseIntError;
+    type Err = core::num::ParseIntError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let year = s[0..4].parse::<u32>()?;
@@ -388,13 +1260,13 @@ impl FromStr for Date {
     }
 }
 
-impl std::string::ToString for Date {
+impl ToString for Date {
     fn to_string(&self) -> String {
         format!("{:04}{:02}{:02}", self.year, self.month, self.day)
     }
 }
 
-impl std::convert::TryFrom<Date> for time::Date {
+impl TryFrom<Date> for time::Date {
     type Error = time::error::ComponentRange;
 
     fn try_from(d: Date) -> Result<Self, Self::Error> {
@@ -475,6 +1347,7 @@ impl Time {
         }
     }
 
+    #[cfg(feature = "std")]
     fn to_time_word(&self) -> i32 {
         let mut time_word = self.hours * Self::HOURS_FACTOR as u32;
         time_word += self.minutes * Self::MINUTES_FACTOR as u32;
@@ -513,21 +1386,213 @@ impl DateTime {
             + self.time().seconds() as i64;
     }
 
-    fn read_from<T: Read>(src: &mut T) -> Result<Self, ErrorKind> {
-        let julian_day_number = src.read_i32::<LittleEndian>()?;
-        let time_word = src.read_i32::<LittleEndian>()?;
+    /// Decodes the julian day number and the time word,
+    /// stored as little endian integers in the first 8 bytes
+    fn from_bytes(bytes: &[u8]) -> Self {
+        let julian_day_number = LittleEndian::read_i32(&bytes[..4]);
+        let time_word = LittleEndian::read_i32(&bytes[4..8]);
         let time = Time::from_word(time_word);
         let date = Date::julian_day_number_to_gregorian_date(julian_day_number);
-        Ok(Self { date, time })
+        Self { date, time }
     }
 
+    #[cfg(feature = "std")]
     fn write_to<W: Write>(&self, dest: &mut W) -> std::io::Result<()> {
         dest.write_i32::<LittleEndian>(self.date.to_julian_day_number())?;
         dest.write_i32::<LittleEndian>(self.time.to_time_word())?;
         Ok(())
     }
+
+    /// dBase Level 7 timestamps are the same two words,
+    /// but stored the way Level 7 stores its integers
+    fn from_level7_bytes(bytes: &[u8]) -> Self {
+        let mut be_bytes = [0u8; core::mem::size_of::<i32>()];
+        be_bytes.copy_from_slice(&bytes[..4]);
+        let julian_day_number = level7_long_from_be_bytes(be_bytes);
+        be_bytes.copy_from_slice(&bytes[4..8]);
+        let time_word = level7_long_from_be_bytes(be_bytes);
+        let time = Time::from_word(time_word);
+        let date = Date::julian_day_number_to_gregorian_date(julian_day_number);
+        Self { date, time }
+    }
+
+    #[cfg(feature = "std")]
+    fn write_level7_to<W: Write>(&self, dest: &mut W) -> std::io::Result<()> {
+        dest.write_all(&level7_long_to_be_bytes(self.date.to_julian_day_number()))?;
+        dest.write_all(&level7_long_to_be_bytes(self.time.to_time_word()))?;
+        Ok(())
+    }
+}
+
+/// Size of the stack buffer numbers are formatted into,
+/// large enough for any Numeric field of the dBase specification
+#[cfg(feature = "std")]
+const NUMBER_BUFFER_SIZE: usize = 64;
+
+/// Writes the text representation of a Numeric or Float value
+/// with the given number of decimal places.
+///
+/// The number is formatted into a stack buffer to avoid allocating
+/// in write loops, only numbers too big to fit in it (which will be
+/// cropped to the field length anyway) go through a `String`.
+#[cfg(feature = "std")]
+fn write_number<T: fmt::Display, E: Encoding, W: Write>(
+    value: T,
+    num_decimal_places: u8,
+    encoding: &E,
+    dst: &mut W,
+) -> Result<(), ErrorKind> {
+    let precision = num_decimal_places as usize;
+    let mut buffer = [0u8; NUMBER_BUFFER_SIZE];
+    let mut cursor = std::io::Cursor::new(&mut buffer[..]);
+
+    if write!(cursor, "{:.*}", precision, value).is_ok() {
+        let len = cursor.position() as usize;
+        let string = core::str::from_utf8(&buffer[..len])
+            .expect("Internal error: formatted numbers should be valid utf8");
+        dst.write_all(&encoding.encode(string)?)?;
+    } else {
+        let string = format!("{:.*}", precision, value);
+        dst.write_all(&encoding.encode(&string)?)?;
+    }
+    Ok(())
+}
+
+/// Currency values are stored as a number of ten-thousandths
+const CURRENCY_SCALE: f64 = 10_000.0;
+
+/// Converts a Currency value to the number of ten-thousandths stored in the file,
+/// rounding halfway values away from zero
+fn currency_to_scaled(value: f64) -> Result<i64, ErrorKind> {
+    let scaled = value * CURRENCY_SCALE;
+    // i64::MAX is not representable as a f64, but i64::MIN (-2^63) is
+    if scaled.is_finite() && scaled >= i64::MIN as f64 && scaled < -(i64::MIN as f64) {
+        // Rounded by hand, f64::round is not available without std
+        let truncated = scaled as i64;
+        let fraction = scaled - truncated as f64;
+        Ok(if fraction >= 0.5 {
+            truncated + 1
+        } else if fraction <= -0.5 {
+            truncated - 1
+        } else {
+            truncated
+        })
+    } else {
+        Err(ErrorKind::NumericOverflow {
+            value: Some(value),
+            width: core::mem::size_of::<i64>() as u8,
+        })
+    }
+}
+
+/// Formats a number of ten-thousandths with 4 decimal places, without going through a float
+fn format_ten_thousandths(scaled: i64) -> String {
+    let sign = if scaled < 0 { "-" } else { "" };
+    let magnitude = scaled.unsigned_abs();
+    let scale = CURRENCY_SCALE as u64;
+    format!("{}{}.{:04}", sign, magnitude / scale, magnitude % scale)
+}
+
+/// Parses an amount with at most 4 decimal places into a number of ten-thousandths,
+/// without going through a float, `None` if it is not written that way
+fn parse_ten_thousandths(text: &str) -> Option<i64> {
+    let (negative, digits) = match text.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, text.strip_prefix('+').unwrap_or(text)),
+    };
+    let (integer, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+    let is_digits = |part: &str| part.bytes().all(|b| b.is_ascii_digit());
+    if integer.is_empty() || fraction.len() > 4 || !is_digits(integer) || !is_digits(fraction) {
+        return None;
+    }
+    let magnitude = format!("{}{:0<4}", integer, fraction);
+    if negative {
+        format!("-{}", magnitude).parse().ok()
+    } else {
+        magnitude.parse().ok()
+    }
+}
+
+/// Converts a number of ten-thousandths to the amount, precision is lost above 2^53
+pub(crate) fn currency_from_scaled(scaled: i64) -> f64 {
+    scaled as f64 / CURRENCY_SCALE
+}
+
+/// Returns the value of a Numeric or Float field that is only made of `*`,
+/// which is how dBase writes numbers that do not fit in the field
+fn read_overflowed_number(
+    field_info: &FieldInfo,
+    policy: OverflowPolicy,
+) -> Result<Option<f64>, ErrorKind> {
+    match policy {
+        OverflowPolicy::ReturnError => Err(ErrorKind::NumericOverflow {
+            value: None,
+            width: field_info.field_length,
+        }),
+        OverflowPolicy::ReturnNull => Ok(None),
+        OverflowPolicy::ReturnMaxValue => Ok(Some(max_value_of_field(field_info))),
+    }
+}
+
+/// Returns the greatest number whose text representation fits in the field,
+/// e.g. `999.99` for a field of length 6 with 2 decimal places
+fn max_value_of_field(field_info: &FieldInfo) -> f64 {
+    let length = usize::from(field_info.field_length);
+    // The decimal point takes one byte, and there is at least one digit before it
+    let decimals = usize::from(field_info.num_decimal_places).min(length.saturating_sub(2));
+    let text = if decimals == 0 {
+        "9".repeat(length)
+    } else {
+        format!(
+            "{}.{}",
+            "9".repeat(length - decimals - 1),
+            "9".repeat(decimals)
+        )
+    };
+    text.parse().unwrap_or(0.0)
 }
 
+/// Writes the text representation of a Numeric or Float value in the field,
+/// fails with [ErrorKind::NumericOverflow] if it is longer than the field.
+///
+/// NaN and infinite numbers have no representation in dBase files,
+/// they are rejected with [ErrorKind::InvalidFieldValue].
+#[cfg(feature = "std")]
+fn write_number_in_field<T, E, W>(
+    value: T,
+    field_info: &FieldInfo,
+    encoding: &E,
+    dst: &mut W,
+) -> Result<(), ErrorKind>
+where
+    T: fmt::Display + Copy + Into<f64>,
+    E: Encoding,
+    W: Write,
+{
+    if !value.into().is_finite() {
+        return Err(ErrorKind::InvalidFieldValue(value.to_string()));
+    }
+    // Fields are at most 255 bytes long, so numbers that do not fit
+    // in the buffer do not fit in the field either
+    let mut buffer = [0u8; 255];
+    let mut cursor = std::io::Cursor::new(&mut buffer[..]);
+    let fits = match write_number(value, field_info.num_decimal_places, encoding, &mut cursor) {
+        Ok(()) => cursor.position() <= u64::from(field_info.field_length),
+        Err(ErrorKind::IoError(_)) => false,
+        Err(error) => return Err(error),
+    };
+    if !fits {
+        return Err(ErrorKind::NumericOverflow {
+            value: Some(value.into()),
+            width: field_info.field_length,
+        });
+    }
+    let len = cursor.position() as usize;
+    dst.write_all(&buffer[..len])?;
+    Ok(())
+}
+
+#[cfg(feature = "std")]
 impl WritableAsDbaseField for FieldValue {
     fn write_as<E: Encoding, W: Write>(
         &self,
@@ -535,26 +1600,47 @@ impl WritableAsDbaseField for FieldValue {
         encoding: &E,
         dst: &mut W,
     ) -> Result<(), ErrorKind> {
-        if self.field_type() != field_info.field_type {
+        // dBase Level 7 types reuse the values of the types they resemble,
+        // which are not what FieldValue::field_type reports for them
+        let is_compatible = self.field_type() == field_info.field_type
+            || matches!(
+                (self, field_info.field_type),
+                (FieldValue::Integer(_), FieldType::AutoIncrement)
+                    | (FieldValue::DateTime(_), FieldType::Timestamp)
+                    | (FieldValue::Double(_), FieldType::DBase7Double)
+            );
+        if !is_compatible {
             Err(ErrorKind::IncompatibleType)
         } else {
             match self {
                 FieldValue::Character(value) => value.write_as(field_info, encoding, dst),
-                FieldValue::BinCharacter(_) => unimplemented!("Currently under implementation"),
+                // The bytes could not be decoded when read, so they are written as is
+                FieldValue::BinCharacter(value) => {
+                    if let Some(bytes) = value {
+                        dst.write_all(bytes)?;
+                    }
+                    Ok(())
+                }
                 FieldValue::Numeric(value) => value.write_as(field_info, encoding, dst),
                 FieldValue::Logical(value) => value.write_as(field_info, encoding, dst),
                 FieldValue::Date(value) => value.write_as(field_info, encoding, dst),
                 FieldValue::Float(value) => value.write_as(field_info, encoding, dst),
                 FieldValue::Integer(value) => value.write_as(field_info, encoding, dst),
-                FieldValue::Currency(value) => value.write_as(field_info, encoding, dst),
+                FieldValue::Currency(value) => {
+                    dst.write_i64::<LittleEndian>(*value)?;
+                    Ok(())
+                }
                 FieldValue::DateTime(value) => value.write_as(field_info, encoding, dst),
                 FieldValue::Double(value) => value.write_as(field_info, encoding, dst),
-                FieldValue::Memo(_) => unimplemented!("Cannot write memo"),
+                FieldValue::Memo(_) | FieldValue::Picture(_) => Err(ErrorKind::Message(
+                    "Memo and Picture values cannot be written yet".to_string(),
+                )),
             }
         }
     }
 }
 
+#[cfg(feature = "std")]
 impl WritableAsDbaseField for f64 {
     fn write_as<E: Encoding, W: Write>(
         &self,
@@ -563,25 +1649,29 @@ impl WritableAsDbaseField for f64 {
         dst: &mut W,
     ) -> Result<(), ErrorKind> {
         match field_info.field_type {
-            FieldType::Numeric => {
-                let string = format!(
-                    "{value:.precision$}",
-                    value = self,
-                    precision = field_info.num_decimal_places as usize
-                );
-                let encoded_string = encoding.encode(&string)?;
-                dst.write_all(&*encoded_string)?;
+            FieldType::Numeric => write_number_in_field(*self, field_info, encoding, dst),
+            FieldType::Currency => {
+                dst.write_i64::<LittleEndian>(currency_to_scaled(*self)?)?;
                 Ok(())
             }
-            FieldType::Currency | FieldType::Double => {
+            // Binary doubles could hold them, but other programs would not read them back
+            FieldType::Double | FieldType::DBase7Double if !self.is_finite() => {
+                Err(ErrorKind::InvalidFieldValue(self.to_string()))
+            }
+            FieldType::Double => {
                 dst.write_f64::<LittleEndian>(*self)?;
                 Ok(())
             }
+            FieldType::DBase7Double => {
+                dst.write_all(&level7_double_to_be_bytes(*self))?;
+                Ok(())
+            }
             _ => Err(ErrorKind::IncompatibleType),
         }
     }
 }
 
+#[cfg(feature = "std")]
 impl WritableAsDbaseField for Date {
     fn write_as<E: Encoding, W: Write>(
         &self,
@@ -590,16 +1680,14 @@ impl WritableAsDbaseField for Date {
         dst: &mut W,
     ) -> Result<(), ErrorKind> {
         if field_info.field_type == FieldType::Date {
-            let string = format!("{:04}{:02}{:02}", self.year, self.month, self.day);
-            let encoded_string = encoding.encode(&string)?;
-            dst.write_all(&*encoded_string)?;
-            Ok(())
+            self.write_field_format(encoding, dst)
         } else {
             Err(ErrorKind::IncompatibleType)
         }
     }
 }
 
+#[cfg(feature = "std")]
 impl WritableAsDbaseField for Option<Date> {
     fn write_as<E: Encoding, W: Write>(
         &self,
@@ -622,6 +1710,7 @@ impl WritableAsDbaseField for Option<Date> {
     }
 }
 
+#[cfg(feature = "std")]
 impl WritableAsDbaseField for Option<f64> {
     fn write_as<E: Encoding, W: Write>(
         &self,
@@ -641,6 +1730,7 @@ impl WritableAsDbaseField for Option<f64> {
     }
 }
 
+#[cfg(feature = "std")]
 impl WritableAsDbaseField for f32 {
     fn write_as<E: Encoding, W: Write>(
         &self,
@@ -649,20 +1739,14 @@ impl WritableAsDbaseField for f32 {
         dst: &mut W,
     ) -> Result<(), ErrorKind> {
         if field_info.field_type == FieldType::Float {
-            let string = format!(
-                "{value:.precision$}",
-                value = self,
-                precision = field_info.num_decimal_places as usize
-            );
-            let encoded_string = encoding.encode(&string)?;
-            dst.write_all(&*encoded_string)?;
-            Ok(())
+            write_number_in_field(*self, field_info, encoding, dst)
         } else {
             Err(ErrorKind::IncompatibleType)
         }
     }
 }
 
+#[cfg(feature = "std")]
 impl WritableAsDbaseField for Option<f32> {
     fn write_as<E: Encoding, W: Write>(
         &self,
@@ -681,6 +1765,7 @@ impl WritableAsDbaseField for Option<f32> {
     }
 }
 
+#[cfg(feature = "std")]
 impl WritableAsDbaseField for String {
     fn write_as<E: Encoding, W: Write>(
         &self,
@@ -698,6 +1783,7 @@ impl WritableAsDbaseField for String {
     }
 }
 
+#[cfg(feature = "std")]
 impl WritableAsDbaseField for Option<String> {
     fn write_as<E: Encoding, W: Write>(
         &self,
@@ -716,6 +1802,7 @@ impl WritableAsDbaseField for Option<String> {
     }
 }
 
+#[cfg(feature = "std")]
 impl WritableAsDbaseField for &str {
     fn write_as<E: Encoding, W: Write>(
         &self,
@@ -733,6 +1820,7 @@ impl WritableAsDbaseField for &str {
     }
 }
 
+#[cfg(feature = "std")]
 impl WritableAsDbaseField for bool {
     fn write_as<E: Encoding, W: Write>(
         &self,
@@ -755,6 +1843,7 @@ impl WritableAsDbaseField for bool {
     }
 }
 
+#[cfg(feature = "std")]
 impl WritableAsDbaseField for Option<bool> {
     fn write_as<E: Encoding, W: Write>(
         &self,
@@ -773,6 +1862,7 @@ impl WritableAsDbaseField for Option<bool> {
     }
 }
 
+#[cfg(feature = "std")]
 impl WritableAsDbaseField for i32 {
     fn write_as<E: Encoding, W: Write>(
         &self,
@@ -783,12 +1873,16 @@ impl WritableAsDbaseField for i32 {
         if field_info.field_type == FieldType::Integer {
             dst.write_i32::<LittleEndian>(*self)?;
             Ok(())
+        } else if field_info.field_type == FieldType::AutoIncrement {
+            dst.write_all(&level7_long_to_be_bytes(*self))?;
+            Ok(())
         } else {
             Err(ErrorKind::IncompatibleType)
         }
     }
 }
 
+#[cfg(feature = "std")]
 impl WritableAsDbaseField for DateTime {
     fn write_as<E: Encoding, W: Write>(
         &self,
@@ -799,6 +1893,9 @@ impl WritableAsDbaseField for DateTime {
         if field_info.field_type == FieldType::DateTime {
             self.write_to(dst)?;
             Ok(())
+        } else if field_info.field_type == FieldType::Timestamp {
+            self.write_level7_to(dst)?;
+            Ok(())
         } else {
             Err(ErrorKind::IncompatibleType)
         }
@@ -810,7 +1907,6 @@ mod de {
     use super::*;
     use serde::de::{Deserialize, Visitor};
     use serde::Deserializer;
-    use std::io::Cursor;
 
     impl<'de> Deserialize<'de> for Date {
         fn deserialize<D>(deserializer: D) -> Result<Self, <D as Deserializer<'de>>::Error>
@@ -821,7 +1917,7 @@ mod de {
             impl<'de> Visitor<'de> for DateVisitor {
                 type Value = Date;
 
-                fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
                     formatter.write_str("struct Date")
                 }
 
@@ -842,7 +1938,7 @@ mod de {
     impl<'de> Visitor<'de> for DateTimeVisitor {
         type Value = DateTime;
 
-        fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
             formatter.write_str("struct dbase::DateTime")
         }
 
@@ -850,11 +1946,10 @@ mod de {
         where
             E: serde::de::Error,
         {
-            let mut cursor = Cursor::new(v);
-            match DateTime::read_from(&mut cursor) {
-                Ok(d) => Ok(d),
-                Err(e) => Err(E::custom(e)),
+            if v.len() < 2 * core::mem::size_of::<i32>() {
+                return Err(E::invalid_length(v.len(), &self));
             }
+            Ok(DateTime::from_bytes(&v))
         }
     }
 
@@ -910,12 +2005,72 @@ pub enum TrimOption {
     BeginEnd,
 }
 
+/// The set of bytes trimmed from Character fields,
+/// see [ReadingOptions::character_trim_bytes](crate::ReadingOptions::character_trim_bytes)
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) struct TrimSet([u64; 4]);
+
+impl TrimSet {
+    /// Spaces and NUL bytes
+    pub(crate) const DEFAULT: Self = Self::new(b" \0");
+
+    pub(crate) const fn new(bytes: &[u8]) -> Self {
+        let mut bits = [0u64; 4];
+        let mut i = 0;
+        while i < bytes.len() {
+            bits[(bytes[i] / 64) as usize] |= 1 << (bytes[i] % 64);
+            i += 1;
+        }
+        Self(bits)
+    }
+
+    pub(crate) const fn contains(&self, byte: u8) -> bool {
+        self.0[(byte / 64) as usize] & (1 << (byte % 64)) != 0
+    }
+}
+
+/// How Character fields are trimmed: the sides and the bytes to trim
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct CharacterTrim {
+    pub(crate) option: TrimOption,
+    pub(crate) bytes: TrimSet,
+}
+
+impl Default for CharacterTrim {
+    fn default() -> Self {
+        Self {
+            option: TrimOption::BeginEnd,
+            bytes: TrimSet::DEFAULT,
+        }
+    }
+}
+
+/// How Numeric and Float fields only made of `*` are read
+///
+/// dBase fills the field with `*` when the number does not fit in it,
+/// see [NumericOverflowPolicy](crate::NumericOverflowPolicy) to do the same when writing.
+/// Other programs (e.g. some shapefile writers) also use `*` for empty values,
+/// which is why they are read as empty by default.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// Reading the field fails with [ErrorKind::NumericOverflow]
+    ReturnError,
+    /// The value is read as empty (`None`)
+    #[default]
+    ReturnNull,
+    /// The value is read as the greatest number that fits in the field,
+    /// e.g. `999.99` for a field of length 6 with 2 decimal places
+    ReturnMaxValue,
+}
+
 fn trim_field_data(bytes: &[u8], option: TrimOption) -> &[u8] {
     // Value in the dbf file is surrounded by space characters (32u8). We discard them before
     // parsing the bytes into string. Doing so doubles the performance in comparison to
     // using String::trim() afterwards.
     let mut first = usize::MAX;
     let mut last = 0;
+    // Some programs pad with zeros instead of spaces, the value ends at the first one
+    let mut end = bytes.len();
     let ptr = bytes.as_ptr();
 
     // Using unchecked indexing of the vector provides around 30% increase of reading speed.
@@ -923,6 +2078,7 @@ fn trim_field_data(bytes: &[u8], option: TrimOption) -> &[u8] {
     unsafe {
         for i in 0..bytes.len() {
             if *ptr.add(i) == 0u8 {
+                end = i;
                 break;
             }
 
@@ -945,12 +2101,67 @@ fn trim_field_data(bytes: &[u8], option: TrimOption) -> &[u8] {
     // format, which makes it safe to drop without checking, as it cannot be part of the multi-byte
     // UTF-8 symbol (all such bytes must start with 10).
     match option {
-        TrimOption::Begin => &bytes[first..],
+        TrimOption::Begin => &bytes[first..end],
         TrimOption::End => &bytes[..last + 1],
         TrimOption::BeginEnd => &bytes[first..last + 1],
     }
 }
 
+/// Trims the bytes of the set from the sides given by the option,
+/// when NUL is in the set, the value ends at the first NUL byte
+fn trim_character_data(bytes: &[u8], trim: CharacterTrim) -> &[u8] {
+    if trim.bytes == TrimSet::DEFAULT {
+        return trim_field_data(bytes, trim.option);
+    }
+    let bytes = match bytes.iter().position(|&b| b == 0) {
+        Some(end) if trim.bytes.contains(0) => &bytes[..end],
+        _ => bytes,
+    };
+    let is_kept = |b: &u8| !trim.bytes.contains(*b);
+    let start = match trim.option {
+        TrimOption::Begin | TrimOption::BeginEnd => {
+            bytes.iter().position(is_kept).unwrap_or(bytes.len())
+        }
+        TrimOption::End => 0,
+    };
+    let end = match trim.option {
+        TrimOption::End | TrimOption::BeginEnd => {
+            bytes.iter().rposition(is_kept).map_or(0, |i| i + 1)
+        }
+        TrimOption::Begin => bytes.len(),
+    };
+    &bytes[start..end.max(start)]
+}
+
+fn field_error(kind: ErrorKind, field_info: &FieldInfo) -> Error {
+    Error::new(FieldIOError::new(kind, Some(field_info.clone())), 0)
+}
+
+/// Reads the index of the block in the memo file that holds the data of a field
+///
+/// The [Dialect] decides whether the index is stored as a number in ASCII (dBase)
+/// or as a little endian u32 (Visual FoxPro).
+/// None is returned when an ASCII field is empty.
+fn read_memo_block_index<E: Encoding>(
+    field_bytes: &[u8],
+    dialect: Dialect,
+    encoding: &E,
+) -> Result<Option<u32>, ErrorKind> {
+    let field_length = u8::try_from(field_bytes.len()).unwrap_or(u8::MAX);
+    if dialect.has_binary_memo_pointers(field_length) {
+        let mut le_bytes = [0u8; core::mem::size_of::<u32>()];
+        le_bytes.copy_from_slice(&field_bytes[..core::mem::size_of::<u32>()]);
+        Ok(Some(u32::from_le_bytes(le_bytes)))
+    } else {
+        let trimmed_value = trim_field_data(field_bytes, TrimOption::BeginEnd);
+        if trimmed_value.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(encoding.decode(trimmed_value)?.parse::<u32>()?))
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -965,10 +2176,13 @@ mod test {
             field_type,
             displacement_field: [0u8; 4],
             field_length: len,
+            wide_record_length: None,
             num_decimal_places: 0,
             flags: FieldFlags { 0: 0u8 },
             autoincrement_next_val: [0u8; 5],
             autoincrement_step: 0u8,
+            offset_in_record: 0,
+            long_name: None,
         }
     }
 
@@ -983,8 +2197,10 @@ mod test {
             out.get_mut(),
             &mut None,
             field_info,
+            Dialect::DBase,
             &encoding,
-            TrimOption::BeginEnd,
+            CharacterTrim::default(),
+            OverflowPolicy::default(),
         )
         .unwrap();
         assert_eq!(value, &read_value);
@@ -1033,6 +2249,500 @@ mod test {
         test_we_can_read_back(&field_info, &value);
     }
 
+    #[test]
+    fn write_read_integer_boundaries() {
+        let field_info =
+            create_temp_field_info(FieldType::Integer, FieldType::Integer.size().unwrap());
+        for value in [i32::MIN, -1, 0, 1, i32::MAX] {
+            let mut out = Cursor::new(Vec::new());
+            FieldValue::Integer(value)
+                .write_as(&field_info, &UnicodeLossy, &mut out)
+                .unwrap();
+            assert_eq!(out.get_ref(), &value.to_le_bytes());
+
+            test_we_can_read_back(&field_info, &FieldValue::Integer(value));
+        }
+    }
+
+    #[test]
+    fn numeric_is_not_written_in_integer_field() {
+        let field_info =
+            create_temp_field_info(FieldType::Integer, FieldType::Integer.size().unwrap());
+        let mut out = Cursor::new(Vec::new());
+        let result = FieldValue::Numeric(Some(1.0)).write_as(&field_info, &UnicodeLossy, &mut out);
+        assert!(matches!(result, Err(ErrorKind::IncompatibleType)));
+        assert!(out.get_ref().is_empty());
+    }
+
+    #[test]
+    fn numbers_longer_than_the_field_overflow() {
+        let field_info = create_temp_field_info(FieldType::Numeric, 4);
+        let mut out = Cursor::new(Vec::new());
+        let result = 123456.0f64.write_as(&field_info, &UnicodeLossy, &mut out);
+        assert!(matches!(
+            result,
+            Err(ErrorKind::NumericOverflow { value: Some(value), width: 4 }) if value == 123456.0
+        ));
+        assert!(out.get_ref().is_empty());
+
+        // Exactly the width of the field
+        1234.0f64
+            .write_as(&field_info, &UnicodeLossy, &mut out)
+            .unwrap();
+        assert_eq!(out.get_ref(), b"1234");
+
+        let mut field_info = create_temp_field_info(FieldType::Float, 6);
+        field_info.num_decimal_places = 2;
+        let mut out = Cursor::new(Vec::new());
+        let result = 1000.5f32.write_as(&field_info, &UnicodeLossy, &mut out);
+        assert!(matches!(
+            result,
+            Err(ErrorKind::NumericOverflow { value: Some(value), width: 6 }) if value == 1000.5
+        ));
+        let result = f64::MAX.write_as(
+            &create_temp_field_info(FieldType::Numeric, 255),
+            &UnicodeLossy,
+            &mut out,
+        );
+        assert!(matches!(result, Err(ErrorKind::NumericOverflow { .. })));
+    }
+
+    #[test]
+    fn write_special_float_values() {
+        let field_info = create_temp_field_info(FieldType::Float, 10);
+        let read = |bytes: &[u8]| {
+            FieldValue::read_from::<Cursor<Vec<u8>>, _>(
+                bytes,
+                &mut None,
+                &field_info,
+                Dialect::DBase,
+                &UnicodeLossy,
+                CharacterTrim::default(),
+                OverflowPolicy::default(),
+            )
+        };
+
+        let mut out = Vec::new();
+        FieldValue::Float(Some(-0.0))
+            .write_as(&field_info, &UnicodeLossy, &mut out)
+            .unwrap();
+        assert_eq!(out, b"-0");
+        match read(b"        -0") {
+            Ok(FieldValue::Float(Some(value))) => {
+                assert_eq!(value, 0.0);
+                assert!(value.is_sign_negative());
+            }
+            other => panic!("unexpected value {:?}", other),
+        }
+
+        for value in [f32::NAN, f32::INFINITY, f32::NEG_INFINITY] {
+            let mut out = Vec::new();
+            let result =
+                FieldValue::Float(Some(value)).write_as(&field_info, &UnicodeLossy, &mut out);
+            assert!(matches!(result, Err(ErrorKind::InvalidFieldValue(_))));
+            assert!(out.is_empty());
+        }
+        let numeric = create_temp_field_info(FieldType::Numeric, 10);
+        let result =
+            FieldValue::Numeric(Some(f64::NAN)).write_as(&numeric, &UnicodeLossy, &mut Vec::new());
+        assert!(matches!(result, Err(ErrorKind::InvalidFieldValue(text)) if text == "NaN"));
+
+        for field_type in [FieldType::Double, FieldType::DBase7Double] {
+            let double = create_temp_field_info(field_type, 8);
+            for value in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+                let mut out = Vec::new();
+                let result = FieldValue::Double(value).write_as(&double, &UnicodeLossy, &mut out);
+                assert!(matches!(result, Err(ErrorKind::InvalidFieldValue(_))));
+                assert!(out.is_empty());
+            }
+        }
+    }
+
+    #[test]
+    fn read_numbers_made_of_asterisks_or_spaces() {
+        let read = |field_info: &FieldInfo, bytes: &[u8], policy| {
+            FieldValue::read_from::<Cursor<Vec<u8>>, _>(
+                bytes,
+                &mut None,
+                field_info,
+                Dialect::DBase,
+                &UnicodeLossy,
+                CharacterTrim::default(),
+                policy,
+            )
+        };
+        let mut numeric = create_temp_field_info(FieldType::Numeric, 6);
+        numeric.num_decimal_places = 2;
+        let float = create_temp_field_info(FieldType::Float, 3);
+
+        for policy in [
+            OverflowPolicy::ReturnError,
+            OverflowPolicy::ReturnNull,
+            OverflowPolicy::ReturnMaxValue,
+        ] {
+            assert_eq!(
+                read(&numeric, b"      ", policy).unwrap(),
+                FieldValue::Numeric(None)
+            );
+        }
+
+        assert!(matches!(
+            read(&numeric, b"******", OverflowPolicy::ReturnError),
+            Err(ErrorKind::NumericOverflow {
+                value: None,
+                width: 6
+            })
+        ));
+        assert!(matches!(
+            read(&float, b"***", OverflowPolicy::ReturnError),
+            Err(ErrorKind::NumericOverflow {
+                value: None,
+                width: 3
+            })
+        ));
+
+        assert_eq!(
+            read(&numeric, b"******", OverflowPolicy::ReturnNull).unwrap(),
+            FieldValue::Numeric(None)
+        );
+        assert_eq!(
+            read(&float, b"***", OverflowPolicy::ReturnNull).unwrap(),
+            FieldValue::Float(None)
+        );
+
+        assert_eq!(
+            read(&numeric, b"******", OverflowPolicy::ReturnMaxValue).unwrap(),
+            FieldValue::Numeric(Some(999.99))
+        );
+        assert_eq!(
+            read(&float, b"***", OverflowPolicy::ReturnMaxValue).unwrap(),
+            FieldValue::Float(Some(999.0))
+        );
+    }
+
+    #[test]
+    fn currency_is_stored_as_ten_thousandths() {
+        let field_info = create_temp_field_info(FieldType::Currency, 8);
+        let values = [
+            (0.1, 1_000i64),
+            (0.01, 100),
+            (123.45, 1_234_500),
+            (1234567890.1234, 12_345_678_901_234),
+            (-42.0001, -420_001),
+            // Rounded to the nearest ten-thousandth, halfway values away from zero
+            (0.00005, 1),
+            (-0.00005, -1),
+            (2.00004, 20_000),
+        ];
+        for (value, scaled) in values.iter() {
+            let currency = FieldValue::currency_from_f64(*value).unwrap();
+            assert_eq!(currency, FieldValue::Currency(*scaled));
+            let mut out = Cursor::new(Vec::new());
+            currency
+                .write_as(&field_info, &UnicodeLossy, &mut out)
+                .unwrap();
+            assert_eq!(out.get_ref(), &scaled.to_le_bytes());
+        }
+
+        // The number of ten-thousandths is read back exactly, whatever its size
+        for scaled in [i64::MAX, i64::MIN + 1, i64::MIN, 9_007_199_254_740_993] {
+            let mut out = Cursor::new(Vec::new());
+            FieldValue::Currency(scaled)
+                .write_as(&field_info, &UnicodeLossy, &mut out)
+                .unwrap();
+            let read = FieldValue::read_from::<Cursor<Vec<u8>>, _>(
+                out.get_ref(),
+                &mut None,
+                &field_info,
+                Dialect::DBase,
+                &UnicodeLossy,
+                CharacterTrim::default(),
+                OverflowPolicy::default(),
+            )
+            .unwrap();
+            assert_eq!(read, FieldValue::Currency(scaled));
+            assert_eq!(read.currency_ten_thousandths(), Some(scaled));
+        }
+
+        for value in [1e16, f64::NAN, f64::INFINITY].iter() {
+            assert!(matches!(
+                FieldValue::currency_from_f64(*value),
+                Err(ErrorKind::NumericOverflow { width: 8, .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn write_number_matches_string_formatting() {
+        let values = [0.0, -0.5, 2.5625, 123456789.98765, -1e40, f64::MAX];
+        for value in values.iter() {
+            for num_decimal_places in [0u8, 2, 10].iter() {
+                let mut out = Vec::new();
+                write_number(*value, *num_decimal_places, &UnicodeLossy, &mut out).unwrap();
+
+                let expected = format!(
+                    "{value:.precision$}",
+                    value = value,
+                    precision = *num_decimal_places as usize
+                );
+                assert_eq!(out, expected.as_bytes());
+            }
+        }
+    }
+
+    #[test]
+    fn add_and_diff_days() {
+        let date = Date::new(28, 2, 2023);
+        assert_eq!(date.add_days(1).unwrap(), Date::new(1, 3, 2023));
+        assert_eq!(
+            Date::new(28, 2, 2024).add_days(1).unwrap(),
+            Date::new(29, 2, 2024)
+        );
+        assert_eq!(date.add_days(365).unwrap(), Date::new(28, 2, 2024));
+        assert_eq!(date.add_days(0).unwrap(), date);
+        assert_eq!(Date::new(1, 3, 2023).diff_days(&date), 1);
+
+        assert!(matches!(
+            Date::new(1, 1, 1).add_days(-1),
+            Err(ErrorKind::InvalidDate)
+        ));
+        assert!(matches!(
+            Date::new(31, 12, 9999).add_days(1),
+            Err(ErrorKind::InvalidDate)
+        ));
+        assert!(matches!(
+            Date::new(30, 2, 2023).add_days(1),
+            Err(ErrorKind::InvalidDate)
+        ));
+        assert!(matches!(
+            date.add_days(i32::MAX),
+            Err(ErrorKind::InvalidDate)
+        ));
+    }
+
+    #[test]
+    fn diff_days_at_the_limits_of_the_years() {
+        // The year 0 is a leap year of the proleptic Gregorian calendar
+        assert_eq!(Date::new(1, 1, 1).diff_days(&Date::new(1, 1, 0)), 366);
+        assert_eq!(Date::new(1, 3, 0).diff_days(&Date::new(1, 1, 0)), 60);
+        assert_eq!(Date::new(1, 1, 0).diff_days(&Date::new(1, 1, 1)), -366);
+        // Date::new does not accept more than 4 digits
+        let first_day_of = |year| Date {
+            year,
+            month: 1,
+            day: 1,
+        };
+        // 400 years always have 146 097 days
+        assert_eq!(
+            first_day_of(u32::MAX).diff_days(&first_day_of(u32::MAX - 400)),
+            146_097
+        );
+        assert_eq!(
+            first_day_of(u32::MAX).diff_days(&first_day_of(0)),
+            1_568_704_592_245
+        );
+    }
+
+    #[test]
+    fn add_days_then_subtract_them_gives_the_same_date() {
+        // Dates and offsets picked with a simple linear congruential generator,
+        // to check the property on many values
+        let mut state = 12345u32;
+        let mut next = |modulo: u32| {
+            state = state.wrapping_mul(1_103_515_245).wrapping_add(12345);
+            (state >> 8) % modulo
+        };
+        for _ in 0..10_000 {
+            let mut date = Date::new(1, next(12) + 1, next(9999) + 1);
+            date.day = next(date.days_in_month()) + 1;
+            let n = next(2 * 3_000_000) as i32 - 3_000_000;
+            match date.add_days(n) {
+                Ok(moved) => {
+                    assert_eq!(moved.add_days(-n).unwrap(), date);
+                    assert_eq!(moved.diff_days(&date), i64::from(n));
+                    assert!(moved.checked_julian_day_number().is_ok());
+                }
+                Err(error) => assert!(matches!(error, ErrorKind::InvalidDate)),
+            }
+        }
+    }
+
+    #[test]
+    fn add_months_clamps_the_day() {
+        let date = Date::new(31, 1, 2023);
+        assert_eq!(date.add_months(1).unwrap(), Date::new(28, 2, 2023));
+        assert_eq!(date.add_months(13).unwrap(), Date::new(29, 2, 2024));
+        assert_eq!(date.add_months(-1).unwrap(), Date::new(31, 12, 2022));
+        assert_eq!(date.add_months(-14).unwrap(), Date::new(30, 11, 2021));
+        assert_eq!(date.add_months(0).unwrap(), date);
+        assert!(matches!(
+            Date::new(15, 12, 9999).add_months(1),
+            Err(ErrorKind::InvalidDate)
+        ));
+        assert!(matches!(
+            Date::new(15, 1, 1).add_months(-1),
+            Err(ErrorKind::InvalidDate)
+        ));
+        assert!(matches!(
+            Date::new(31, 4, 2023).add_months(1),
+            Err(ErrorKind::InvalidDate)
+        ));
+    }
+
+    #[test]
+    fn default_values_can_be_written() {
+        let field_types = [
+            (FieldType::Character, 10),
+            (FieldType::Date, 8),
+            (FieldType::Float, 10),
+            (FieldType::Numeric, 10),
+            (FieldType::Logical, 1),
+            (FieldType::Currency, 8),
+            (FieldType::DateTime, 8),
+            (FieldType::Integer, 4),
+            (FieldType::AutoIncrement, 4),
+            (FieldType::Timestamp, 8),
+            (FieldType::DBase7Double, 8),
+            (FieldType::Double, 8),
+        ];
+        for (field_type, length) in field_types {
+            let mut field_info = create_temp_field_info(field_type, length);
+            field_info.num_decimal_places = 2;
+            let value = FieldValue::default_for(field_type);
+            let mut out = Cursor::new(Vec::new());
+            value
+                .write_as(&field_info, &UnicodeLossy, &mut out)
+                .unwrap_or_else(|error| panic!("{:?}: {}", field_type, error));
+            assert!(out.get_ref().len() <= usize::from(length));
+        }
+    }
+
+    #[test]
+    fn currency_decimal_strings() {
+        for (scaled, text) in [
+            (123400, "12.3400"),
+            (1, "0.0001"),
+            (-1, "-0.0001"),
+            (-123456789, "-12345.6789"),
+            (0, "0.0000"),
+            (i64::MIN, "-922337203685477.5808"),
+            (i64::MAX, "922337203685477.5807"),
+        ] {
+            assert_eq!(format_ten_thousandths(scaled), text);
+        }
+
+        // Values read from a file
+        let field_info = create_temp_field_info(FieldType::Currency, 8);
+        for scaled in [123400i64, -5, 9_007_199_254_740_991] {
+            let value = FieldValue::read_from::<Cursor<Vec<u8>>, _>(
+                &scaled.to_le_bytes(),
+                &mut None,
+                &field_info,
+                Dialect::DBase,
+                &UnicodeLossy,
+                CharacterTrim::default(),
+                OverflowPolicy::default(),
+            )
+            .unwrap();
+            assert_eq!(value.currency_ten_thousandths(), Some(scaled));
+        }
+        assert_eq!(
+            FieldValue::Currency(i64::MAX)
+                .to_decimal_string()
+                .as_deref(),
+            Some("922337203685477.5807")
+        );
+        assert_eq!(
+            FieldValue::Currency(i64::MIN + 1)
+                .to_decimal_string()
+                .as_deref(),
+            Some("-922337203685477.5807")
+        );
+        assert_eq!(FieldValue::Double(0.1).to_decimal_string(), None);
+
+        // Parsing does not go through a float when it does not have to
+        for (text, scaled) in [
+            ("922337203685477.5807", i64::MAX),
+            ("-922337203685477.5807", i64::MIN + 1),
+            ("12.34", 123_400),
+            ("+7", 70_000),
+            ("1.5e3", 15_000_000),
+        ] {
+            assert_eq!(
+                FieldValue::parse(text, FieldType::Currency).unwrap(),
+                FieldValue::Currency(scaled)
+            );
+        }
+    }
+
+    #[test]
+    fn validate_date() {
+        assert!(Date::new(29, 2, 2000).validate().is_ok());
+
+        // Date::new panics for invalid months and days
+        let out_of_range_dates = [
+            Date {
+                year: 2000,
+                month: 13,
+                day: 1,
+            },
+            Date {
+                year: 2000,
+                month: 1,
+                day: 32,
+            },
+            Date::new(31, 12, 1899),
+            Date::new(1, 1, 2156),
+            Date::new(29, 2, 2023),
+            Date::new(29, 2, 1900),
+            Date::new(31, 4, 2023),
+        ];
+        for date in out_of_range_dates.iter() {
+            assert!(matches!(
+                date.validate(),
+                Err(ErrorKind::DateOutOfRange { year, month, day })
+                    if year == date.year && month == date.month && day == date.day
+            ));
+        }
+    }
+
+    #[test]
+    fn leap_years_and_days_in_month() {
+        assert!(Date::new(1, 1, 2024).is_leap_year());
+        assert!(Date::new(1, 1, 2000).is_leap_year());
+        assert!(!Date::new(1, 1, 2023).is_leap_year());
+        assert!(!Date::new(1, 1, 1900).is_leap_year());
+        assert!(!Date::new(1, 1, 2100).is_leap_year());
+
+        assert_eq!(Date::new(1, 2, 2023).days_in_month(), 28);
+        assert_eq!(Date::new(1, 2, 2024).days_in_month(), 29);
+        assert_eq!(Date::new(1, 2, 1900).days_in_month(), 28);
+        assert_eq!(Date::new(1, 2, 2100).days_in_month(), 28);
+        let days = (1..=12)
+            .map(|month| Date::new(1, month, 2023).days_in_month())
+            .collect::<Vec<_>>();
+        assert_eq!(days, [31, 28, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31]);
+
+        assert!(Date::new(28, 2, 2023).validate().is_ok());
+        assert!(Date::new(29, 2, 2024).validate().is_ok());
+        assert!(Date::new(30, 4, 2023).validate().is_ok());
+        assert!(Date::new(31, 12, 2023).validate().is_ok());
+    }
+
+    #[test]
+    fn invalid_date_is_not_written() {
+        let field_info = create_temp_field_info(FieldType::Date, FieldType::Date.size().unwrap());
+        let date = Date {
+            year: 2000,
+            month: 13,
+            day: 1,
+        };
+        let mut out = Cursor::new(Vec::<u8>::new());
+        let result = date.write_as(&field_info, &UnicodeLossy, &mut out);
+        assert!(matches!(result, Err(ErrorKind::InvalidDate)));
+        assert!(out.get_ref().is_empty());
+    }
+
     #[test]
     fn test_from_julian_day_number() {
         let date = Date::julian_day_number_to_gregorian_date(2458685);
@@ -1066,4 +2776,320 @@ mod test {
         let datetime = DateTime::new(Date::new(1, 1, 1970), Time::new(1, 1, 1));
         assert_eq!(datetime.to_unix_timestamp(), 3661);
     }
+
+    #[test]
+    fn test_coerce_to() {
+        let text = |s: &str| FieldValue::Character(Some(s.to_string()));
+
+        assert_eq!(
+            text(" 42.5 ").coerce_to(FieldType::Numeric).unwrap(),
+            FieldValue::Numeric(Some(42.5))
+        );
+        assert_eq!(
+            FieldValue::Character(None)
+                .coerce_to(FieldType::Numeric)
+                .unwrap(),
+            FieldValue::Numeric(None)
+        );
+        assert_eq!(
+            FieldValue::Numeric(Some(1.0))
+                .coerce_to(FieldType::Logical)
+                .unwrap(),
+            FieldValue::Logical(Some(true))
+        );
+        assert_eq!(
+            text("n").coerce_to(FieldType::Logical).unwrap(),
+            FieldValue::Logical(Some(false))
+        );
+        assert_eq!(
+            text("20230615").coerce_to(FieldType::Date).unwrap(),
+            FieldValue::Date(Some(Date::new(15, 6, 2023)))
+        );
+        assert_eq!(
+            FieldValue::Date(Some(Date::new(15, 6, 2023)))
+                .coerce_to(FieldType::Character)
+                .unwrap(),
+            text("20230615")
+        );
+        assert_eq!(
+            FieldValue::Date(Some(Date::new(15, 6, 2023)))
+                .coerce_to(FieldType::DateTime)
+                .unwrap(),
+            FieldValue::DateTime(DateTime::new(Date::new(15, 6, 2023), Time::new(0, 0, 0)))
+        );
+        assert_eq!(
+            FieldValue::Numeric(Some(12.0))
+                .coerce_to(FieldType::Integer)
+                .unwrap(),
+            FieldValue::Integer(12)
+        );
+        assert_eq!(
+            FieldValue::Integer(7).coerce_to(FieldType::Double).unwrap(),
+            FieldValue::Double(7.0)
+        );
+        assert_eq!(
+            text("memo").coerce_to(FieldType::Memo).unwrap(),
+            FieldValue::Memo("memo".to_string())
+        );
+    }
+
+    #[test]
+    fn test_coerce_to_errors() {
+        let text = |s: &str| FieldValue::Character(Some(s.to_string()));
+
+        assert!(matches!(
+            FieldValue::Logical(Some(true)).coerce_to(FieldType::Date),
+            Err(ErrorKind::IncompatibleCoercion {
+                from: FieldType::Logical,
+                to: FieldType::Date
+            })
+        ));
+        assert!(matches!(
+            FieldValue::Numeric(Some(12.5)).coerce_to(FieldType::Integer),
+            Err(ErrorKind::IncompatibleCoercion { .. })
+        ));
+        assert!(matches!(
+            FieldValue::Numeric(None).coerce_to(FieldType::Integer),
+            Err(ErrorKind::IncompatibleCoercion { .. })
+        ));
+        assert!(matches!(
+            text("abc").coerce_to(FieldType::Numeric),
+            Err(ErrorKind::ParseFloatError(_))
+        ));
+        assert!(matches!(
+            text("2023").coerce_to(FieldType::Date),
+            Err(ErrorKind::IncompatibleCoercion { .. })
+        ));
+        assert!(matches!(
+            text("20231345").coerce_to(FieldType::Date),
+            Err(ErrorKind::InvalidDate)
+        ));
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            FieldValue::parse(" 42.5 ", FieldType::Numeric).unwrap(),
+            FieldValue::Numeric(Some(42.5))
+        );
+        assert_eq!(
+            FieldValue::parse("", FieldType::Numeric).unwrap(),
+            FieldValue::Numeric(None)
+        );
+        assert_eq!(
+            FieldValue::parse("-12", FieldType::Integer).unwrap(),
+            FieldValue::Integer(-12)
+        );
+        assert_eq!(
+            FieldValue::parse("1.5", FieldType::Double).unwrap(),
+            FieldValue::Double(1.5)
+        );
+        assert_eq!(
+            FieldValue::parse("FALSE", FieldType::Logical).unwrap(),
+            FieldValue::Logical(Some(false))
+        );
+        assert_eq!(
+            FieldValue::parse("1", FieldType::Logical).unwrap(),
+            FieldValue::Logical(Some(true))
+        );
+        assert_eq!(
+            FieldValue::parse("20230615", FieldType::Date).unwrap(),
+            FieldValue::Date(Some(Date::new(15, 6, 2023)))
+        );
+        assert_eq!(
+            FieldValue::parse(" padded ", FieldType::Character).unwrap(),
+            FieldValue::Character(Some(" padded ".to_string()))
+        );
+
+        assert!(matches!(
+            FieldValue::parse("abc", FieldType::Float),
+            Err(ErrorKind::ParseFloatError(_))
+        ));
+        assert!(matches!(
+            FieldValue::parse("1.5", FieldType::Integer),
+            Err(ErrorKind::ParseIntError(_))
+        ));
+        assert!(matches!(
+            FieldValue::parse("yes please", FieldType::Logical),
+            Err(ErrorKind::ParseError {
+                field_type: FieldType::Logical,
+                ..
+            })
+        ));
+        assert!(matches!(
+            FieldValue::parse("2023-06-15", FieldType::Date),
+            Err(ErrorKind::ParseError { .. })
+        ));
+    }
+
+    #[test]
+    fn logical_and_date_texts_are_read_the_same_way() {
+        let text = |s: &str| FieldValue::Character(Some(s.to_string()));
+
+        let field_info = create_temp_field_info(FieldType::Logical, 1);
+        for (input, expected) in [
+            ("T", Some(true)),
+            ("y", Some(true)),
+            ("1", Some(true)),
+            ("f", Some(false)),
+            ("N", Some(false)),
+            ("0", Some(false)),
+            ("?", None),
+            (" ", None),
+        ] {
+            let expected = FieldValue::Logical(expected);
+            let from_bytes = FieldValue::from_bytes(input.as_bytes(), &field_info).unwrap();
+            assert_eq!(from_bytes, expected, "{:?}", input);
+            let parsed = FieldValue::parse(input, FieldType::Logical).unwrap();
+            assert_eq!(parsed, expected, "{:?}", input);
+            let coerced = text(input).coerce_to(FieldType::Logical).unwrap();
+            assert_eq!(coerced, expected, "{:?}", input);
+        }
+
+        let field_info = create_temp_field_info(FieldType::Date, 8);
+        for (input, expected) in [
+            ("20230615", Some(Date::new(15, 6, 2023))),
+            ("        ", None),
+            ("00000000", None),
+        ] {
+            let expected = FieldValue::Date(expected);
+            let from_bytes = FieldValue::from_bytes(input.as_bytes(), &field_info).unwrap();
+            assert_eq!(from_bytes, expected, "{:?}", input);
+            let parsed = FieldValue::parse(input, FieldType::Date).unwrap();
+            assert_eq!(parsed, expected, "{:?}", input);
+            let coerced = text(input).coerce_to(FieldType::Date).unwrap();
+            assert_eq!(coerced, expected, "{:?}", input);
+        }
+        for input in ["2023    ", "2023-6-1", "20230230"] {
+            assert!(FieldValue::from_bytes(input.as_bytes(), &field_info).is_err());
+            assert!(FieldValue::parse(input, FieldType::Date).is_err());
+            assert!(text(input).coerce_to(FieldType::Date).is_err());
+        }
+    }
+
+    #[test]
+    fn test_as_display() {
+        let display = |value: FieldValue| value.as_display().to_string();
+        assert_eq!(
+            display(FieldValue::Character(Some("text".to_string()))),
+            "text"
+        );
+        assert_eq!(display(FieldValue::Character(None)), "");
+        assert_eq!(display(FieldValue::Memo("memo".to_string())), "memo");
+        assert_eq!(display(FieldValue::Numeric(Some(-1.25))), "-1.25");
+        assert_eq!(display(FieldValue::Numeric(None)), "");
+        assert_eq!(display(FieldValue::Float(Some(0.5))), "0.5");
+        assert_eq!(display(FieldValue::Integer(17)), "17");
+        assert_eq!(display(FieldValue::Currency(35_000)), "3.5000");
+        assert_eq!(display(FieldValue::Logical(Some(false))), "F");
+        assert_eq!(display(FieldValue::Logical(None)), "");
+        assert_eq!(
+            display(FieldValue::Date(Some(Date::new(5, 3, 2021)))),
+            "20210305"
+        );
+        assert_eq!(
+            display(FieldValue::DateTime(DateTime::new(
+                Date::new(5, 3, 2021),
+                Time::new(7, 8, 9)
+            ))),
+            "20210305 07:08:09"
+        );
+        assert_eq!(
+            display(FieldValue::Picture(vec![0x00, 0xAB, 0x10])),
+            "00ab10"
+        );
+        assert_eq!(display(FieldValue::BinCharacter(None)), "");
+    }
+
+    #[test]
+    fn memo_pointers_of_each_dialect() {
+        let dbase = Dialect::from(crate::Version::DBase3 {
+            supports_memo: true,
+        });
+        let fox_pro = Dialect::from(crate::Version::VisualFoxPro);
+        assert_eq!(dbase, Dialect::DBase);
+        assert_eq!(fox_pro, Dialect::VisualFoxPro);
+
+        // dBase III: 10 ASCII digits
+        let index = read_memo_block_index(b"       258", dbase, &UnicodeLossy).unwrap();
+        assert_eq!(index, Some(258));
+        let index = read_memo_block_index(b"          ", dbase, &UnicodeLossy).unwrap();
+        assert_eq!(index, None);
+
+        // FoxPro: little endian u32, which is not valid ASCII
+        let le_bytes = 258u32.to_le_bytes();
+        let index = read_memo_block_index(&le_bytes, fox_pro, &UnicodeLossy).unwrap();
+        assert_eq!(index, Some(258));
+        assert!(read_memo_block_index(b"  25", fox_pro, &UnicodeLossy)
+            .is_ok_and(|index| index != Some(25)));
+    }
+
+    #[test]
+    fn date_header_and_field_formats() {
+        let date = Date::new(15, 6, 2023);
+        let mut header_bytes = Vec::new();
+        date.write_header_format(&mut header_bytes).unwrap();
+        assert_eq!(header_bytes, [123, 6, 15]);
+        assert_eq!(Date::from_header_format([123, 6, 15]), date);
+
+        let mut field_bytes = Vec::new();
+        date.write_field_format(&UnicodeLossy, &mut field_bytes)
+            .unwrap();
+        assert_eq!(field_bytes, b"20230615");
+
+        // Date values are written in the field format
+        let field_info = create_temp_field_info(FieldType::Date, 8);
+        let mut out = Vec::new();
+        FieldValue::Date(Some(date))
+            .write_as(&field_info, &UnicodeLossy, &mut out)
+            .unwrap();
+        assert_eq!(out, b"20230615");
+    }
+
+    #[test]
+    fn field_value_to_and_from_bytes() {
+        let values = [
+            (
+                FieldType::Character,
+                6,
+                FieldValue::Character(Some("abc".to_string())),
+            ),
+            (FieldType::Numeric, 6, FieldValue::Numeric(Some(-12.0))),
+            (FieldType::Logical, 1, FieldValue::Logical(Some(false))),
+            (FieldType::Integer, 4, FieldValue::Integer(-7)),
+            (FieldType::Double, 8, FieldValue::Double(0.5)),
+        ];
+        let expected: [&[u8]; 5] = [
+            b"abc   ",
+            b"   -12",
+            b"f",
+            &(-7i32).to_le_bytes(),
+            &0.5f64.to_le_bytes(),
+        ];
+        for ((field_type, length, value), expected) in values.into_iter().zip(expected) {
+            let field_info = create_temp_field_info(field_type, length);
+            let bytes = value.to_bytes(&field_info).unwrap();
+            assert_eq!(bytes, expected);
+            assert_eq!(FieldValue::from_bytes(&bytes, &field_info).unwrap(), value);
+        }
+
+        // Too long strings are truncated, like when writing records
+        let field_info = create_temp_field_info(FieldType::Character, 2);
+        let value = FieldValue::Character(Some("abc".to_string()));
+        assert_eq!(value.to_bytes(&field_info).unwrap(), b"ab");
+
+        let error = FieldValue::from_bytes(b"abc", &field_info).unwrap_err();
+        assert!(matches!(
+            error.kind(),
+            ErrorKind::FieldLengthMismatch {
+                expected: 2,
+                actual: 3
+            }
+        ));
+        let error = FieldValue::Logical(Some(true))
+            .to_bytes(&field_info)
+            .unwrap_err();
+        assert!(matches!(error.kind(), ErrorKind::IncompatibleType));
+        assert_eq!(error.field().as_ref(), Some(&field_info));
+    }
 }
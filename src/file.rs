@@ -1,24 +1,31 @@
 use crate::encoding::DynEncoding;
-use crate::field::{DeletionFlag, FieldsInfo, DELETION_FLAG_SIZE};
-use crate::header::Header;
+use crate::field::{validate_record_size, DeletionFlag, FieldsInfo, DELETION_FLAG_SIZE};
+use crate::header::{Dialect, Header};
 use crate::memo::MemoReader;
-use crate::reading::{ReadingOptions, BACKLINK_SIZE, TERMINATOR_VALUE};
-use crate::writing::{write_header_parts, WritableAsDbaseField};
+use crate::reading::ReadingOptions;
+use crate::writing::{
+    field_buffer_size, write_header_parts, NumericOverflowPolicy, WritableAsDbaseField,
+    FILE_TERMINATOR,
+};
 use crate::ErrorKind::UnsupportedCodePage;
 use crate::{
-    Error, ErrorKind, FieldConversionError, FieldIOError, FieldInfo, FieldIterator, FieldValue,
-    FieldWriter, ReadableRecord, TableInfo, WritableRecord,
+    Error, ErrorKind, FieldConversionError, FieldIOError, FieldInfo, FieldIterator, FieldType,
+    FieldValue, FieldWriter, ReadableRecord, TableInfo, WritableRecord,
 };
-use byteorder::ReadBytesExt;
 use std::fmt::{Debug, Formatter};
-use std::io::{BufReader, BufWriter, Cursor, Read, Seek, SeekFrom, Write};
+#[cfg(not(target_arch = "wasm32"))]
+use std::io::{BufReader, BufWriter};
+use std::io::{Cursor, Read, Seek, SeekFrom, Write};
+#[cfg(not(target_arch = "wasm32"))]
 use std::path::Path;
 
+#[cfg(not(target_arch = "wasm32"))]
 pub struct BufReadWriteFile {
     input: BufReader<std::fs::File>,
     output: BufWriter<std::fs::File>,
 }
 
+#[cfg(not(target_arch = "wasm32"))]
 impl BufReadWriteFile {
     fn new(file: std::fs::File) -> std::io::Result<Self> {
         let input = BufReader::new(file.try_clone()?);
@@ -28,12 +35,14 @@ impl BufReadWriteFile {
     }
 }
 
+#[cfg(not(target_arch = "wasm32"))]
 impl Read for BufReadWriteFile {
     fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
         self.input.read(buf)
     }
 }
 
+#[cfg(not(target_arch = "wasm32"))]
 impl Write for BufReadWriteFile {
     fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
         self.output.write(buf)
@@ -44,6 +53,7 @@ impl Write for BufReadWriteFile {
     }
 }
 
+#[cfg(not(target_arch = "wasm32"))]
 impl Seek for BufReadWriteFile {
     fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
         self.output.seek(pos)?;
@@ -124,14 +134,16 @@ where
 
         let start_pos = self.position_in_record();
         let field_bytes = &mut self.file.record_data_buffer.get_mut()
-            [start_pos..start_pos + field_info.field_length as usize];
+            [start_pos..start_pos + field_info.record_length()];
 
         FieldValue::read_from(
             field_bytes,
             &mut self.file.memo_reader,
             field_info,
+            Dialect::from(self.file.header.file_type),
             &self.file.encoding,
             self.file.options.character_trim,
+            self.file.options.numeric_overflow,
         )
         .map_err(|e| {
             Error::new(
@@ -177,7 +189,7 @@ where
 
         let start_pos = self.position_in_record();
         let field_bytes = &mut self.file.record_data_buffer.get_mut()
-            [start_pos..start_pos + field_info.field_length as usize];
+            [start_pos..start_pos + field_info.record_length()];
         field_bytes.fill(0);
 
         // Note that since we modify the internal buffer, we don't need to re-read the
@@ -319,6 +331,7 @@ where
             memo_reader: &mut self.file.memo_reader,
             field_data_buffer: &mut self.file.field_data_buffer,
             encoding: &self.file.encoding,
+            dialect: Dialect::from(self.file.header.file_type),
             options: self.file.options,
         };
 
@@ -361,8 +374,9 @@ where
         let mut field_writer = FieldWriter {
             dst: &mut self.file.record_data_buffer,
             fields_info: self.file.fields_info.iter().peekable(),
-            field_buffer: &mut Cursor::new(&mut self.file.field_data_buffer),
+            field_buffer: &mut Cursor::new(&mut self.file.field_data_buffer[..]),
             encoding: &self.file.encoding,
+            numeric_overflow: NumericOverflowPolicy::default(),
         };
 
         record
@@ -456,9 +470,9 @@ pub struct File<T> {
     /// Buffer that contains a whole record worth of data
     /// It also contains the deletion flag
     record_data_buffer: Cursor<Vec<u8>>,
-    /// Non-Memo field length is stored on a u8,
-    /// so fields cannot exceed 255 bytes
-    field_data_buffer: [u8; 255],
+    /// Buffer where a field is read or written, as long as the longest field
+    /// (at least 255 bytes), see [field_buffer_size]
+    field_data_buffer: Vec<u8>,
     pub(crate) options: ReadingOptions,
     /// We track the position in the file
     /// to avoid calling `seek` when we are reading buffer
@@ -485,6 +499,24 @@ impl<T> File<T> {
         self.header.num_records as usize
     }
 
+    /// Returns the header of the file
+    pub fn header(&self) -> &Header {
+        &self.header
+    }
+
+    /// Returns the header of the file, to change it before writing it
+    /// with [File::flush_header]
+    ///
+    /// Only the fields that do not describe the layout of the records should be changed,
+    /// such as the code page mark, the encryption flag or the date of the last update:
+    /// the number of records, the offset to the first record and the size of records
+    /// are used to find the records.
+    /// Changing the code page mark does not change the encoding used to read and write
+    /// the records.
+    pub fn header_mut(&mut self) -> &mut Header {
+        &mut self.header
+    }
+
     pub fn set_options(&mut self, options: ReadingOptions) {
         self.options = options;
     }
@@ -492,33 +524,51 @@ impl<T> File<T> {
 
 impl<T: Read + Seek> File<T> {
     /// creates of File using source as the storage space.
-    pub fn open(mut source: T) -> Result<Self, Error> {
-        let mut header =
-            Header::read_from(&mut source).map_err(|error| Error::io_error(error, 0))?;
+    ///
+    /// The source is read in small chunks, so if it is not already in memory,
+    /// it should be buffered by the caller.
+    /// The path based functions (e.g. [File::open_read_only]) do that for you.
+    ///
+    /// Files whose version is not [supported](crate::Version::is_supported)
+    /// are rejected with [ErrorKind::UnsupportedVersion].
+    pub fn open(source: T) -> Result<Self, Error> {
+        Self::open_with_reading_options(source, ReadingOptions::default())
+    }
 
-        let offset = if header.file_type.is_visual_fox_pro() {
-            if BACKLINK_SIZE > header.offset_to_first_record {
-                panic!("Invalid file");
-            }
-            header.offset_to_first_record - BACKLINK_SIZE
-        } else {
-            header.offset_to_first_record
-        };
-        let num_fields =
-            (offset as usize - Header::SIZE - std::mem::size_of::<u8>()) / FieldInfo::SIZE;
+    /// Opens the file with the given options, a [lenient](ReadingOptions::lenient)
+    /// file accepts field descriptors that are not followed by their terminator
+    pub(crate) fn open_with_reading_options(
+        mut source: T,
+        options: ReadingOptions,
+    ) -> Result<Self, Error> {
+        let mut header = Header::read_from(&mut source)?;
 
-        let fields_info =
-            FieldsInfo::read_from(&mut source, num_fields).map_err(|error| Error {
+        let require_terminator = !options.lenient;
+        let max_num_fields = header
+            .max_num_fields(require_terminator)
+            .map_err(|kind| Error {
                 record_num: 0,
                 field: None,
-                kind: error,
+                kind,
             })?;
+        if header.file_type.is_dbase7() {
+            let mut _level7_extension = [0u8; Header::LEVEL7_EXTENSION_SIZE];
+            source
+                .read_exact(&mut _level7_extension)
+                .map_err(|error| Error::io_error(error, 0))?;
+        }
 
-        let terminator = source
-            .read_u8()
-            .map_err(|error| Error::io_error(error, 0))?;
-
-        debug_assert_eq!(terminator, TERMINATOR_VALUE);
+        let fields_info = FieldsInfo::read_from(
+            &mut source,
+            max_num_fields,
+            header.file_type,
+            require_terminator,
+        )
+        .map_err(|error| Error {
+            record_num: 0,
+            field: None,
+            kind: error,
+        })?;
 
         source
             .seek(SeekFrom::Start(u64::from(header.offset_to_first_record)))
@@ -529,6 +579,12 @@ impl<T: Read + Seek> File<T> {
             Error::new(field_error, 0)
         })?;
 
+        validate_record_size(&header, fields_info.as_ref()).map_err(|kind| Error {
+            record_num: 0,
+            field: None,
+            kind,
+        })?;
+
         let record_size: usize = DELETION_FLAG_SIZE + fields_info.size_of_all_fields();
         let record_data_buffer = Cursor::new(vec![0u8; record_size]);
         // Some file seems not to include the DELETION_FLAG_SIZE into the record size,
@@ -540,11 +596,11 @@ impl<T: Read + Seek> File<T> {
             inner: source,
             memo_reader: None,
             header,
+            field_data_buffer: vec![0u8; field_buffer_size(fields_info.as_ref())],
             fields_info,
             encoding,
             record_data_buffer,
-            field_data_buffer: [0u8; 255],
-            options: ReadingOptions::default(),
+            options,
             file_position: header.offset_to_first_record as u64,
         })
     }
@@ -615,7 +671,7 @@ impl<T: Write + Seek> File<T> {
             + table_info
                 .fields_info
                 .iter()
-                .map(|i| i.field_length as usize)
+                .map(FieldInfo::record_length)
                 .sum::<usize>();
         let record_data_buffer = Cursor::new(vec![0u8; record_size]);
         let file_position = table_info.header.offset_to_first_record as u64;
@@ -624,12 +680,12 @@ impl<T: Write + Seek> File<T> {
             inner: dst,
             memo_reader: None,
             header: table_info.header,
+            field_data_buffer: vec![0u8; field_buffer_size(&table_info.fields_info)],
             fields_info: FieldsInfo {
                 inner: table_info.fields_info,
             },
             encoding: table_info.encoding,
             record_data_buffer,
-            field_data_buffer: [0u8; 255],
             options: ReadingOptions::default(),
             file_position,
         })
@@ -668,8 +724,9 @@ impl<T: Write + Seek> File<T> {
             let mut field_writer = FieldWriter {
                 dst: &mut self.inner,
                 fields_info: self.fields_info.iter().peekable(),
-                field_buffer: &mut Cursor::new(&mut self.field_data_buffer),
+                field_buffer: &mut Cursor::new(&mut self.field_data_buffer[..]),
                 encoding: &self.encoding,
+                numeric_overflow: NumericOverflowPolicy::default(),
             };
 
             field_writer
@@ -689,6 +746,28 @@ impl<T: Write + Seek> File<T> {
         Ok(())
     }
 
+    /// Writes the header at the beginning of the file, the rest of the file is not changed
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dbase::CodePageMark;
+    ///
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let mut data = std::fs::read("tests/data/stations.dbf").unwrap();
+    /// let mut file = dbase::File::open(std::io::Cursor::new(&mut data))?;
+    /// file.header_mut().code_page_mark = CodePageMark::CP1252;
+    /// file.flush_header()?;
+    ///
+    /// let reader = dbase::Reader::from_bytes(&data)?;
+    /// assert!(matches!(reader.header().code_page_mark, CodePageMark::CP1252));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn flush_header(&mut self) -> Result<(), Error> {
+        self.sync_all().map_err(|error| Error::io_error(error, 0))
+    }
+
     pub fn sync_all(&mut self) -> std::io::Result<()> {
         let current_pos = self.inner.stream_position()?;
         self.inner.seek(SeekFrom::Start(0))?;
@@ -698,6 +777,242 @@ impl<T: Write + Seek> File<T> {
     }
 }
 
+impl<T: Read + Write + Seek> File<T> {
+    /// Physically removes the records marked as deleted,
+    /// like dBase's `PACK` command.
+    ///
+    /// Live records are moved over the deleted ones, keeping their order,
+    /// and the number of records in the header is updated.
+    ///
+    /// The size of the underlying file is not changed,
+    /// the (now unused) bytes after the file terminator are left untouched.
+    pub fn pack(&mut self) -> Result<(), Error> {
+        let record_size = u64::from(self.header.size_of_record);
+        let first_record_pos = u64::from(self.header.offset_to_first_record);
+        let mut record_data = vec![0u8; record_size as usize];
+
+        let mut num_live_records = 0u32;
+        for index in 0..self.header.num_records {
+            let record_num = index as usize;
+            self.inner
+                .seek(SeekFrom::Start(
+                    first_record_pos + u64::from(index) * record_size,
+                ))
+                .map_err(|error| Error::io_error(error, record_num))?;
+            self.inner
+                .read_exact(&mut record_data)
+                .map_err(|error| Error::io_error(error, record_num))?;
+
+            if DeletionFlag::from_byte(record_data[0]) == DeletionFlag::Deleted {
+                continue;
+            }
+
+            if num_live_records != index {
+                self.inner
+                    .seek(SeekFrom::Start(
+                        first_record_pos + u64::from(num_live_records) * record_size,
+                    ))
+                    .map_err(|error| Error::io_error(error, record_num))?;
+                self.inner
+                    .write_all(&record_data)
+                    .map_err(|error| Error::io_error(error, record_num))?;
+            }
+            num_live_records += 1;
+        }
+
+        self.inner
+            .seek(SeekFrom::Start(
+                first_record_pos + u64::from(num_live_records) * record_size,
+            ))
+            .and_then(|_| self.inner.write_all(&[FILE_TERMINATOR]))
+            .map_err(|error| Error::io_error(error, num_live_records as usize))?;
+
+        self.header.num_records = num_live_records;
+        self.sync_all()
+            .map_err(|error| Error::io_error(error, num_live_records as usize))?;
+        // Records have moved, make sure the next read does not use stale data
+        self.file_position = self
+            .inner
+            .stream_position()
+            .map_err(|error| Error::io_error(error, num_live_records as usize))?;
+
+        Ok(())
+    }
+
+    /// Removes all the records, keeping the fields,
+    /// like dBase's `ZAP` command.
+    ///
+    /// As with [Self::pack], the size of the underlying file is not changed.
+    pub fn zap(&mut self) -> Result<(), Error> {
+        self.inner
+            .seek(SeekFrom::Start(u64::from(
+                self.header.offset_to_first_record,
+            )))
+            .and_then(|_| self.inner.write_all(&[FILE_TERMINATOR]))
+            .map_err(|error| Error::io_error(error, 0))?;
+
+        self.header.num_records = 0;
+        self.sync_all().map_err(|error| Error::io_error(error, 0))?;
+        self.file_position = self
+            .inner
+            .stream_position()
+            .map_err(|error| Error::io_error(error, 0))?;
+
+        Ok(())
+    }
+
+    /// Adds a field at the end of the records, whose value is `default` in all the records
+    ///
+    /// As the descriptors of the fields precede the records, the whole file is rewritten:
+    /// all the records are read in memory first.
+    /// The record size and offset of the first record in the header are updated.
+    ///
+    /// Fails if a field with the same name already exists,
+    /// if the field is a memo field (memo fields cannot be written),
+    /// or if `default` cannot be written in the field.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dbase::{FieldInfo, FieldName, FieldType, FieldValue};
+    ///
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let mut data = std::fs::read("tests/data/stations.dbf").unwrap();
+    /// let mut file = dbase::File::open(std::io::Cursor::new(&mut data))?;
+    /// let info = FieldInfo::new(FieldName::try_from("open").unwrap(), FieldType::Logical, 1);
+    /// file.add_column(info, &FieldValue::Logical(Some(true)))?;
+    ///
+    /// let value = file.record(0).unwrap().read()?.remove("open");
+    /// assert_eq!(value, Some(FieldValue::Logical(Some(true))));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn add_column(&mut self, mut info: FieldInfo, default: &FieldValue) -> Result<(), Error> {
+        let make_error = |kind| Error {
+            record_num: 0,
+            field: None,
+            kind,
+        };
+        if self.field_index(&info.name).is_some() {
+            return Err(make_error(ErrorKind::DuplicateFieldName {
+                name: info.name,
+            }));
+        }
+        if matches!(info.field_type, FieldType::Memo | FieldType::Picture) {
+            return Err(make_error(ErrorKind::Message(format!(
+                "The memo field '{}' cannot be written",
+                info.name
+            ))));
+        }
+
+        let size_of_record = self
+            .header
+            .size_of_record
+            .checked_add(info.record_length() as u16);
+        let offset_to_first_record = self
+            .header
+            .offset_to_first_record
+            .checked_add(FieldInfo::size_for(self.header.file_type) as u16);
+        let (size_of_record, offset_to_first_record) =
+            match (size_of_record, offset_to_first_record) {
+                (Some(size), Some(offset)) => (size, offset),
+                (None, _) => {
+                    return Err(make_error(ErrorKind::HeaderFieldOverflow {
+                        name: "size of records",
+                        value: u32::from(self.header.size_of_record) + info.record_length() as u32,
+                    }))
+                }
+                (_, None) => {
+                    return Err(make_error(ErrorKind::HeaderFieldOverflow {
+                        name: "offset of the first record",
+                        value: u32::from(self.header.offset_to_first_record)
+                            + FieldInfo::size_for(self.header.file_type) as u32,
+                    }))
+                }
+            };
+        let displacement = DELETION_FLAG_SIZE + self.fields_info.size_of_all_fields();
+        info.displacement_field = (displacement as u32).to_le_bytes();
+        info.offset_in_record = displacement as u16;
+
+        // The default value, padded like when writing records
+        if self.field_data_buffer.len() < info.record_length() {
+            self.field_data_buffer.resize(info.record_length(), 0);
+        }
+        let mut default_data = Vec::with_capacity(info.record_length());
+        let new_fields_info = [info];
+        FieldWriter {
+            dst: &mut default_data,
+            fields_info: new_fields_info.iter().peekable(),
+            field_buffer: &mut Cursor::new(&mut self.field_data_buffer[..]),
+            encoding: &self.encoding,
+            numeric_overflow: NumericOverflowPolicy::default(),
+        }
+        .write_next_field_value(default)
+        .map_err(|error| Error::new(error, 0))?;
+        let [info] = new_fields_info;
+
+        // The header is read too, to keep the bytes that are not rewritten
+        // (the Level 7 header extension, the Visual FoxPro backlink, any padding)
+        let old_size_of_record = self.header.size_of_record as usize;
+        let mut old_header_data = vec![0u8; usize::from(self.header.offset_to_first_record)];
+        let mut records_data = vec![0u8; self.num_records() * old_size_of_record];
+        self.inner
+            .seek(SeekFrom::Start(0))
+            .and_then(|_| self.inner.read_exact(&mut old_header_data))
+            .and_then(|_| self.inner.read_exact(&mut records_data))
+            .map_err(|error| Error::io_error(error, 0))?;
+        let fields_start = if self.header.file_type.is_dbase7() {
+            Header::SIZE + Header::LEVEL7_EXTENSION_SIZE
+        } else {
+            Header::SIZE
+        };
+        let old_terminator_end = fields_start
+            + self.fields_info.len() * FieldInfo::size_for(self.header.file_type)
+            + std::mem::size_of::<u8>();
+
+        let mut header = self.header;
+        header.size_of_record = size_of_record;
+        header.offset_to_first_record = offset_to_first_record;
+        self.fields_info.inner.push(info);
+
+        let mut header_data = Vec::with_capacity(usize::from(offset_to_first_record));
+        write_header_parts(&mut header_data, &header, self.fields_info.as_ref())?;
+        // write_header_parts writes zeros where the old header may have data:
+        // put back the Level 7 extension, and what follows the terminator
+        header_data[Header::SIZE..fields_start]
+            .copy_from_slice(&old_header_data[Header::SIZE..fields_start]);
+        header_data.truncate(old_terminator_end + FieldInfo::size_for(header.file_type));
+        header_data.extend_from_slice(old_header_data.get(old_terminator_end..).unwrap_or(&[]));
+        header_data.resize(usize::from(offset_to_first_record), 0);
+        self.inner
+            .seek(SeekFrom::Start(0))
+            .and_then(|_| self.inner.write_all(&header_data))
+            .map_err(|error| Error::io_error(error, 0))?;
+
+        for (record_num, record_data) in records_data.chunks_exact(old_size_of_record).enumerate() {
+            self.inner
+                .write_all(record_data)
+                .and_then(|_| self.inner.write_all(&default_data))
+                .map_err(|error| Error::io_error(error, record_num))?;
+        }
+        self.inner
+            .write_all(&[FILE_TERMINATOR])
+            .map_err(|error| Error::io_error(error, self.num_records()))?;
+
+        self.header = header;
+        self.record_data_buffer
+            .get_mut()
+            .resize(size_of_record as usize, 0);
+        self.file_position = self
+            .inner
+            .stream_position()
+            .map_err(|error| Error::io_error(error, self.num_records()))?;
+
+        Ok(())
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
 impl File<BufReadWriteFile> {
     pub fn open_with_options<P: AsRef<Path>>(
         path: P,
@@ -706,14 +1021,16 @@ impl File<BufReadWriteFile> {
         let file = options
             .open(path)
             .map_err(|error| Error::io_error(error, 0))?;
-        File::open(BufReadWriteFile::new(file).unwrap())
+        let file = BufReadWriteFile::new(file).map_err(|error| Error::io_error(error, 0))?;
+        File::open(file)
     }
 
     /// Opens an existing dBase file in read only mode
     pub fn open_read_only<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
         let file = std::fs::File::open(path.as_ref()).map_err(|error| Error::io_error(error, 0))?;
 
-        let mut file = File::open(BufReadWriteFile::new(file).unwrap())?;
+        let file = BufReadWriteFile::new(file).map_err(|error| Error::io_error(error, 0))?;
+        let mut file = File::open(file)?;
         if file.fields_info.at_least_one_field_is_memo() {
             let p = path.as_ref();
             let memo_type = file.header.file_type.supported_memo_type();
@@ -760,7 +1077,8 @@ impl File<BufReadWriteFile> {
     pub fn create<P: AsRef<Path>>(path: P, table_info: TableInfo) -> Result<Self, Error> {
         let file = std::fs::File::create(path).map_err(|error| Error::io_error(error, 0))?;
 
-        File::create_new(BufReadWriteFile::new(file).unwrap(), table_info)
+        let file = BufReadWriteFile::new(file).map_err(|error| Error::io_error(error, 0))?;
+        File::create_new(file, table_info)
     }
 }
 
@@ -0,0 +1,249 @@
+//! Module with the definition of the [RecordFilter], predicates over the values
+//! of the fields of a [Record] expressed as data instead of code.
+use std::cmp::Ordering;
+
+use crate::field::types::currency_from_scaled;
+use crate::{Date, DateTime, FieldValue, Record};
+
+/// Predicate over the values of the fields of a [Record]
+///
+/// Fields are referred to by name. A filter on a field that the record does not
+/// have never matches (except when negated with [RecordFilter::Not]).
+///
+/// Values are compared with the following rules:
+///
+/// - numbers (`Numeric`, `Float`, `Integer`, `Double` and `Currency`) are compared
+///   to each other as `f64`
+/// - `Character` and `Memo` values are compared to each other as strings
+/// - `Date`, `DateTime` and `Logical` are compared to values of the same type
+/// - empty values (`None`) are only equal to the same empty value,
+///   and are neither less nor greater than anything
+///
+/// # Example
+///
+/// ```
+/// use dbase::{FieldValue, RecordFilter};
+///
+/// # fn main() -> Result<(), dbase::Error> {
+/// let filter = RecordFilter::And(vec![
+///     RecordFilter::Eq(
+///         "line".to_string(),
+///         FieldValue::Character(Some("blue".to_string())),
+///     ),
+///     RecordFilter::Not(Box::new(RecordFilter::Contains(
+///         "name".to_string(),
+///         "Street".to_string(),
+///     ))),
+/// ]);
+///
+/// let mut reader = dbase::Reader::from_path("tests/data/stations.dbf")?;
+/// for record in reader.filter_with(filter) {
+///     let record = record?;
+///     assert_eq!(
+///         record.get("line"),
+///         Some(&FieldValue::Character(Some("blue".to_string())))
+///     );
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum RecordFilter {
+    /// The value of the field is equal to the value
+    Eq(String, FieldValue),
+    /// The value of the field is less than the value
+    Lt(String, FieldValue),
+    /// The value of the field is greater than the value
+    Gt(String, FieldValue),
+    /// The `Character` or `Memo` value of the field contains the string
+    Contains(String, String),
+    /// The value of the field is empty (`None`)
+    IsNull(String),
+    /// All the filters match, true if there are no filters
+    And(Vec<RecordFilter>),
+    /// At least one of the filters matches, false if there are no filters
+    Or(Vec<RecordFilter>),
+    /// The filter does not match
+    Not(Box<RecordFilter>),
+}
+
+impl RecordFilter {
+    /// Returns true if the record matches the filter
+    pub fn matches(&self, record: &Record) -> bool {
+        match self {
+            RecordFilter::Eq(name, expected) => record.get(name).is_some_and(|value| {
+                value == expected || compare(value, expected) == Some(Ordering::Equal)
+            }),
+            RecordFilter::Lt(name, expected) => record
+                .get(name)
+                .is_some_and(|value| compare(value, expected) == Some(Ordering::Less)),
+            RecordFilter::Gt(name, expected) => record
+                .get(name)
+                .is_some_and(|value| compare(value, expected) == Some(Ordering::Greater)),
+            RecordFilter::Contains(name, pattern) => match record.get(name) {
+                Some(FieldValue::Character(Some(text))) | Some(FieldValue::Memo(text)) => {
+                    text.contains(pattern.as_str())
+                }
+                _ => false,
+            },
+            RecordFilter::IsNull(name) => record.get(name).is_some_and(is_null),
+            RecordFilter::And(filters) => filters.iter().all(|filter| filter.matches(record)),
+            RecordFilter::Or(filters) => filters.iter().any(|filter| filter.matches(record)),
+            RecordFilter::Not(filter) => !filter.matches(record),
+        }
+    }
+}
+
+fn is_null(value: &FieldValue) -> bool {
+    matches!(
+        value,
+        FieldValue::Character(None)
+            | FieldValue::BinCharacter(None)
+            | FieldValue::Numeric(None)
+            | FieldValue::Float(None)
+            | FieldValue::Logical(None)
+            | FieldValue::Date(None)
+    )
+}
+
+fn as_number(value: &FieldValue) -> Option<f64> {
+    match value {
+        FieldValue::Numeric(Some(value)) => Some(*value),
+        FieldValue::Float(Some(value)) => Some(f64::from(*value)),
+        FieldValue::Integer(value) => Some(f64::from(*value)),
+        FieldValue::Double(value) => Some(*value),
+        FieldValue::Currency(value) => Some(currency_from_scaled(*value)),
+        _ => None,
+    }
+}
+
+fn as_text(value: &FieldValue) -> Option<&str> {
+    match value {
+        FieldValue::Character(Some(text)) | FieldValue::Memo(text) => Some(text),
+        _ => None,
+    }
+}
+
+/// Compares two non empty values of compatible types
+fn compare(lhs: &FieldValue, rhs: &FieldValue) -> Option<Ordering> {
+    if let (Some(lhs), Some(rhs)) = (as_number(lhs), as_number(rhs)) {
+        return lhs.partial_cmp(&rhs);
+    }
+    if let (Some(lhs), Some(rhs)) = (as_text(lhs), as_text(rhs)) {
+        return Some(lhs.cmp(rhs));
+    }
+    match (lhs, rhs) {
+        (FieldValue::Date(Some(lhs)), FieldValue::Date(Some(rhs))) => {
+            Some(date_key(lhs).cmp(&date_key(rhs)))
+        }
+        (FieldValue::DateTime(lhs), FieldValue::DateTime(rhs)) => {
+            Some(date_time_key(lhs).cmp(&date_time_key(rhs)))
+        }
+        (FieldValue::Logical(Some(lhs)), FieldValue::Logical(Some(rhs))) => Some(lhs.cmp(rhs)),
+        _ => None,
+    }
+}
+
+fn date_key(date: &Date) -> (u32, u32, u32) {
+    (date.year(), date.month(), date.day())
+}
+
+fn date_time_key(date_time: &DateTime) -> ((u32, u32, u32), u32, u32, u32) {
+    let time = date_time.time();
+    (
+        date_key(&date_time.date()),
+        time.hours(),
+        time.minutes(),
+        time.seconds(),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Time;
+
+    fn record() -> Record {
+        let mut record = Record::default();
+        record.insert(
+            "name".to_string(),
+            FieldValue::Character(Some("Van Dorn Street".to_string())),
+        );
+        record.insert("amount".to_string(), FieldValue::Numeric(Some(12.5)));
+        record.insert("count".to_string(), FieldValue::Integer(3));
+        record.insert("note".to_string(), FieldValue::Character(None));
+        record.insert(
+            "date".to_string(),
+            FieldValue::Date(Some(Date::new(15, 6, 2023))),
+        );
+        record.insert(
+            "timestamp".to_string(),
+            FieldValue::DateTime(DateTime::new(Date::new(15, 6, 2023), Time::new(12, 0, 0))),
+        );
+        record
+    }
+
+    fn name(field: &str) -> String {
+        field.to_string()
+    }
+
+    #[test]
+    fn comparisons() {
+        let record = record();
+        assert!(RecordFilter::Eq(name("amount"), FieldValue::Numeric(Some(12.5))).matches(&record));
+        // Numbers of different types are compared as f64
+        assert!(RecordFilter::Eq(name("count"), FieldValue::Numeric(Some(3.0))).matches(&record));
+        assert!(RecordFilter::Lt(name("amount"), FieldValue::Integer(13)).matches(&record));
+        assert!(!RecordFilter::Gt(name("amount"), FieldValue::Integer(13)).matches(&record));
+        assert!(
+            RecordFilter::Gt(name("name"), FieldValue::Character(Some("Van".to_string())))
+                .matches(&record)
+        );
+        assert!(
+            RecordFilter::Lt(name("date"), FieldValue::Date(Some(Date::new(1, 1, 2024))))
+                .matches(&record)
+        );
+        assert!(RecordFilter::Gt(
+            name("timestamp"),
+            FieldValue::DateTime(DateTime::new(Date::new(15, 6, 2023), Time::new(11, 59, 59)))
+        )
+        .matches(&record));
+
+        // Values of incompatible types are never equal, less or greater
+        let text = FieldValue::Character(Some("12.5".to_string()));
+        assert!(!RecordFilter::Eq(name("amount"), text.clone()).matches(&record));
+        assert!(!RecordFilter::Lt(name("amount"), text.clone()).matches(&record));
+        assert!(!RecordFilter::Gt(name("amount"), text).matches(&record));
+        // Neither are missing fields
+        assert!(!RecordFilter::Eq(name("missing"), FieldValue::Integer(3)).matches(&record));
+    }
+
+    #[test]
+    fn nulls_and_substrings() {
+        let record = record();
+        assert!(RecordFilter::IsNull(name("note")).matches(&record));
+        assert!(!RecordFilter::IsNull(name("name")).matches(&record));
+        assert!(!RecordFilter::IsNull(name("missing")).matches(&record));
+        assert!(RecordFilter::Eq(name("note"), FieldValue::Character(None)).matches(&record));
+        assert!(!RecordFilter::Lt(name("note"), FieldValue::Character(None)).matches(&record));
+
+        assert!(RecordFilter::Contains(name("name"), "Dorn".to_string()).matches(&record));
+        assert!(!RecordFilter::Contains(name("name"), "dorn".to_string()).matches(&record));
+        assert!(!RecordFilter::Contains(name("note"), String::new()).matches(&record));
+        assert!(!RecordFilter::Contains(name("amount"), "12".to_string()).matches(&record));
+    }
+
+    #[test]
+    fn combinations() {
+        let record = record();
+        let is_null = RecordFilter::IsNull(name("note"));
+        let is_not_null = RecordFilter::Not(Box::new(is_null.clone()));
+
+        assert!(RecordFilter::And(vec![]).matches(&record));
+        assert!(!RecordFilter::Or(vec![]).matches(&record));
+        assert!(RecordFilter::And(vec![is_null.clone(), is_null.clone()]).matches(&record));
+        assert!(!RecordFilter::And(vec![is_null.clone(), is_not_null.clone()]).matches(&record));
+        assert!(RecordFilter::Or(vec![is_not_null.clone(), is_null]).matches(&record));
+        assert!(!RecordFilter::Or(vec![is_not_null.clone(), is_not_null]).matches(&record));
+    }
+}
@@ -1,10 +1,19 @@
-use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+#[cfg(feature = "std")]
+use byteorder::WriteBytesExt;
+use byteorder::{ByteOrder, LittleEndian};
 
 use crate::encoding::DynEncoding;
+#[cfg(feature = "std")]
 use std::io::{Read, Write};
 
-use crate::field::types::Date;
+use crate::field::types::{Date, FieldType};
+use crate::field::FieldInfo;
 use crate::memo::MemoFileType;
+use crate::{Error, ErrorKind};
+
+/// Size of the backlink to the database container
+/// that Visual FoxPro writes after the field descriptors
+pub(crate) const BACKLINK_SIZE: u16 = 263;
 
 // Used this as source: https://blog.codetitans.pl/post/dbf-and-language-code-page/
 // also https://github.com/ethanfurman/dbf/blob/4f8ff35bec18ca167981ba741bfe353f5f362f99/dbf/__init__.py#L8299
@@ -184,6 +193,7 @@ pub enum Version {
     VisualFoxPro,
     DBase4 { supports_memo: bool },
     FoxPro2 { supports_memo: bool },
+    DBase7 { supports_memo: bool },
     Unknown(u8),
 }
 
@@ -210,6 +220,9 @@ impl Version {
             Version::FoxPro2 {
                 supports_memo: true,
             } => Some(MemoFileType::FoxBaseMemo),
+            Version::DBase7 {
+                supports_memo: true,
+            } => Some(MemoFileType::DbaseMemo4),
             _ => None,
         }
     }
@@ -217,6 +230,140 @@ impl Version {
     pub(crate) fn is_visual_fox_pro(self) -> bool {
         matches!(self, Version::VisualFoxPro)
     }
+
+    /// dBase Level 7 files have a bigger header and bigger field descriptors
+    pub(crate) fn is_dbase7(self) -> bool {
+        matches!(self, Version::DBase7 { .. })
+    }
+
+    /// Returns the major version of the product that introduced this file format
+    ///
+    /// e.g. 2 for FoxBase & FoxPro 2.x, 3 for dBase III, 6 for Visual FoxPro.
+    ///
+    /// For unknown versions, the lowest 3 bits of the version byte are returned.
+    pub fn major(self) -> u8 {
+        match self {
+            Version::FoxBase => 2,
+            Version::DBase3 { .. } => 3,
+            Version::DBase4 { .. } => 4,
+            Version::FoxPro2 { .. } => 2,
+            Version::VisualFoxPro => 6,
+            Version::DBase7 { .. } => 7,
+            Version::Unknown(b) => b & 0x07,
+        }
+    }
+
+    /// Returns whether files of this version can be read and written by this crate
+    ///
+    /// Only [Version::Unknown] versions are not supported.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// assert!(dbase::Version::version_or_unknown(0x03).is_supported());
+    /// assert!(!dbase::Version::version_or_unknown(0x07).is_supported());
+    /// ```
+    pub fn is_supported(self) -> bool {
+        !matches!(self, Version::Unknown(_))
+    }
+
+    /// Returns whether files of this version can have memo fields
+    pub fn supports_memo(self) -> bool {
+        self.supported_memo_type().is_some()
+    }
+
+    /// Returns whether this version allows field names longer than 10 characters
+    pub fn supports_long_field_names(self) -> bool {
+        matches!(self, Version::VisualFoxPro | Version::DBase7 { .. })
+    }
+
+    /// Returns whether this version knows about the [Integer](crate::FieldType::Integer)
+    /// field type (and the other FoxPro binary types)
+    pub fn supports_integer_field(self) -> bool {
+        matches!(self, Version::VisualFoxPro | Version::FoxPro2 { .. })
+    }
+
+    /// Returns whether fields of the given type can be stored in files of this version
+    ///
+    /// - Character, Numeric, Float, Logical and Date fields are supported by all versions
+    /// - Memo fields need a version that [supports memo](Self::supports_memo)
+    /// - Integer fields are supported by FoxPro 2, Visual FoxPro and dBase Level 7
+    /// - Double, DateTime and Currency fields are supported by FoxPro 2 and Visual FoxPro,
+    ///   as well as Picture fields which also need memo support
+    /// - AutoIncrement, Timestamp and DBase7Double fields are only supported by dBase Level 7
+    ///
+    /// All the field types are accepted for unknown versions.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dbase::{FieldType, Version};
+    ///
+    /// let dbase3 = Version::DBase3 { supports_memo: false };
+    /// assert!(dbase3.supports_field_type(FieldType::Numeric));
+    /// assert!(!dbase3.supports_field_type(FieldType::Currency));
+    /// assert!(Version::VisualFoxPro.supports_field_type(FieldType::Currency));
+    /// ```
+    pub fn supports_field_type(self, field_type: FieldType) -> bool {
+        if !self.is_supported() {
+            return true;
+        }
+        match field_type {
+            FieldType::Character
+            | FieldType::Numeric
+            | FieldType::Float
+            | FieldType::Logical
+            | FieldType::Date => true,
+            FieldType::Memo => self.supports_memo(),
+            FieldType::Integer => self.supports_integer_field() || self.is_dbase7(),
+            FieldType::Double | FieldType::DateTime | FieldType::Currency => {
+                self.supports_integer_field()
+            }
+            FieldType::Picture => self.supports_integer_field() && self.supports_memo(),
+            FieldType::AutoIncrement | FieldType::Timestamp | FieldType::DBase7Double => {
+                self.is_dbase7()
+            }
+        }
+    }
+}
+
+/// Family of programs writing a [Version], which decides how some values
+/// are stored in the records
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum Dialect {
+    /// dBase, FoxBase and FoxPro 2.x
+    ///
+    /// Pointers to memo blocks are numbers written with 10 ASCII digits.
+    DBase,
+    /// Visual FoxPro
+    ///
+    /// Pointers to memo blocks are 4 bytes little endian integers.
+    VisualFoxPro,
+}
+
+impl Dialect {
+    /// Returns true if the pointers to memo blocks of a field
+    /// of `field_length` bytes are binary instead of ASCII digits
+    ///
+    /// The length of the field wins when it cannot hold the pointers
+    /// of the dialect, as some programs write 4 bytes binary pointers
+    /// in dBase files.
+    pub(crate) fn has_binary_memo_pointers(self, field_length: u8) -> bool {
+        match self {
+            Dialect::DBase => field_length == 4,
+            Dialect::VisualFoxPro => field_length >= 4,
+        }
+    }
+}
+
+impl From<Version> for Dialect {
+    fn from(version: Version) -> Self {
+        if version.is_visual_fox_pro() {
+            Dialect::VisualFoxPro
+        } else {
+            Dialect::DBase
+        }
+    }
 }
 
 impl From<Version> for u8 {
@@ -242,13 +389,31 @@ impl From<Version> for u8 {
             Version::FoxPro2 {
                 supports_memo: true,
             } => 0xf5,
+            Version::DBase7 {
+                supports_memo: false,
+            } => 0x04,
+            Version::DBase7 {
+                supports_memo: true,
+            } => 0x8c,
             Version::Unknown(v) => v,
         }
     }
 }
 
-impl From<u8> for Version {
-    fn from(b: u8) -> Self {
+impl Version {
+    /// Returns the version the byte stands for, [Version::Unknown] if the byte is not known
+    ///
+    /// Use [Version::try_from] to reject unknown versions.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dbase::Version;
+    ///
+    /// assert!(matches!(Version::version_or_unknown(0x30), Version::VisualFoxPro));
+    /// assert!(matches!(Version::version_or_unknown(0x07), Version::Unknown(0x07)));
+    /// ```
+    pub fn version_or_unknown(b: u8) -> Self {
         match b {
             0x02 => Version::FoxBase,
             0x03 => Version::DBase3 {
@@ -273,25 +438,106 @@ impl From<u8> for Version {
             0xf5 => Version::FoxPro2 {
                 supports_memo: true,
             },
+            0x04 => Version::DBase7 {
+                supports_memo: false,
+            },
+            0x8c => Version::DBase7 {
+                supports_memo: true,
+            },
             b => Version::Unknown(b),
         }
     }
 }
 
+impl TryFrom<u8> for Version {
+    type Error = Error;
+
+    /// Fails with [ErrorKind::UnsupportedVersion] if the byte is not a known version
+    fn try_from(b: u8) -> Result<Self, Self::Error> {
+        match Self::version_or_unknown(b) {
+            Version::Unknown(b) => Err(Error {
+                record_num: 0,
+                field: None,
+                kind: ErrorKind::UnsupportedVersion(b),
+            }),
+            version => Ok(version),
+        }
+    }
+}
+
+/// Table flags stored in the header (byte 28).
+///
+/// As documented for Visual FoxPro:
+///
+/// - 0x01: the file has a structural .cdx
+/// - 0x02: the file has a Memo field
+/// - 0x04: the file is a database (.dbc)
 #[derive(Debug, Copy, Clone)]
 pub struct TableFlags(u8);
 
 impl TableFlags {
+    const STRUCTURAL_CDX: u8 = 0x01;
+    const MEMO_FIELD: u8 = 0x02;
+    const DATABASE: u8 = 0x04;
+
+    /// Creates the flags from what they indicate
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let flags = dbase::TableFlags::new(false, true, false);
+    /// assert!(flags.has_memo_field());
+    /// assert_eq!(flags.to_byte(), 0x02);
+    /// ```
+    pub fn new(has_structural_cdx: bool, has_memo_field: bool, is_a_database: bool) -> Self {
+        let mut flags = 0u8;
+        if has_structural_cdx {
+            flags |= Self::STRUCTURAL_CDX;
+        }
+        if has_memo_field {
+            flags |= Self::MEMO_FIELD;
+        }
+        if is_a_database {
+            flags |= Self::DATABASE;
+        }
+        Self(flags)
+    }
+
+    /// Creates the flags from the byte stored in the header,
+    /// bits that have no known meaning are kept
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let flags = dbase::TableFlags::from_raw(0x83);
+    /// assert!(flags.has_structural_cdx());
+    /// assert!(flags.has_memo_field());
+    /// assert_eq!(flags.raw(), 0x83);
+    /// ```
+    pub fn from_raw(byte: u8) -> Self {
+        Self(byte)
+    }
+
+    /// Returns the byte stored in the header, including the bits that have no known meaning
+    pub fn raw(&self) -> u8 {
+        self.0
+    }
+
+    /// Returns the flags as stored in the header
+    pub fn to_byte(self) -> u8 {
+        self.0
+    }
+
     pub fn has_structural_cdx(&self) -> bool {
-        (self.0 & 0x01) == 1
+        (self.0 & Self::STRUCTURAL_CDX) != 0
     }
 
     pub fn has_memo_field(&self) -> bool {
-        (self.0 & 0x02) == 2
+        (self.0 & Self::MEMO_FIELD) != 0
     }
 
     pub fn is_a_database(&self) -> bool {
-        (self.0 & 0x03) == 1
+        (self.0 & Self::DATABASE) != 0
     }
 }
 
@@ -312,7 +558,11 @@ pub struct Header {
 
 impl Header {
     pub(crate) const SIZE: usize = 32;
+    /// Size of what dBase Level 7 adds after the header:
+    /// the language driver name (32 bytes) and 4 reserved bytes
+    pub(crate) const LEVEL7_EXTENSION_SIZE: usize = 36;
 
+    #[cfg(feature = "std")]
     pub(crate) fn new(num_records: u32, offset: u16, size_of_records: u16) -> Self {
         let current_date = Self::get_today_date();
         Self {
@@ -325,11 +575,12 @@ impl Header {
             size_of_record: size_of_records,
             is_transaction_incomplete: false,
             encryption_flag: 0,
-            table_flags: TableFlags(0),
+            table_flags: TableFlags::new(false, false, false),
             code_page_mark: CodePageMark::Undefined,
         }
     }
 
+    #[cfg(feature = "std")]
     fn get_today_date() -> Date {
         let current_date = time::OffsetDateTime::now_utc().date();
         // The year will be saved a a u8 offset from 1900
@@ -340,59 +591,100 @@ impl Header {
         }
     }
 
+    #[cfg(feature = "std")]
     pub(crate) fn update_date(&mut self) {
         self.last_update = Self::get_today_date();
     }
 
-    pub(crate) fn read_from<T: Read>(source: &mut T) -> Result<Self, std::io::Error> {
-        let file_type = Version::from(source.read_u8()?);
-
-        let mut date_bytes = [0u8; 3];
-        source.read_exact(&mut date_bytes)?;
-        let last_update = Date {
-            year: 1900u32 + date_bytes[0] as u32,
-            month: date_bytes[1] as u32,
-            day: date_bytes[2] as u32,
-        };
-
-        let num_records = source.read_u32::<LittleEndian>()?;
-        let offset_to_first_record = source.read_u16::<LittleEndian>()?;
-        let size_of_record = source.read_u16::<LittleEndian>()?;
-
-        let _reserved = source.read_u16::<LittleEndian>()?;
-
-        let is_transaction_incomplete = (source.read_u8()? != 0) as bool;
-        let encryption_flag = source.read_u8()?;
-
-        let mut _reserved = [0u8; 12];
-        source.read_exact(&mut _reserved)?;
-
-        let table_flags = TableFlags(source.read_u8()?);
-
-        let code_page_mark = source.read_u8().map(From::from)?;
-
-        let _reserved = source.read_u8()?;
-        let _reserved = source.read_u8()?;
-
+    /// Parses the header from the first bytes of a dBase file
+    ///
+    /// Unlike [Self::read_from], this does not need a stream,
+    /// which is handy to peek at a file's version or number of records.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let bytes = std::fs::read("tests/data/line.dbf")?;
+    /// let mut header_bytes = [0u8; 32];
+    /// header_bytes.copy_from_slice(&bytes[..32]);
+    ///
+    /// let header = dbase::Header::parse(&header_bytes)?;
+    /// assert_eq!(header.num_records, 1);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn parse(bytes: &[u8; Header::SIZE]) -> Result<Self, Error> {
+        let file_type = Version::try_from(bytes[0])?;
         Ok(Self {
             file_type,
-            last_update,
-            num_records,
-            offset_to_first_record,
-            is_transaction_incomplete,
-            encryption_flag,
-            size_of_record,
-            table_flags,
-            code_page_mark,
+            last_update: Date::from_header_format([bytes[1], bytes[2], bytes[3]]),
+            num_records: LittleEndian::read_u32(&bytes[4..8]),
+            offset_to_first_record: LittleEndian::read_u16(&bytes[8..10]),
+            size_of_record: LittleEndian::read_u16(&bytes[10..12]),
+            // 2 reserved bytes
+            is_transaction_incomplete: bytes[14] != 0,
+            encryption_flag: bytes[15],
+            // 12 reserved bytes
+            table_flags: TableFlags(bytes[28]),
+            code_page_mark: CodePageMark::from(bytes[29]),
+            // 2 reserved bytes
         })
     }
 
+    /// Returns the number of bytes before the first field descriptor
+    pub(crate) fn size_before_descriptors(&self) -> usize {
+        if self.file_type.is_dbase7() {
+            Self::SIZE + Self::LEVEL7_EXTENSION_SIZE
+        } else {
+            Self::SIZE
+        }
+    }
+
+    /// Returns how many field descriptors fit between the header
+    /// and the first record, followed by their terminator if `has_terminator`
+    pub(crate) fn max_num_fields(&self, has_terminator: bool) -> Result<usize, ErrorKind> {
+        let offset = if self.file_type.is_visual_fox_pro() {
+            self.offset_to_first_record
+                .checked_sub(BACKLINK_SIZE)
+                .ok_or(ErrorKind::MalformedDescriptors)?
+        } else {
+            self.offset_to_first_record
+        };
+        let max_size = (offset as usize)
+            .checked_sub(self.size_before_descriptors() + usize::from(has_terminator))
+            .ok_or(ErrorKind::MalformedDescriptors)?;
+        Ok(max_size / FieldInfo::size_for(self.file_type))
+    }
+
+    /// Reads the header from the source
+    ///
+    /// Exactly 32 bytes are read, so if `source` is at the beginning of a file,
+    /// it is left at the start of the first field descriptor.
+    ///
+    /// dBase Level 7 files have 36 more bytes (language driver name and reserved bytes)
+    /// after these, which are not read.
+    ///
+    /// Fails with [ErrorKind::UnsupportedVersion] if the version byte is not known.
+    #[cfg(feature = "std")]
+    pub fn read_from<T: Read>(source: &mut T) -> Result<Self, Error> {
+        let mut bytes = [0u8; Header::SIZE];
+        source
+            .read_exact(&mut bytes[..1])
+            .map_err(|error| Error::io_error(error, 0))?;
+        // Unknown versions are rejected before reading the rest
+        Version::try_from(bytes[0])?;
+        source
+            .read_exact(&mut bytes[1..])
+            .map_err(|error| Error::io_error(error, 0))?;
+        Self::parse(&bytes)
+    }
+
+    #[cfg(feature = "std")]
     pub(crate) fn write_to<T: Write>(&self, dest: &mut T) -> std::io::Result<()> {
         dest.write_u8(u8::from(self.file_type))?;
 
-        dest.write_u8((self.last_update.year() - 1900) as u8)?;
-        dest.write_u8(self.last_update.month() as u8)?;
-        dest.write_u8(self.last_update.day() as u8)?;
+        self.last_update.write_header_format(dest)?;
 
         dest.write_u32::<LittleEndian>(self.num_records)?;
         dest.write_u16::<LittleEndian>(self.offset_to_first_record)?;
@@ -406,7 +698,7 @@ impl Header {
         let _reserved = [0u8; 12];
         dest.write_all(&_reserved)?;
 
-        dest.write_u8(self.table_flags.0)?;
+        dest.write_u8(self.table_flags.to_byte())?;
         dest.write_u8(self.code_page_mark.into())?;
         // Reserved
         dest.write_u8(0)?;
@@ -469,4 +761,144 @@ mod test {
 
         assert_eq!(hdr_bytes_written, hdr_bytes);
     }
+
+    #[test]
+    fn table_flags() {
+        let flags = TableFlags(0x00);
+        assert!(!flags.has_structural_cdx());
+        assert!(!flags.has_memo_field());
+        assert!(!flags.is_a_database());
+
+        let flags = TableFlags(0x01);
+        assert!(flags.has_structural_cdx());
+        assert!(!flags.has_memo_field());
+        assert!(!flags.is_a_database());
+
+        let flags = TableFlags(0x02);
+        assert!(!flags.has_structural_cdx());
+        assert!(flags.has_memo_field());
+        assert!(!flags.is_a_database());
+
+        let flags = TableFlags(0x03);
+        assert!(flags.has_structural_cdx());
+        assert!(flags.has_memo_field());
+        assert!(!flags.is_a_database());
+
+        let flags = TableFlags(0x04);
+        assert!(!flags.has_structural_cdx());
+        assert!(!flags.has_memo_field());
+        assert!(flags.is_a_database());
+
+        let flags = TableFlags(0x07);
+        assert!(flags.has_structural_cdx());
+        assert!(flags.has_memo_field());
+        assert!(flags.is_a_database());
+
+        for byte in 0..8u8 {
+            let flags = TableFlags(byte);
+            let rebuilt = TableFlags::new(
+                flags.has_structural_cdx(),
+                flags.has_memo_field(),
+                flags.is_a_database(),
+            );
+            assert_eq!(rebuilt.to_byte(), byte);
+        }
+
+        // Unknown bits are kept when the header is read and written back
+        let mut hdr_bytes = std::fs::read("tests/data/line.dbf").unwrap();
+        hdr_bytes.truncate(Header::SIZE);
+        hdr_bytes[28] = 0xF1;
+        let hdr = Header::read_from(&mut Cursor::new(hdr_bytes.clone())).unwrap();
+        assert_eq!(hdr.table_flags.raw(), 0xF1);
+        assert!(hdr.table_flags.has_structural_cdx());
+        assert!(!hdr.table_flags.has_memo_field());
+
+        let mut cursor = Cursor::new(Vec::<u8>::with_capacity(Header::SIZE));
+        hdr.write_to(&mut cursor).unwrap();
+        assert_eq!(cursor.into_inner(), hdr_bytes);
+        assert_eq!(TableFlags::from_raw(0xF1).raw(), 0xF1);
+    }
+
+    #[test]
+    fn version_predicates() {
+        let dbase3 = Version::version_or_unknown(0x03);
+        assert_eq!(dbase3.major(), 3);
+        assert!(!dbase3.supports_memo());
+        assert!(!dbase3.supports_long_field_names());
+        assert!(!dbase3.supports_integer_field());
+
+        let dbase3_memo = Version::version_or_unknown(0x83);
+        assert_eq!(dbase3_memo.major(), 3);
+        assert!(dbase3_memo.supports_memo());
+
+        let fox_base = Version::version_or_unknown(0x02);
+        assert_eq!(fox_base.major(), 2);
+        assert!(fox_base.supports_memo());
+
+        let vfp = Version::version_or_unknown(0x30);
+        assert_eq!(vfp.major(), 6);
+        assert!(vfp.supports_memo());
+        assert!(vfp.supports_long_field_names());
+        assert!(vfp.supports_integer_field());
+
+        let fox_pro = Version::version_or_unknown(0xfb);
+        assert_eq!(fox_pro.major(), 2);
+        assert!(!fox_pro.supports_memo());
+        assert!(fox_pro.supports_integer_field());
+
+        for byte in [0x02, 0x03, 0x83, 0x30, 0x8b, 0x43, 0xfb, 0xf5, 0x04, 0x8c] {
+            assert!(Version::version_or_unknown(byte).is_supported());
+        }
+        let unknown = Version::version_or_unknown(0x07);
+        assert!(!unknown.is_supported());
+        assert!(!unknown.supports_memo());
+
+        assert!(matches!(Version::try_from(0x30), Ok(Version::VisualFoxPro)));
+        let error = Version::try_from(0x07).unwrap_err();
+        assert!(matches!(error.kind(), ErrorKind::UnsupportedVersion(0x07)));
+
+        let mut bytes = [0u8; Header::SIZE];
+        bytes[0] = 0x07;
+        let error = Header::parse(&bytes).unwrap_err();
+        assert!(matches!(error.kind(), ErrorKind::UnsupportedVersion(0x07)));
+    }
+
+    #[test]
+    fn fox_pro_2_versions() {
+        let with_memo = Version::version_or_unknown(0xf5);
+        assert!(matches!(
+            with_memo,
+            Version::FoxPro2 {
+                supports_memo: true
+            }
+        ));
+        assert!(with_memo.supports_memo());
+        assert!(matches!(
+            with_memo.supported_memo_type(),
+            Some(MemoFileType::FoxBaseMemo)
+        ));
+        assert_eq!(u8::from(with_memo), 0xf5);
+
+        let without_memo = Version::version_or_unknown(0xfb);
+        assert!(matches!(
+            without_memo,
+            Version::FoxPro2 {
+                supports_memo: false
+            }
+        ));
+        assert!(!without_memo.supports_memo());
+        assert_eq!(u8::from(without_memo), 0xfb);
+    }
+
+    #[test]
+    fn last_update_is_written_since_1900() {
+        let mut header = Header::new(0, 0, 0);
+        header.last_update = Date::new(15, 6, 2023);
+        let mut out = Vec::new();
+        header.write_to(&mut out).unwrap();
+        assert_eq!(&out[1..4], &[123, 6, 15]);
+
+        let read = Header::read_from(&mut out.as_slice()).unwrap();
+        assert_eq!(read.last_update, Date::new(15, 6, 2023));
+    }
 }
@@ -0,0 +1,486 @@
+//! Module with the definition of fn's and struct's to read .cdx index files
+//!
+//! A .cdx file (FoxPro compound index) contains one or more indexes, called tags,
+//! each one being a B-tree whose keys point to record numbers of the .dbf file.
+//!
+//! The file starts with a tag directory, which is itself an index whose keys are the tag names
+//! and whose record numbers are the offsets of the tag headers.
+use std::cmp::Ordering;
+use std::io::{Read, Seek, SeekFrom};
+
+use byteorder::{BigEndian, LittleEndian, ReadBytesExt};
+
+use super::{invalid_index, pad_key, unknown_tag, Index, TagIndex, KEY_FILL_BYTE};
+use crate::error::Error;
+
+/// Size of the header of an index (tag), including the expression pool
+const HEADER_SIZE: usize = 1024;
+/// Size of a node of the B-tree
+const NODE_SIZE: usize = 512;
+/// Size of the beginning of nodes, common to interior and leaf nodes
+const NODE_HEADER_SIZE: usize = 12;
+/// Size of the beginning of leaf nodes, that describes how the keys are compressed
+const LEAF_HEADER_SIZE: usize = 24;
+/// Value used as a 'null' node pointer
+const NO_NODE: u32 = u32::MAX;
+/// Maximum length of the keys
+const MAX_KEY_LENGTH: u16 = 240;
+
+const ROOT_NODE_ATTRIBUTE: u16 = 0x01;
+const LEAF_NODE_ATTRIBUTE: u16 = 0x02;
+
+const DESCENDING_ORDER: u16 = 1;
+
+/// Header of an index
+#[derive(Debug, Clone)]
+struct IndexHeader {
+    root_node: u32,
+    key_length: u16,
+    descending: bool,
+    key_expression: String,
+}
+
+impl IndexHeader {
+    fn read_from<T: Read>(source: &mut T) -> std::io::Result<Self> {
+        let mut bytes = [0u8; HEADER_SIZE];
+        source.read_exact(&mut bytes)?;
+
+        let mut header = &bytes[..];
+        let root_node = header.read_u32::<LittleEndian>()?;
+        let _free_list = header.read_u32::<LittleEndian>()?;
+        let _reserved = header.read_u32::<LittleEndian>()?;
+        let key_length = header.read_u16::<LittleEndian>()?;
+
+        let mut order = &bytes[502..504];
+        let descending = order.read_u16::<LittleEndian>()? == DESCENDING_ORDER;
+
+        // The key expression is the first null terminated string of the expression pool
+        let expression_pool = &bytes[512..];
+        let expression_len = expression_pool
+            .iter()
+            .position(|b| *b == 0)
+            .unwrap_or(expression_pool.len());
+        let key_expression = String::from_utf8_lossy(&expression_pool[..expression_len])
+            .trim()
+            .to_string();
+
+        Ok(Self {
+            root_node,
+            key_length,
+            descending,
+            key_expression,
+        })
+    }
+
+    fn validate(&self) -> Result<(), Error> {
+        if self.key_length == 0 || self.key_length > MAX_KEY_LENGTH {
+            return Err(invalid_index("invalid key length"));
+        }
+        Ok(())
+    }
+}
+
+/// A key of the index, and the record it points to
+struct KeyEntry {
+    key: Vec<u8>,
+    record: u32,
+    /// Only set for interior nodes
+    child: u32,
+}
+
+/// A node of the B-tree
+struct Node {
+    attributes: u16,
+    right_sibling: u32,
+    entries: Vec<KeyEntry>,
+}
+
+impl Node {
+    fn is_leaf(&self) -> bool {
+        self.attributes & LEAF_NODE_ATTRIBUTE != 0
+    }
+
+    fn read_from<T: Read>(source: &mut T, key_length: u16) -> Result<Self, Error> {
+        let mut bytes = [0u8; NODE_SIZE];
+        source
+            .read_exact(&mut bytes)
+            .map_err(|error| Error::io_error(error, 0))?;
+
+        let mut node_header = &bytes[..NODE_HEADER_SIZE];
+        // reading from a slice of the right size cannot fail
+        let attributes = node_header.read_u16::<LittleEndian>().unwrap();
+        let num_keys = node_header.read_u16::<LittleEndian>().unwrap();
+        let _left_sibling = node_header.read_u32::<LittleEndian>().unwrap();
+        let right_sibling = node_header.read_u32::<LittleEndian>().unwrap();
+
+        if attributes & !(ROOT_NODE_ATTRIBUTE | LEAF_NODE_ATTRIBUTE) != 0 {
+            return Err(invalid_index("invalid node attributes"));
+        }
+
+        let entries = if attributes & LEAF_NODE_ATTRIBUTE != 0 {
+            Self::read_leaf_entries(&bytes, num_keys, key_length)?
+        } else {
+            Self::read_interior_entries(&bytes, num_keys, key_length)?
+        };
+
+        Ok(Self {
+            attributes,
+            right_sibling,
+            entries,
+        })
+    }
+
+    /// In interior nodes, keys are stored uncompressed, followed by the
+    /// record number and the offset of the child node (both in big endian)
+    fn read_interior_entries(
+        bytes: &[u8; NODE_SIZE],
+        num_keys: u16,
+        key_length: u16,
+    ) -> Result<Vec<KeyEntry>, Error> {
+        let key_length = key_length as usize;
+        let entry_size = key_length + 8;
+        if NODE_HEADER_SIZE + num_keys as usize * entry_size > NODE_SIZE {
+            return Err(invalid_index("too many keys in interior node"));
+        }
+
+        let entries = bytes[NODE_HEADER_SIZE..]
+            .chunks_exact(entry_size)
+            .take(num_keys as usize)
+            .map(|entry| {
+                let mut pointers = &entry[key_length..];
+                KeyEntry {
+                    key: entry[..key_length].to_vec(),
+                    record: pointers.read_u32::<BigEndian>().unwrap(),
+                    child: pointers.read_u32::<BigEndian>().unwrap(),
+                }
+            })
+            .collect();
+        Ok(entries)
+    }
+
+    /// In leaf nodes, keys are compressed:
+    ///
+    /// - For each key, the record number, the number of bytes that are the same
+    ///   as the previous key (duplicates) and the number of trailing bytes
+    ///   are bit-packed in a few bytes, stored after the leaf header.
+    /// - The remaining bytes of the keys are stored from the end of the node.
+    fn read_leaf_entries(
+        bytes: &[u8; NODE_SIZE],
+        num_keys: u16,
+        key_length: u16,
+    ) -> Result<Vec<KeyEntry>, Error> {
+        let mut leaf_header = &bytes[NODE_HEADER_SIZE..LEAF_HEADER_SIZE];
+        let _free_space = leaf_header.read_u16::<LittleEndian>().unwrap();
+        let record_mask = leaf_header.read_u32::<LittleEndian>().unwrap();
+        let duplicate_mask = leaf_header.read_u8().unwrap();
+        let trailing_mask = leaf_header.read_u8().unwrap();
+        let record_bits = leaf_header.read_u8().unwrap();
+        let duplicate_bits = leaf_header.read_u8().unwrap();
+        let _trailing_bits = leaf_header.read_u8().unwrap();
+        let entry_size = leaf_header.read_u8().unwrap() as usize;
+
+        if entry_size == 0
+            || entry_size > std::mem::size_of::<u64>()
+            || record_bits as usize + duplicate_bits as usize >= entry_size * 8
+            || LEAF_HEADER_SIZE + num_keys as usize * entry_size > NODE_SIZE
+        {
+            return Err(invalid_index("invalid leaf node"));
+        }
+
+        let key_length = key_length as usize;
+        let mut entries = Vec::<KeyEntry>::with_capacity(num_keys as usize);
+        let mut key_data_end = NODE_SIZE;
+        for info in bytes[LEAF_HEADER_SIZE..]
+            .chunks_exact(entry_size)
+            .take(num_keys as usize)
+        {
+            let mut info_bytes = [0u8; std::mem::size_of::<u64>()];
+            info_bytes[..entry_size].copy_from_slice(info);
+            let info = u64::from_le_bytes(info_bytes);
+
+            let record = (info & u64::from(record_mask)) as u32;
+            let duplicates = ((info >> record_bits) & u64::from(duplicate_mask)) as usize;
+            let trailing =
+                ((info >> (record_bits + duplicate_bits)) & u64::from(trailing_mask)) as usize;
+
+            let previous_key = entries.last().map(|e| e.key.as_slice()).unwrap_or(&[]);
+            if duplicates + trailing > key_length || duplicates > previous_key.len() {
+                return Err(invalid_index("invalid leaf key"));
+            }
+            let num_new_bytes = key_length - duplicates - trailing;
+            if key_data_end < LEAF_HEADER_SIZE + num_new_bytes {
+                return Err(invalid_index("invalid leaf key"));
+            }
+            key_data_end -= num_new_bytes;
+
+            let mut key = Vec::with_capacity(key_length);
+            key.extend_from_slice(&previous_key[..duplicates]);
+            key.extend_from_slice(&bytes[key_data_end..key_data_end + num_new_bytes]);
+            key.resize(key_length, KEY_FILL_BYTE);
+
+            entries.push(KeyEntry {
+                key,
+                record,
+                child: NO_NODE,
+            });
+        }
+        Ok(entries)
+    }
+}
+
+/// Information about a tag (an index) of a .cdx file
+#[derive(Debug, Clone)]
+pub struct CdxTag {
+    name: String,
+    header: IndexHeader,
+}
+
+impl CdxTag {
+    /// Returns the name of the tag
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the expression used to compute the keys (e.g. the name of a field)
+    pub fn key_expression(&self) -> &str {
+        &self.header.key_expression
+    }
+
+    /// Returns the length of the keys
+    pub fn key_length(&self) -> u16 {
+        self.header.key_length
+    }
+
+    /// Returns true if the keys are sorted in descending order
+    pub fn is_descending(&self) -> bool {
+        self.header.descending
+    }
+}
+
+/// Reader for FoxPro compound index (.cdx) files
+///
+/// Only exact-match lookups of character keys are supported:
+/// keys are compared byte per byte, after being padded with spaces.
+///
+/// # Example
+///
+/// ```
+/// # fn main() -> Result<(), dbase::Error> {
+/// let mut index = dbase::CdxReader::from_path("tests/data/indexed.cdx")?;
+/// let records = index.find("CODE", b"C00007")?;
+/// assert_eq!(records, vec![1]);
+///
+/// let mut reader = dbase::Reader::from_path("tests/data/indexed.dbf")?;
+/// let record = &reader.read()?[records[0] as usize];
+/// assert_eq!(
+///     record.get("CODE"),
+///     Some(&dbase::FieldValue::Character(Some("C00007".to_string())))
+/// );
+/// # Ok(())
+/// # }
+/// ```
+pub struct CdxReader<T: Read + Seek> {
+    source: T,
+    tags: Vec<CdxTag>,
+    num_nodes: u64,
+}
+
+impl<T: Read + Seek> CdxReader<T> {
+    /// Creates a new CdxReader, reads the tag directory and the header of each tag
+    pub fn new(mut source: T) -> Result<Self, Error> {
+        let file_size = source
+            .seek(SeekFrom::End(0))
+            .map_err(|error| Error::io_error(error, 0))?;
+        source
+            .seek(SeekFrom::Start(0))
+            .map_err(|error| Error::io_error(error, 0))?;
+
+        let directory_header =
+            IndexHeader::read_from(&mut source).map_err(|error| Error::io_error(error, 0))?;
+        directory_header.validate()?;
+
+        let mut reader = Self {
+            source,
+            tags: vec![],
+            num_nodes: file_size / NODE_SIZE as u64,
+        };
+
+        let mut tags = vec![];
+        for entry in reader.leaf_entries(&directory_header)? {
+            let name = String::from_utf8_lossy(&entry.key)
+                .trim_end_matches([' ', '\0'])
+                .to_string();
+            reader
+                .source
+                .seek(SeekFrom::Start(u64::from(entry.record)))
+                .map_err(|error| Error::io_error(error, 0))?;
+            let header = IndexHeader::read_from(&mut reader.source)
+                .map_err(|error| Error::io_error(error, 0))?;
+            header.validate()?;
+            tags.push(CdxTag { name, header });
+        }
+        reader.tags = tags;
+        Ok(reader)
+    }
+
+    /// Returns the tags contained in the file
+    pub fn tags(&self) -> &[CdxTag] {
+        &self.tags
+    }
+
+    /// Returns the tag with the given name, the comparison ignores the case
+    pub fn tag(&self, name: &str) -> Option<&CdxTag> {
+        self.tags
+            .iter()
+            .find(|tag| tag.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Selects the tag named `name`, to use it as an [Index]
+    pub fn select_tag(&mut self, name: &str) -> Result<TagIndex<'_, Self>, Error> {
+        let name = self
+            .tag(name)
+            .ok_or_else(|| unknown_tag(name))?
+            .name
+            .clone();
+        Ok(TagIndex { reader: self, name })
+    }
+
+    /// Returns the indices of the records whose key in the tag `tag_name` is `key`
+    ///
+    /// The key is padded with spaces to the key length of the tag.
+    /// Indices start at 0 (like [Reader::seek](crate::Reader::seek)),
+    /// and are sorted in the order of the index.
+    pub fn find(&mut self, tag_name: &str, key: &[u8]) -> Result<Vec<u32>, Error> {
+        let tag = self
+            .tag(tag_name)
+            .cloned()
+            .ok_or_else(|| unknown_tag(tag_name))?;
+        let header = &tag.header;
+
+        if key.len() > header.key_length as usize {
+            return Ok(vec![]);
+        }
+        let searched_key = pad_key(key, header.key_length);
+
+        let compare = |key: &[u8]| {
+            let ordering = key.cmp(&searched_key);
+            if header.descending {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        };
+
+        let mut records = vec![];
+        let mut node_offset = header.root_node;
+        // Limit the number of visited nodes, to not loop forever on corrupted files
+        for _ in 0..self.num_nodes {
+            if node_offset == NO_NODE {
+                break;
+            }
+            let node = self.read_node_at(node_offset, header.key_length)?;
+            if node.is_leaf() {
+                for entry in &node.entries {
+                    match compare(&entry.key) {
+                        Ordering::Less => {}
+                        Ordering::Equal => records.push(
+                            // Record numbers start at 1
+                            entry
+                                .record
+                                .checked_sub(1)
+                                .ok_or_else(|| invalid_index("invalid record number"))?,
+                        ),
+                        Ordering::Greater => return Ok(records),
+                    }
+                }
+                // Equal keys may continue in the next leaf
+                node_offset = node.right_sibling;
+            } else {
+                // Keys of interior nodes are the greatest key of their child
+                match node
+                    .entries
+                    .iter()
+                    .find(|entry| compare(&entry.key) != Ordering::Less)
+                {
+                    Some(entry) => node_offset = entry.child,
+                    None => break,
+                }
+            }
+        }
+        Ok(records)
+    }
+
+    fn read_node_at(&mut self, offset: u32, key_length: u16) -> Result<Node, Error> {
+        self.source
+            .seek(SeekFrom::Start(u64::from(offset)))
+            .map_err(|error| Error::io_error(error, 0))?;
+        Node::read_from(&mut self.source, key_length)
+    }
+
+    /// Returns all the entries of the leaves of the index, in order
+    fn leaf_entries(&mut self, header: &IndexHeader) -> Result<Vec<KeyEntry>, Error> {
+        let mut node_offset = header.root_node;
+        let mut entries = vec![];
+        // Go down to the first leaf, then follow the siblings
+        for _ in 0..self.num_nodes {
+            if node_offset == NO_NODE {
+                break;
+            }
+            let mut node = self.read_node_at(node_offset, header.key_length)?;
+            if node.is_leaf() {
+                entries.append(&mut node.entries);
+                node_offset = node.right_sibling;
+            } else {
+                node_offset = node.entries.first().map_or(NO_NODE, |entry| entry.child);
+            }
+        }
+        Ok(entries)
+    }
+}
+
+impl<'a, T: Read + Seek> Index for TagIndex<'a, CdxReader<T>> {
+    fn find(&mut self, key: &[u8]) -> Result<Vec<u32>, Error> {
+        self.reader.find(&self.name, key)
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl CdxReader<std::io::BufReader<std::fs::File>> {
+    /// Creates a new CdxReader from a path
+    pub fn from_path<P: AsRef<std::path::Path>>(path: P) -> Result<Self, Error> {
+        let file = std::fs::File::open(path).map_err(|error| Error::io_error(error, 0))?;
+        Self::new(std::io::BufReader::new(file))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn read_tags() {
+        let index = CdxReader::from_path("tests/data/indexed.cdx").unwrap();
+        let names = index.tags().iter().map(CdxTag::name).collect::<Vec<_>>();
+        assert_eq!(names, vec!["CITY", "CODE"]);
+
+        let tag = index.tag("code").unwrap();
+        assert_eq!(tag.key_expression(), "CODE");
+        assert_eq!(tag.key_length(), 8);
+        assert!(!tag.is_descending());
+    }
+
+    #[test]
+    fn tag_spans_multiple_leaves() {
+        let mut index = CdxReader::from_path("tests/data/indexed.cdx").unwrap();
+        let header = index.tag("CODE").unwrap().header.clone();
+        let entries = index.leaf_entries(&header).unwrap();
+        assert_eq!(entries.len(), 150);
+        assert!(entries.windows(2).all(|w| w[0].key < w[1].key));
+
+        let root = index
+            .read_node_at(header.root_node, header.key_length)
+            .unwrap();
+        assert!(!root.is_leaf());
+        assert!(root.entries.len() > 1);
+    }
+}
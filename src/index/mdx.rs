@@ -0,0 +1,380 @@
+//! Module with the definition of fn's and struct's to read dBase IV .mdx index files
+//!
+//! A .mdx file contains multiple indexes called tags, each one being a B-tree
+//! over the keys computed from the records of a .dbf file.
+//!
+//! The file is made of pages of 512 bytes, grouped in blocks (usually of 2 pages).
+//! The file header is followed by the tag table, then each tag has a header block,
+//! pointing to the root block of its B-tree.
+use std::cmp::Ordering;
+use std::io::{Read, Seek, SeekFrom};
+
+use byteorder::{LittleEndian, ReadBytesExt};
+
+use super::{
+    invalid_index, pad_key, unknown_tag, BTree, BTreeCursor, Index, Page, PageEntry, TagIndex,
+};
+use crate::error::Error;
+
+/// Size of a page, positions in the file are given as page numbers
+const PAGE_SIZE: u64 = 512;
+/// Size of the file header, without the tag table
+const HEADER_SIZE: usize = 48;
+/// Offset of the tag table
+const TAG_TABLE_OFFSET: u64 = 544;
+/// Size of an entry of the tag table
+const TAG_TABLE_ENTRY_SIZE: usize = 32;
+/// Size of the part of the tag header that we read
+const TAG_HEADER_SIZE: usize = 124;
+/// Offset of the key expression in the tag header
+const KEY_EXPRESSION_OFFSET: usize = 24;
+/// Size of the fields that precede the entries in a block:
+/// the number of keys and a reserved field
+const BLOCK_HEADER_SIZE: usize = 8;
+/// Size of the field that precedes the key in an entry,
+/// the record number in leaves or the page number of the child otherwise
+const ENTRY_POINTER_SIZE: usize = 4;
+/// Bit of the key format, set if the keys are sorted in descending order
+const DESCENDING_KEY_FORMAT: u8 = 0x08;
+
+/// Information about a tag (an index) of a .mdx file
+#[derive(Debug, Clone)]
+pub struct MdxTag {
+    name: String,
+    root_page: u32,
+    key_type: char,
+    key_length: u16,
+    entry_size: u16,
+    is_descending: bool,
+    is_unique: bool,
+    key_expression: String,
+}
+
+impl MdxTag {
+    fn read_from<T: Read>(source: &mut T, name: String) -> Result<Self, Error> {
+        let mut bytes = [0u8; TAG_HEADER_SIZE];
+        source
+            .read_exact(&mut bytes)
+            .map_err(|error| Error::io_error(error, 0))?;
+
+        // reading from a slice of the right size cannot fail
+        let mut header = &bytes[..];
+        let root_page = header.read_u32::<LittleEndian>().unwrap();
+        let _num_pages = header.read_u32::<LittleEndian>().unwrap();
+        let key_format = header.read_u8().unwrap();
+        let key_type = header.read_u8().unwrap() as char;
+        let _reserved = header.read_u16::<LittleEndian>().unwrap();
+        let key_length = header.read_u16::<LittleEndian>().unwrap();
+        let _max_keys_per_block = header.read_u16::<LittleEndian>().unwrap();
+        let _reserved = header.read_u16::<LittleEndian>().unwrap();
+        let entry_size = header.read_u16::<LittleEndian>().unwrap();
+        let _version = header.read_u16::<LittleEndian>().unwrap();
+        let _reserved = header.read_u8().unwrap();
+        let is_unique = header.read_u8().unwrap() != 0;
+
+        let expression = &bytes[KEY_EXPRESSION_OFFSET..];
+        let expression_len = expression
+            .iter()
+            .position(|b| *b == 0)
+            .unwrap_or(expression.len());
+        let key_expression = String::from_utf8_lossy(&expression[..expression_len])
+            .trim()
+            .to_string();
+
+        if key_length == 0 || (entry_size as usize) < key_length as usize + ENTRY_POINTER_SIZE {
+            return Err(invalid_index("invalid tag header"));
+        }
+
+        Ok(Self {
+            name,
+            root_page,
+            key_type,
+            key_length,
+            entry_size,
+            is_descending: key_format & DESCENDING_KEY_FORMAT != 0,
+            is_unique,
+            key_expression,
+        })
+    }
+
+    /// Returns the name of the tag
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the type of the keys: 'C' (character), 'N' (numeric) or 'D' (date)
+    pub fn key_type(&self) -> char {
+        self.key_type
+    }
+
+    /// Returns the expression used to compute the keys (e.g. the name of a field)
+    pub fn key_expression(&self) -> &str {
+        &self.key_expression
+    }
+
+    /// Returns the length of the keys
+    pub fn key_length(&self) -> u16 {
+        self.key_length
+    }
+
+    /// Returns true if the keys are sorted in descending order
+    pub fn is_descending(&self) -> bool {
+        self.is_descending
+    }
+
+    /// Returns true if the tag does not allow duplicated keys
+    pub fn is_unique(&self) -> bool {
+        self.is_unique
+    }
+}
+
+/// Reader for dBase IV multiple index (.mdx) files
+///
+/// Only exact-match lookups are supported, and keys are compared byte per byte
+/// after being padded with spaces, which is only correct for character keys.
+///
+/// # Example
+///
+/// ```
+/// # fn main() -> Result<(), dbase::Error> {
+/// let mut index = dbase::MdxReader::from_path("tests/data/indexed.mdx")?;
+/// let records = index.find("CITY", b"Lyon")?;
+/// assert_eq!(records.len(), 22);
+///
+/// let mut reader = dbase::Reader::from_path("tests/data/indexed.dbf")?;
+/// let record = reader.read_record(records[0] as usize)?.unwrap();
+/// assert_eq!(
+///     record.get("CITY"),
+///     Some(&dbase::FieldValue::Character(Some("Lyon".to_string())))
+/// );
+/// # Ok(())
+/// # }
+/// ```
+pub struct MdxReader<T: Read + Seek> {
+    source: T,
+    block_size: usize,
+    num_pages: u32,
+    tags: Vec<MdxTag>,
+}
+
+impl<T: Read + Seek> MdxReader<T> {
+    /// Creates a new MdxReader, reads the file header and the header of each tag
+    pub fn new(mut source: T) -> Result<Self, Error> {
+        source
+            .seek(SeekFrom::Start(0))
+            .map_err(|error| Error::io_error(error, 0))?;
+        let mut bytes = [0u8; HEADER_SIZE];
+        source
+            .read_exact(&mut bytes)
+            .map_err(|error| Error::io_error(error, 0))?;
+
+        let mut block_size_bytes = &bytes[22..24];
+        let block_size = block_size_bytes.read_u16::<LittleEndian>().unwrap() as usize;
+        let tag_entry_size = bytes[26] as usize;
+        let mut header = &bytes[28..36];
+        let num_tags = header.read_u16::<LittleEndian>().unwrap();
+        let _reserved = header.read_u16::<LittleEndian>().unwrap();
+        let num_pages = header.read_u32::<LittleEndian>().unwrap();
+
+        if block_size < BLOCK_HEADER_SIZE || tag_entry_size < TAG_TABLE_ENTRY_SIZE {
+            return Err(invalid_index("invalid header"));
+        }
+
+        let mut tag_table = vec![0u8; num_tags as usize * tag_entry_size];
+        source
+            .seek(SeekFrom::Start(TAG_TABLE_OFFSET))
+            .map_err(|error| Error::io_error(error, 0))?;
+        source
+            .read_exact(&mut tag_table)
+            .map_err(|error| Error::io_error(error, 0))?;
+
+        let mut tags = Vec::with_capacity(num_tags as usize);
+        for mut entry in tag_table.chunks_exact(tag_entry_size) {
+            let header_page = entry.read_u32::<LittleEndian>().unwrap();
+            let name = &entry[..11];
+            let name_len = name.iter().position(|b| *b == 0).unwrap_or(name.len());
+            let name = String::from_utf8_lossy(&name[..name_len])
+                .trim()
+                .to_string();
+
+            source
+                .seek(SeekFrom::Start(u64::from(header_page) * PAGE_SIZE))
+                .map_err(|error| Error::io_error(error, 0))?;
+            tags.push(MdxTag::read_from(&mut source, name)?);
+        }
+
+        Ok(Self {
+            source,
+            block_size,
+            num_pages,
+            tags,
+        })
+    }
+
+    /// Returns the tags contained in the file
+    pub fn tags(&self) -> &[MdxTag] {
+        &self.tags
+    }
+
+    /// Returns the tag with the given name, the comparison ignores the case
+    pub fn tag(&self, name: &str) -> Option<&MdxTag> {
+        self.tags
+            .iter()
+            .find(|tag| tag.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Selects the tag named `name`, to use it as an [Index]
+    pub fn select_tag(&mut self, name: &str) -> Result<TagIndex<'_, Self>, Error> {
+        let name = self
+            .tag(name)
+            .ok_or_else(|| unknown_tag(name))?
+            .name
+            .clone();
+        Ok(TagIndex { reader: self, name })
+    }
+
+    /// Returns the indices of the records whose key in the tag `tag_name` is `key`
+    ///
+    /// The key is padded with spaces to the key length of the tag.
+    /// Indices start at 0 (like [Reader::seek](crate::Reader::seek)),
+    /// and are sorted in the order of the index.
+    pub fn find(&mut self, tag_name: &str, key: &[u8]) -> Result<Vec<u32>, Error> {
+        let tag = self
+            .tag(tag_name)
+            .cloned()
+            .ok_or_else(|| unknown_tag(tag_name))?;
+        if key.len() > tag.key_length as usize {
+            return Ok(vec![]);
+        }
+        let key = pad_key(key, tag.key_length);
+
+        let mut tree = MdxTree { reader: self, tag };
+        let mut cursor = BTreeCursor::new(key.clone(), key);
+        std::iter::from_fn(|| cursor.next_record(&mut tree)).collect()
+    }
+}
+
+impl<'a, T: Read + Seek> Index for TagIndex<'a, MdxReader<T>> {
+    fn find(&mut self, key: &[u8]) -> Result<Vec<u32>, Error> {
+        self.reader.find(&self.name, key)
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl MdxReader<std::io::BufReader<std::fs::File>> {
+    /// Creates a new MdxReader from a path
+    pub fn from_path<P: AsRef<std::path::Path>>(path: P) -> Result<Self, Error> {
+        let file = std::fs::File::open(path).map_err(|error| Error::io_error(error, 0))?;
+        Self::new(std::io::BufReader::new(file))
+    }
+}
+
+/// The B-tree of one tag
+struct MdxTree<'a, T: Read + Seek> {
+    reader: &'a mut MdxReader<T>,
+    tag: MdxTag,
+}
+
+impl<'a, T: Read + Seek> BTree for MdxTree<'a, T> {
+    fn root_page(&self) -> u32 {
+        self.tag.root_page
+    }
+
+    fn num_pages(&self) -> u32 {
+        self.reader.num_pages
+    }
+
+    /// In blocks, each entry is made of a pointer followed by the key.
+    ///
+    /// Like in .ndx files, interior blocks have one more pointer than keys.
+    /// The pointer that follows the last key of leaves is 0.
+    fn read_page(&mut self, page_number: u32) -> Result<Page, Error> {
+        if page_number == 0 || page_number >= self.reader.num_pages {
+            return Err(invalid_index("invalid page number"));
+        }
+        let mut bytes = vec![0u8; self.reader.block_size];
+        self.reader
+            .source
+            .seek(SeekFrom::Start(u64::from(page_number) * PAGE_SIZE))
+            .map_err(|error| Error::io_error(error, 0))?;
+        self.reader
+            .source
+            .read_exact(&mut bytes)
+            .map_err(|error| Error::io_error(error, 0))?;
+
+        let num_keys = (&bytes[..4]).read_u32::<LittleEndian>().unwrap() as usize;
+        let entry_size = self.tag.entry_size as usize;
+        let key_length = self.tag.key_length as usize;
+        let max_entries = (bytes.len() - BLOCK_HEADER_SIZE) / entry_size;
+        if num_keys > max_entries {
+            return Err(invalid_index("too many keys in block"));
+        }
+
+        let mut entries = bytes[BLOCK_HEADER_SIZE..]
+            .chunks_exact(entry_size)
+            .take(num_keys + 1)
+            .map(|mut entry| PageEntry {
+                child: entry.read_u32::<LittleEndian>().unwrap(),
+                record: 0,
+                key: entry[..key_length].to_vec(),
+            })
+            .collect::<Vec<_>>();
+
+        let is_leaf = entries.get(num_keys).map_or(0, |entry| entry.child) == 0;
+        if is_leaf {
+            entries.truncate(num_keys);
+            for entry in &mut entries {
+                entry.record = entry.child;
+                entry.child = 0;
+            }
+        }
+
+        Ok(Page {
+            is_leaf,
+            num_keys,
+            entries,
+        })
+    }
+
+    fn compare_keys(&self, lhs: &[u8], rhs: &[u8]) -> Ordering {
+        let ordering = lhs.cmp(rhs);
+        if self.tag.is_descending {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn read_tags() {
+        let index = MdxReader::from_path("tests/data/indexed.mdx").unwrap();
+        let names = index.tags().iter().map(MdxTag::name).collect::<Vec<_>>();
+        assert_eq!(names, vec!["CODE", "CITY"]);
+
+        let tag = index.tag("city").unwrap();
+        assert_eq!(tag.key_expression(), "CITY");
+        assert_eq!(tag.key_length(), 12);
+        assert_eq!(tag.key_type(), 'C');
+        assert!(!tag.is_unique());
+        assert!(!tag.is_descending());
+        assert!(index.tag("CODE").unwrap().is_unique());
+    }
+
+    #[test]
+    fn root_block_is_interior() {
+        let mut index = MdxReader::from_path("tests/data/indexed.mdx").unwrap();
+        let tag = index.tag("CITY").unwrap().clone();
+        let mut tree = MdxTree {
+            reader: &mut index,
+            tag,
+        };
+        let root = tree.read_page(tree.root_page()).unwrap();
+        assert!(!root.is_leaf);
+        assert_eq!(root.entries.len(), root.num_keys + 1);
+    }
+}
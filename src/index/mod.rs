@@ -0,0 +1,247 @@
+//! Module with the definition of fn's and struct's to read index files
+//!
+//! Three formats are supported:
+//!
+//! - dBase III .ndx files, which contain one index ([NdxReader])
+//! - dBase IV .mdx files, which contain multiple indexes called tags ([MdxReader])
+//! - FoxPro .cdx files, which contain multiple indexes called tags ([CdxReader])
+//!
+//! All of them implement the [Index] trait (after selecting a tag, for .mdx and .cdx files).
+use std::cmp::Ordering;
+
+use crate::error::{Error, ErrorKind};
+
+mod cdx;
+mod mdx;
+mod ndx;
+
+pub use cdx::{CdxReader, CdxTag};
+pub use mdx::{MdxReader, MdxTag};
+pub use ndx::{NdxHeader, NdxRange, NdxReader};
+
+/// Byte used to pad character keys
+const KEY_FILL_BYTE: u8 = b' ';
+
+/// Trait implemented by the indexes of the different index file formats
+pub trait Index {
+    /// Returns the indices of the records whose key is `key`, in the order of the index
+    ///
+    /// Character keys are padded with spaces to the key length of the index.
+    /// Indices start at 0, they can be given to [Reader::read_record](crate::Reader::read_record).
+    fn find(&mut self, key: &[u8]) -> Result<Vec<u32>, Error>;
+}
+
+/// One tag (index) of a file that contains multiple tags
+///
+/// Created by [CdxReader::select_tag] or [MdxReader::select_tag].
+///
+/// # Example
+///
+/// ```
+/// use dbase::Index;
+///
+/// # fn main() -> Result<(), dbase::Error> {
+/// let mut index = dbase::MdxReader::from_path("tests/data/indexed.mdx")?;
+/// let mut tag = index.select_tag("CODE")?;
+/// assert_eq!(tag.find(b"C00007")?, vec![1]);
+/// # Ok(())
+/// # }
+/// ```
+pub struct TagIndex<'a, R> {
+    reader: &'a mut R,
+    name: String,
+}
+
+impl<'a, R> TagIndex<'a, R> {
+    /// Returns the name of the selected tag
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+fn invalid_index(msg: &str) -> Error {
+    Error {
+        record_num: 0,
+        field: None,
+        kind: ErrorKind::InvalidIndexFile(msg.to_string()),
+    }
+}
+
+fn unknown_tag(name: &str) -> Error {
+    Error {
+        record_num: 0,
+        field: None,
+        kind: ErrorKind::UnknownIndexTag(name.to_string()),
+    }
+}
+
+/// Pads character keys with spaces
+fn pad_key(key: &[u8], key_length: u16) -> Vec<u8> {
+    let mut key = key.to_vec();
+    if key.len() < key_length as usize {
+        key.resize(key_length as usize, KEY_FILL_BYTE);
+    }
+    key
+}
+
+/// An entry of a page of a B-tree
+struct PageEntry {
+    /// Page number of the left child, 0 in leaf pages
+    child: u32,
+    /// Record number, starting at 1, only set in leaf pages
+    record: u32,
+    key: Vec<u8>,
+}
+
+/// A page of a B-tree, as stored in .ndx and .mdx files
+///
+/// All the keys are stored in the leaves, the keys of interior pages
+/// are the greatest key of their child.
+/// Interior pages have one more entry than keys,
+/// whose child holds the keys greater than all the others.
+struct Page {
+    is_leaf: bool,
+    num_keys: usize,
+    entries: Vec<PageEntry>,
+}
+
+/// B-tree made of [Page]s
+trait BTree {
+    fn root_page(&self) -> u32;
+
+    /// Maximum number of pages that can be visited, to not loop forever on corrupted files
+    fn num_pages(&self) -> u32;
+
+    fn read_page(&mut self, page_number: u32) -> Result<Page, Error>;
+
+    fn compare_keys(&self, lhs: &[u8], rhs: &[u8]) -> Ordering;
+}
+
+/// In order traversal of the keys of a [BTree] that are in a range
+struct BTreeCursor {
+    start: Vec<u8>,
+    end: Vec<u8>,
+    /// Pages from the root to the current leaf, with the position of the current entry
+    stack: Vec<(Page, usize)>,
+    started: bool,
+    finished: bool,
+    num_visited_pages: u32,
+}
+
+impl BTreeCursor {
+    fn new(start: Vec<u8>, end: Vec<u8>) -> Self {
+        Self {
+            start,
+            end,
+            stack: vec![],
+            started: false,
+            finished: false,
+            num_visited_pages: 0,
+        }
+    }
+
+    fn push_page<B: BTree>(&mut self, tree: &mut B, page_number: u32) -> Result<(), Error> {
+        self.num_visited_pages += 1;
+        if self.num_visited_pages > tree.num_pages() {
+            return Err(invalid_index("the B-tree has cycles"));
+        }
+        let page = tree.read_page(page_number)?;
+        self.stack.push((page, 0));
+        Ok(())
+    }
+
+    /// Goes down from the root to the first key that is not less than `start`
+    fn seek_start<B: BTree>(&mut self, tree: &mut B) -> Result<(), Error> {
+        self.push_page(tree, tree.root_page())?;
+        loop {
+            let (page, position) = self.stack.last_mut().unwrap();
+            let keys = &page.entries[..page.num_keys.min(page.entries.len())];
+            *position = keys
+                .iter()
+                .position(|entry| tree.compare_keys(&entry.key, &self.start) != Ordering::Less)
+                .unwrap_or(keys.len());
+            if page.is_leaf {
+                return Ok(());
+            }
+            match page.entries.get(*position) {
+                Some(entry) => {
+                    let child = entry.child;
+                    self.push_page(tree, child)?;
+                }
+                None => return Ok(()),
+            }
+        }
+    }
+
+    /// Moves to the leftmost leaf of the next subtree,
+    /// returns false if there are no more leaves
+    fn advance_to_next_leaf<B: BTree>(&mut self, tree: &mut B) -> Result<bool, Error> {
+        // Find the deepest interior page that has a next child
+        loop {
+            self.stack.pop();
+            let (page, position) = match self.stack.last_mut() {
+                Some(last) => last,
+                None => return Ok(false),
+            };
+            *position += 1;
+            if let Some(entry) = page.entries.get(*position) {
+                let child = entry.child;
+                self.push_page(tree, child)?;
+                break;
+            }
+        }
+        // Go down to the leftmost leaf
+        loop {
+            let (page, _) = self.stack.last().unwrap();
+            if page.is_leaf {
+                return Ok(true);
+            }
+            let child = match page.entries.first() {
+                Some(entry) => entry.child,
+                None => return Err(invalid_index("empty interior page")),
+            };
+            self.push_page(tree, child)?;
+        }
+    }
+
+    fn next_record_impl<B: BTree>(&mut self, tree: &mut B) -> Result<Option<u32>, Error> {
+        if !self.started {
+            self.started = true;
+            self.seek_start(tree)?;
+        }
+
+        loop {
+            let (page, position) = match self.stack.last_mut() {
+                Some(last) => last,
+                None => return Ok(None),
+            };
+            if page.is_leaf && *position < page.num_keys.min(page.entries.len()) {
+                let entry = &page.entries[*position];
+                *position += 1;
+                if tree.compare_keys(&entry.key, &self.end) == Ordering::Greater {
+                    return Ok(None);
+                }
+                // Record numbers start at 1
+                return match entry.record.checked_sub(1) {
+                    Some(index) => Ok(Some(index)),
+                    None => Err(invalid_index("invalid record number")),
+                };
+            }
+            if !self.advance_to_next_leaf(tree)? {
+                return Ok(None);
+            }
+        }
+    }
+
+    /// Returns the index of the next record in the range
+    fn next_record<B: BTree>(&mut self, tree: &mut B) -> Option<Result<u32, Error>> {
+        if self.finished {
+            return None;
+        }
+        let result = self.next_record_impl(tree);
+        if !matches!(result, Ok(Some(_))) {
+            self.finished = true;
+        }
+        result.transpose()
+    }
+}
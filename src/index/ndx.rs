@@ -0,0 +1,341 @@
+//! Module with the definition of fn's and struct's to read dBase III .ndx index files
+//!
+//! A .ndx file is a B-tree over the keys computed from the records of a .dbf file
+//! (often, the value of one field). The file is made of pages of 512 bytes,
+//! the first one being the header.
+use std::cmp::Ordering;
+use std::io::{Read, Seek, SeekFrom};
+
+use byteorder::{LittleEndian, ReadBytesExt};
+
+use super::{invalid_index, pad_key, BTree, BTreeCursor, Index, Page, PageEntry};
+use crate::error::Error;
+
+/// Size of a page, the header is also one page
+const PAGE_SIZE: usize = 512;
+/// Offset of the key expression in the header
+const KEY_EXPRESSION_OFFSET: usize = 24;
+/// Size of the fields that precede the key in an entry:
+/// the page number of the left child and the record number
+const ENTRY_POINTERS_SIZE: usize = 8;
+
+/// Header of a .ndx file
+#[derive(Debug, Clone)]
+pub struct NdxHeader {
+    root_page: u32,
+    num_pages: u32,
+    key_length: u16,
+    max_keys_per_page: u16,
+    is_numeric: bool,
+    entry_size: u32,
+    is_unique: bool,
+    key_expression: String,
+}
+
+impl NdxHeader {
+    fn read_from<T: Read>(source: &mut T) -> std::io::Result<Self> {
+        let mut bytes = [0u8; PAGE_SIZE];
+        source.read_exact(&mut bytes)?;
+
+        let mut header = &bytes[..];
+        let root_page = header.read_u32::<LittleEndian>()?;
+        let num_pages = header.read_u32::<LittleEndian>()?;
+        let _reserved = header.read_u32::<LittleEndian>()?;
+        let key_length = header.read_u16::<LittleEndian>()?;
+        let max_keys_per_page = header.read_u16::<LittleEndian>()?;
+        let is_numeric = header.read_u16::<LittleEndian>()? != 0;
+        let entry_size = header.read_u32::<LittleEndian>()?;
+        let _reserved = header.read_u8()?;
+        let is_unique = header.read_u8()? != 0;
+
+        let expression = &bytes[KEY_EXPRESSION_OFFSET..];
+        let expression_len = expression
+            .iter()
+            .position(|b| *b == 0)
+            .unwrap_or(expression.len());
+        let key_expression = String::from_utf8_lossy(&expression[..expression_len])
+            .trim()
+            .to_string();
+
+        Ok(Self {
+            root_page,
+            num_pages,
+            key_length,
+            max_keys_per_page,
+            is_numeric,
+            entry_size,
+            is_unique,
+            key_expression,
+        })
+    }
+
+    fn validate(&self) -> Result<(), Error> {
+        if self.key_length == 0
+            || (self.entry_size as usize) < self.key_length as usize + ENTRY_POINTERS_SIZE
+            || (self.entry_size as usize) > PAGE_SIZE - 4
+            || (self.is_numeric && self.key_length as usize != std::mem::size_of::<f64>())
+        {
+            return Err(invalid_index("invalid header"));
+        }
+        Ok(())
+    }
+
+    /// Returns the page number of the root of the B-tree
+    pub fn root_page(&self) -> u32 {
+        self.root_page
+    }
+
+    /// Returns the number of pages in the file
+    pub fn num_pages(&self) -> u32 {
+        self.num_pages
+    }
+
+    /// Returns the length of the keys
+    pub fn key_length(&self) -> u16 {
+        self.key_length
+    }
+
+    /// Returns the maximum number of keys a page can hold
+    pub fn max_keys_per_page(&self) -> u16 {
+        self.max_keys_per_page
+    }
+
+    /// Returns true if the keys are numbers (Numeric or Date fields),
+    /// false if they are characters
+    pub fn is_numeric(&self) -> bool {
+        self.is_numeric
+    }
+
+    /// Returns true if the index does not allow duplicated keys
+    pub fn is_unique(&self) -> bool {
+        self.is_unique
+    }
+
+    /// Returns the expression used to compute the keys (e.g. the name of a field)
+    pub fn key_expression(&self) -> &str {
+        &self.key_expression
+    }
+}
+
+/// Reads a page, whose entries are made of the page number of the left child,
+/// the record number and the key
+fn read_page_from<T: Read>(source: &mut T, header: &NdxHeader) -> Result<Page, Error> {
+    let mut bytes = [0u8; PAGE_SIZE];
+    source
+        .read_exact(&mut bytes)
+        .map_err(|error| Error::io_error(error, 0))?;
+
+    let num_keys = (&bytes[..4]).read_u32::<LittleEndian>().unwrap() as usize;
+    let entry_size = header.entry_size as usize;
+    let key_length = header.key_length as usize;
+    // There may be one more entry than keys
+    let num_entries = (num_keys + 1).min((PAGE_SIZE - 4) / entry_size);
+    if num_keys > num_entries {
+        return Err(invalid_index("too many keys in page"));
+    }
+
+    let entries = bytes[4..]
+        .chunks_exact(entry_size)
+        .take(num_entries)
+        .map(|mut entry| {
+            let child = entry.read_u32::<LittleEndian>().unwrap();
+            let record = entry.read_u32::<LittleEndian>().unwrap();
+            PageEntry {
+                child,
+                record,
+                key: entry[..key_length].to_vec(),
+            }
+        })
+        .collect::<Vec<_>>();
+
+    Ok(Page {
+        is_leaf: entries.first().map_or(0, |entry| entry.child) == 0,
+        num_keys,
+        entries,
+    })
+}
+
+/// Reader for dBase III .ndx index files
+///
+/// Character keys are compared byte per byte, after being padded with spaces.
+/// Numeric keys (used for Numeric and Date fields) are the little endian bytes
+/// of a `f64`.
+///
+/// Record indices returned start at 0, they can be given to
+/// [Reader::read_record](crate::Reader::read_record).
+///
+/// # Example
+///
+/// ```
+/// # fn main() -> Result<(), dbase::Error> {
+/// let mut index = dbase::NdxReader::open("tests/data/indexed_code.ndx")?;
+/// let mut reader = dbase::Reader::from_path("tests/data/indexed.dbf")?;
+///
+/// let index_of_record = index.seek_to_key(b"C00007")?.unwrap();
+/// let record = reader.read_record(index_of_record as usize)?.unwrap();
+/// assert_eq!(
+///     record.get("CODE"),
+///     Some(&dbase::FieldValue::Character(Some("C00007".to_string())))
+/// );
+/// # Ok(())
+/// # }
+/// ```
+pub struct NdxReader<T: Read + Seek> {
+    source: T,
+    header: NdxHeader,
+}
+
+impl<T: Read + Seek> NdxReader<T> {
+    /// Creates a new NdxReader, reads the header
+    pub fn new(mut source: T) -> Result<Self, Error> {
+        source
+            .seek(SeekFrom::Start(0))
+            .map_err(|error| Error::io_error(error, 0))?;
+        let header =
+            NdxHeader::read_from(&mut source).map_err(|error| Error::io_error(error, 0))?;
+        header.validate()?;
+        Ok(Self { source, header })
+    }
+
+    /// Returns the header of the index
+    pub fn header(&self) -> &NdxHeader {
+        &self.header
+    }
+
+    /// Returns the index of the first record whose key is `key`
+    pub fn seek_to_key(&mut self, key: &[u8]) -> Result<Option<u32>, Error> {
+        self.range(key, key).next().transpose()
+    }
+
+    /// Returns an iterator over the indices of the records whose keys are between
+    /// `start` and `end` (both included), in the order of the keys
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let mut index = dbase::NdxReader::open("tests/data/indexed_code.ndx")?;
+    /// let records = index
+    ///     .range(b"C00100", b"C00120")
+    ///     .collect::<Result<Vec<u32>, dbase::Error>>()?;
+    /// // C00105, C00112, C00119
+    /// assert_eq!(records, vec![15, 16, 17]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn range(&mut self, start: &[u8], end: &[u8]) -> NdxRange<'_, T> {
+        let cursor = BTreeCursor::new(self.make_key(start), self.make_key(end));
+        NdxRange {
+            reader: self,
+            cursor,
+        }
+    }
+
+    fn make_key(&self, key: &[u8]) -> Vec<u8> {
+        if self.header.is_numeric {
+            key.to_vec()
+        } else {
+            pad_key(key, self.header.key_length)
+        }
+    }
+}
+
+impl<T: Read + Seek> BTree for NdxReader<T> {
+    fn root_page(&self) -> u32 {
+        self.header.root_page
+    }
+
+    fn num_pages(&self) -> u32 {
+        self.header.num_pages
+    }
+
+    fn read_page(&mut self, page_number: u32) -> Result<Page, Error> {
+        if page_number == 0 || page_number >= self.header.num_pages {
+            return Err(invalid_index("invalid page number"));
+        }
+        self.source
+            .seek(SeekFrom::Start(u64::from(page_number) * PAGE_SIZE as u64))
+            .map_err(|error| Error::io_error(error, 0))?;
+        read_page_from(&mut self.source, &self.header)
+    }
+
+    fn compare_keys(&self, lhs: &[u8], rhs: &[u8]) -> Ordering {
+        if self.header.is_numeric {
+            match (key_as_f64(lhs), key_as_f64(rhs)) {
+                (Some(lhs), Some(rhs)) => lhs.partial_cmp(&rhs).unwrap_or(Ordering::Equal),
+                _ => lhs.cmp(rhs),
+            }
+        } else {
+            lhs.cmp(rhs)
+        }
+    }
+}
+
+impl<T: Read + Seek> Index for NdxReader<T> {
+    fn find(&mut self, key: &[u8]) -> Result<Vec<u32>, Error> {
+        self.range(key, key).collect()
+    }
+}
+
+fn key_as_f64(key: &[u8]) -> Option<f64> {
+    let bytes: [u8; 8] = key.try_into().ok()?;
+    Some(f64::from_le_bytes(bytes))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl NdxReader<std::io::BufReader<std::fs::File>> {
+    /// Opens the .ndx file at `path`
+    pub fn open<P: AsRef<std::path::Path>>(path: P) -> Result<Self, Error> {
+        let file = std::fs::File::open(path).map_err(|error| Error::io_error(error, 0))?;
+        Self::new(std::io::BufReader::new(file))
+    }
+}
+
+/// Iterator over the indices of records whose keys are in a range
+///
+/// Created by [NdxReader::range].
+pub struct NdxRange<'a, T: Read + Seek> {
+    reader: &'a mut NdxReader<T>,
+    cursor: BTreeCursor,
+}
+
+impl<'a, T: Read + Seek> Iterator for NdxRange<'a, T> {
+    type Item = Result<u32, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.cursor.next_record(self.reader)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn read_header() {
+        let index = NdxReader::open("tests/data/indexed_city.ndx").unwrap();
+        let header = index.header();
+        assert_eq!(header.key_length(), 12);
+        assert_eq!(header.key_expression(), "CITY");
+        assert!(!header.is_numeric());
+        assert!(!header.is_unique());
+        assert_ne!(header.root_page(), 0);
+    }
+
+    #[test]
+    fn range_spans_multiple_pages() {
+        let mut index = NdxReader::open("tests/data/indexed_code.ndx").unwrap();
+        let all = index
+            .range(b"", b"ZZZZZZZZ")
+            .collect::<Result<Vec<u32>, Error>>()
+            .unwrap();
+        assert_eq!(all.len(), 150);
+
+        let mut sorted = all.clone();
+        sorted.sort_unstable();
+        assert_eq!(sorted, (0..150).collect::<Vec<u32>>());
+
+        assert_eq!(index.range(b"C99999", b"ZZZZZZZZ").count(), 0);
+        assert_eq!(index.range(b"C00002", b"C00001").count(), 0);
+    }
+}
@@ -260,8 +260,30 @@
 //!
 //! This struct allows to read/write an existing or new file
 //! without having to fully read it first.
+//!
+//! # WebAssembly
+//!
+//! There is no filesystem on `wasm32` targets, so the functions that take a path,
+//! like [Reader::from_path], [TableWriterBuilder::build_with_file_dest]
+//! or [File::open_read_only], are not available there.
+//! Use [Reader::from_bytes] or [Reader::from_vec] to read a file held in memory,
+//! and [TableWriterBuilder::build_with_dest] with a [std::io::Cursor] to write one.
+//!
+//! # Asynchronous reading
+//!
+//! With the `tokio` optional feature, the records can be read from a
+//! `tokio::io::AsyncRead` source with the `AsyncReader`.
+//!
+//! # `no_std`
+//!
+//! Everything that reads or writes files needs the `std` feature, which is on by default.
+//! Without it, the crate only needs `alloc`, and a file already loaded in memory
+//! can still be parsed with a [TableView], which decodes the header,
+//! the fields and the records directly from the bytes.
 #![deny(unstable_features)]
+#![cfg_attr(not(feature = "std"), no_std)]
 
+extern crate alloc;
 extern crate byteorder;
 #[cfg(feature = "serde")]
 extern crate serde;
@@ -278,34 +300,90 @@ pub use yore;
 #[cfg(feature = "datafusion")]
 mod datafusion;
 
+#[cfg(feature = "std")]
+mod dbc;
 pub mod encoding;
 mod error;
 mod field;
+#[cfg(feature = "std")]
 mod file;
+#[cfg(feature = "std")]
+mod filter;
 mod header;
+#[cfg(feature = "std")]
+mod index;
 mod memo;
+#[cfg(feature = "std")]
+mod profile;
+#[cfg(feature = "std")]
+mod projection;
+#[cfg(feature = "std")]
 mod reading;
+#[cfg(feature = "std")]
 mod record;
+#[cfg(feature = "std")]
+mod sql;
+#[cfg(feature = "tokio")]
+mod tokio;
+mod view;
+#[cfg(feature = "std")]
 mod writing;
 
+#[cfg(feature = "std")]
 pub use file::{FieldIndex, FieldRef, File, RecordIndex, RecordRef};
 
 #[cfg(feature = "datafusion")]
 pub use crate::datafusion::{DbaseTable, DbaseTableFactory};
+#[cfg(feature = "std")]
+pub use crate::dbc::DatabaseContainer;
 pub use crate::encoding::{Encoding, Unicode, UnicodeLossy};
 pub use crate::error::{Error, ErrorKind, FieldIOError};
-pub use crate::field::types::{Date, DateTime, FieldType, FieldValue, Time, TrimOption};
+pub use crate::field::types::{
+    Date, DateTime, FieldType, FieldValue, FieldValueDisplay, OverflowPolicy, Time, TrimOption,
+};
 pub use crate::field::{FieldConversionError, FieldInfo, FieldName};
-pub use crate::header::CodePageMark;
+#[cfg(feature = "std")]
+pub use crate::filter::RecordFilter;
+pub use crate::header::{CodePageMark, Header, TableFlags, Version};
+#[cfg(feature = "std")]
+pub use crate::index::{
+    CdxReader, CdxTag, Index, MdxReader, MdxTag, NdxHeader, NdxRange, NdxReader, TagIndex,
+};
+#[cfg(feature = "std")]
+pub use crate::profile::ColumnStats;
+#[cfg(feature = "std")]
+pub use crate::projection::Projection;
+#[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+pub use crate::reading::read;
+#[cfg(feature = "std")]
 pub use crate::reading::{
-    read, FieldIterator, NamedValue, ReadableRecord, Reader, ReaderBuilder, ReadingOptions,
-    RecordIterator, TableInfo,
+    read_header_only, transcode, FieldIterator, ForwardOnly, IndexInfo, MappedReader, NamedValue,
+    ReadableRecord, Reader, ReaderBuilder, ReadingOptions, RecordBytesIterator, RecordIterator,
+    SkipPrefix, TableInfo,
 };
+#[cfg(feature = "std")]
 pub use crate::record::Record;
-pub use crate::writing::{FieldWriter, TableWriter, TableWriterBuilder, WritableRecord};
+#[cfg(feature = "tokio")]
+pub use crate::tokio::AsyncReader;
+pub use crate::view::TableView;
+#[cfg(feature = "std")]
+pub use crate::writing::{
+    FieldWriter, NumericOverflowPolicy, PreparedWriter, TableWriter, TableWriterBuilder,
+    WritableRecord,
+};
 
 /// macro to define a struct that implements the ReadableRecord and WritableRecord
 ///
+/// The fields are read and written in the order of the fields of the table,
+/// each struct field from and to the field of the table with the same name
+/// (see [FieldWriter::next_field_matches]), so the struct fields do not have to
+/// be declared in the order of the table.
+///
+/// Reading skips the fields of the table that the struct does not have,
+/// and fails with [ErrorKind::UnknownFieldName] if a struct field is not in the table.
+/// Writing fails with [ErrorKind::UnknownFieldName] if a field of the table
+/// has no struct field.
+///
 /// # Examples
 ///
 /// ```
@@ -321,6 +399,7 @@ pub use crate::writing::{FieldWriter, TableWriter, TableWriterBuilder, WritableR
 /// );
 /// # }
 /// ```
+#[cfg(feature = "std")]
 #[macro_export]
 macro_rules! dbase_record {
     (
@@ -341,11 +420,32 @@ macro_rules! dbase_record {
                 where Source: std::io::Read + std::io::Seek,
                       MemoSource: std::io::Read + std::io::Seek
                 {
+                    $(let mut $field_name = None::<$field_type>;)+
+                    // The fields are read in the order of the table, not of the struct
+                    while field_iterator.next_field_name().is_some() {
+                        $(
+                            if $field_name.is_none()
+                                && field_iterator.next_field_matches(stringify!($field_name))
+                            {
+                                $field_name = Some(
+                                    field_iterator.read_next_field_as::<$field_type>()?.value
+                                );
+                                continue;
+                            }
+                        )+
+                        field_iterator.skip_next_field()?;
+                    }
                     Ok(Self {
                         $(
-                            $field_name: field_iterator
-                                .read_next_field_as::<$field_type>()?
-                                .value
+                            $field_name: $field_name.ok_or_else(|| {
+                                let name = stringify!($field_name);
+                                dbase::FieldIOError::new(
+                                    dbase::ErrorKind::UnknownFieldName(
+                                        name.strip_prefix("r#").unwrap_or(name).to_string()
+                                    ),
+                                    None,
+                                )
+                            })?
                         ),+
                     })
             }
@@ -355,9 +455,19 @@ macro_rules! dbase_record {
            fn write_using<'a, W>(&self, field_writer: &mut dbase::FieldWriter<'a, W>) -> Result<(), dbase::FieldIOError>
            where W: std::io::Write,
            {
-                $(
-                    field_writer.write_next_field_value(&self.$field_name)?;
-                )+
+                // The fields are written in the order of the table, not of the struct
+                while let Some(name) = field_writer.next_field_name() {
+                    $(
+                        if field_writer.next_field_matches(stringify!($field_name)) {
+                            field_writer.write_next_field_value(&self.$field_name)?;
+                            continue;
+                        }
+                    )+
+                    return Err(dbase::FieldIOError::new(
+                        dbase::ErrorKind::UnknownFieldName(name.to_string()),
+                        None,
+                    ));
+                }
                 Ok(())
            }
         }
@@ -1,6 +1,19 @@
+#[cfg(feature = "std")]
 use byteorder::{BigEndian, LittleEndian, ReadBytesExt};
+#[cfg(feature = "std")]
 use std::io::{Read, Seek, SeekFrom};
 
+#[cfg(feature = "std")]
+use crate::error::ErrorKind;
+
+/// Types of the blocks of .fpt files, stored at the beginning of each block
+#[cfg(feature = "std")]
+const FPT_PICTURE_BLOCK: u32 = 0;
+#[cfg(feature = "std")]
+const FPT_TEXT_BLOCK: u32 = 1;
+#[cfg(feature = "std")]
+const FPT_OBJECT_BLOCK: u32 = 2;
+
 /// The different types of Memo file structure there seem to exist
 #[derive(Debug, PartialEq, Copy, Clone)]
 pub(crate) enum MemoFileType {
@@ -10,6 +23,7 @@ pub(crate) enum MemoFileType {
 }
 
 impl MemoFileType {
+    #[cfg(all(feature = "std", not(target_arch = "wasm32")))]
     pub(crate) const fn extension(self) -> &'static str {
         match self {
             MemoFileType::DbaseMemo | MemoFileType::DbaseMemo4 => "dbt",
@@ -20,12 +34,14 @@ impl MemoFileType {
 
 /// Although there are different memo file type with each a different
 /// header organisation, we use the same struct internally
+#[cfg(feature = "std")]
 #[derive(Debug, Copy, Clone)]
 pub(crate) struct MemoHeader {
     next_available_block_index: u32,
     block_size: u32,
 }
 
+#[cfg(feature = "std")]
 impl MemoHeader {
     pub(crate) fn read_from<R: Read>(
         src: &mut R,
@@ -53,6 +69,7 @@ impl MemoHeader {
 }
 
 /// Struct that reads knows how to read data from a memo source
+#[cfg(feature = "std")]
 #[derive(Debug, Clone)]
 pub(crate) struct MemoReader<T> {
     memo_file_type: MemoFileType,
@@ -61,6 +78,7 @@ pub(crate) struct MemoReader<T> {
     internal_buffer: Vec<u8>,
 }
 
+#[cfg(feature = "std")]
 impl<T: Read + Seek> MemoReader<T> {
     pub(crate) fn new(memo_type: MemoFileType, mut src: T) -> std::io::Result<Self> {
         let header = MemoHeader::read_from(&mut src, memo_type)?;
@@ -73,18 +91,53 @@ impl<T: Read + Seek> MemoReader<T> {
         })
     }
 
-    pub(crate) fn read_data_at(&mut self, index: u32) -> std::io::Result<&[u8]> {
+    /// Reads the data of the block at `index`, without the padding or terminator bytes
+    /// that are stripped by [Self::read_data_at]
+    ///
+    /// Only .fpt files store the length of the data, for the other types this
+    /// is the same as [Self::read_data_at].
+    pub(crate) fn read_binary_data_at(&mut self, index: u32) -> Result<&[u8], ErrorKind> {
+        if self.memo_file_type != MemoFileType::FoxBaseMemo {
+            return self.read_data_at(index);
+        }
+        let byte_offset = u64::from(index) * u64::from(self.header.block_size);
+        self.source.seek(SeekFrom::Start(byte_offset))?;
+        let length = self.read_fpt_block_header()?;
+        if length > self.internal_buffer.len() {
+            self.internal_buffer.resize(length, 0);
+        }
+        self.source
+            .read_exact(&mut self.internal_buffer[..length])?;
+        Ok(&self.internal_buffer[..length])
+    }
+
+    /// Reads the type and length of a .fpt block, returns the length
+    ///
+    /// Blocks whose type is not one of the known types hold data compressed
+    /// or encoded in a way we do not know, they are rejected with
+    /// [ErrorKind::UnsupportedMemoCompression] instead of returning garbage.
+    fn read_fpt_block_header(&mut self) -> Result<usize, ErrorKind> {
+        let block_type = self.source.read_u32::<BigEndian>()?;
+        if !matches!(
+            block_type,
+            FPT_PICTURE_BLOCK | FPT_TEXT_BLOCK | FPT_OBJECT_BLOCK
+        ) {
+            return Err(ErrorKind::UnsupportedMemoCompression(block_type));
+        }
+        Ok(self.source.read_u32::<BigEndian>()? as usize)
+    }
+
+    pub(crate) fn read_data_at(&mut self, index: u32) -> Result<&[u8], ErrorKind> {
         let byte_offset = index * self.header.block_size;
         self.source.seek(SeekFrom::Start(u64::from(byte_offset)))?;
 
         match self.memo_file_type {
             MemoFileType::FoxBaseMemo => {
-                let _type = self.source.read_u32::<BigEndian>()?;
-                let length = self.source.read_u32::<BigEndian>()?;
-                if length as usize > self.internal_buffer.len() {
-                    self.internal_buffer.resize(length as usize, 0);
+                let length = self.read_fpt_block_header()?;
+                if length > self.internal_buffer.len() {
+                    self.internal_buffer.resize(length, 0);
                 }
-                let buf_slice = &mut self.internal_buffer[..length as usize];
+                let buf_slice = &mut self.internal_buffer[..length];
                 self.source.read_exact(buf_slice)?;
                 match buf_slice.iter().rposition(|b| *b != 0) {
                     Some(pos) => Ok(&buf_slice[..=pos]),
@@ -115,7 +168,7 @@ impl<T: Read + Seek> MemoReader<T> {
                     if index != self.header.next_available_block_index - 1
                         && e.kind() != std::io::ErrorKind::UnexpectedEof
                     {
-                        return Err(e);
+                        return Err(e.into());
                     }
                 }
                 match self.internal_buffer.iter().position(|b| *b == 0x1A) {
@@ -0,0 +1,252 @@
+//! Module with the definition of [ColumnStats], statistics computed
+//! over the values of each field of a table.
+use std::io::{Read, Seek};
+
+use crate::error::Error;
+use crate::field::types::{currency_from_scaled, FieldType, FieldValue};
+use crate::reading::Reader;
+
+/// Statistics over the values of one field (column) of a table
+///
+/// Computed by [Reader::profile].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnStats {
+    name: String,
+    field_type: FieldType,
+    num_values: usize,
+    num_nulls: usize,
+    min: Option<f64>,
+    max: Option<f64>,
+    sum: Option<f64>,
+    max_length: Option<usize>,
+}
+
+impl ColumnStats {
+    fn new(name: &str, field_type: FieldType) -> Self {
+        let is_number = matches!(
+            field_type,
+            FieldType::Numeric
+                | FieldType::Float
+                | FieldType::Integer
+                | FieldType::AutoIncrement
+                | FieldType::Double
+                | FieldType::DBase7Double
+                | FieldType::Currency
+        );
+        let is_text = matches!(field_type, FieldType::Character | FieldType::Memo);
+        Self {
+            name: name.to_string(),
+            field_type,
+            num_values: 0,
+            num_nulls: 0,
+            min: None,
+            max: None,
+            sum: is_number.then_some(0.0),
+            max_length: is_text.then_some(0),
+        }
+    }
+
+    fn add(&mut self, value: &FieldValue) {
+        if is_null(value) {
+            self.num_nulls += 1;
+            return;
+        }
+        self.num_values += 1;
+        if let Some(number) = as_number(value) {
+            self.min = Some(self.min.map_or(number, |min| min.min(number)));
+            self.max = Some(self.max.map_or(number, |max| max.max(number)));
+            self.sum = self.sum.map(|sum| sum + number);
+        }
+        if let FieldValue::Character(Some(text)) | FieldValue::Memo(text) = value {
+            let length = text.chars().count();
+            self.max_length = self.max_length.map(|max| max.max(length));
+        }
+    }
+
+    /// Returns the name of the field
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the type of the field
+    pub fn field_type(&self) -> FieldType {
+        self.field_type
+    }
+
+    /// Returns the number of values that are not null or blank
+    pub fn num_values(&self) -> usize {
+        self.num_values
+    }
+
+    /// Returns the number of null or blank values
+    ///
+    /// Values are null or blank when they are empty (`None`),
+    /// or for Memo fields, when the text is empty.
+    pub fn num_nulls(&self) -> usize {
+        self.num_nulls
+    }
+
+    /// Returns the smallest value of a numeric field,
+    /// `None` for other fields or if all the values are null
+    pub fn min(&self) -> Option<f64> {
+        self.min
+    }
+
+    /// Returns the greatest value of a numeric field,
+    /// `None` for other fields or if all the values are null
+    pub fn max(&self) -> Option<f64> {
+        self.max
+    }
+
+    /// Returns the sum of the values of a numeric field, `None` for other fields
+    pub fn sum(&self) -> Option<f64> {
+        self.sum
+    }
+
+    /// Returns the length, in characters, of the longest value of a Character
+    /// or Memo field, `None` for other fields
+    ///
+    /// Character values are trimmed according to the
+    /// [ReadingOptions](crate::ReadingOptions) of the reader.
+    pub fn max_length(&self) -> Option<usize> {
+        self.max_length
+    }
+}
+
+fn is_null(value: &FieldValue) -> bool {
+    match value {
+        FieldValue::Character(None)
+        | FieldValue::BinCharacter(None)
+        | FieldValue::Numeric(None)
+        | FieldValue::Float(None)
+        | FieldValue::Logical(None)
+        | FieldValue::Date(None) => true,
+        FieldValue::Memo(text) => text.is_empty(),
+        FieldValue::Picture(bytes) => bytes.is_empty(),
+        _ => false,
+    }
+}
+
+fn as_number(value: &FieldValue) -> Option<f64> {
+    match value {
+        FieldValue::Numeric(Some(value)) => Some(*value),
+        FieldValue::Float(Some(value)) => Some(f64::from(*value)),
+        FieldValue::Integer(value) => Some(f64::from(*value)),
+        FieldValue::Double(value) => Some(*value),
+        FieldValue::Currency(value) => Some(currency_from_scaled(*value)),
+        _ => None,
+    }
+}
+
+impl<T: Read + Seek> Reader<T> {
+    /// Reads all the records once and returns statistics over the values
+    /// of each field, in the order of the fields
+    ///
+    /// Deleted records are not taken into account.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let mut reader = dbase::Reader::from_path("tests/data/stations.dbf")?;
+    /// let stats = reader.profile()?;
+    /// assert_eq!(stats[0].name(), "name");
+    /// assert_eq!(stats[0].num_values() + stats[0].num_nulls(), 86);
+    /// assert_eq!(stats[0].max_length(), Some(44));
+    /// assert_eq!(stats[0].sum(), None);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn profile(&mut self) -> Result<Vec<ColumnStats>, Error> {
+        let mut stats = self
+            .fields()
+            .iter()
+            .map(|field| ColumnStats::new(field.name(), field.field_type()))
+            .collect::<Vec<_>>();
+        for record in self.iter_records() {
+            let record = record?;
+            for column in &mut stats {
+                if let Some(value) = record.get(&column.name) {
+                    column.add(value);
+                }
+            }
+        }
+        Ok(stats)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::io::Cursor;
+
+    use crate::{FieldName, Record, TableWriterBuilder};
+
+    #[test]
+    fn profile_columns() {
+        let mut writer = TableWriterBuilder::new()
+            .add_character_field(FieldName::try_from("name").unwrap(), 10)
+            .add_numeric_field(FieldName::try_from("amount").unwrap(), 8, 2)
+            .add_integer_field(FieldName::try_from("count").unwrap())
+            .add_logical_field(FieldName::try_from("flag").unwrap())
+            .build_with_dest(Cursor::new(Vec::<u8>::new()));
+        let rows = [
+            (Some("short"), Some(-2.5), 3, Some(true)),
+            (Some("longer one"), None, -7, None),
+            (None, Some(10.25), 10, Some(false)),
+        ];
+        for (name, amount, count, flag) in rows {
+            let mut record = Record::default();
+            record.insert(
+                "name".to_string(),
+                FieldValue::Character(name.map(str::to_string)),
+            );
+            record.insert("amount".to_string(), FieldValue::Numeric(amount));
+            record.insert("count".to_string(), FieldValue::Integer(count));
+            record.insert("flag".to_string(), FieldValue::Logical(flag));
+            writer.write_record(&record).unwrap();
+        }
+        let mut cursor = writer.finish().unwrap();
+        cursor.set_position(0);
+
+        let stats = Reader::new(cursor).unwrap().profile().unwrap();
+        assert_eq!(stats.len(), 4);
+
+        let name = &stats[0];
+        assert_eq!(name.field_type(), FieldType::Character);
+        assert_eq!((name.num_values(), name.num_nulls()), (2, 1));
+        assert_eq!(name.max_length(), Some(10));
+        assert_eq!((name.min(), name.max(), name.sum()), (None, None, None));
+
+        let amount = &stats[1];
+        assert_eq!((amount.num_values(), amount.num_nulls()), (2, 1));
+        assert_eq!(amount.min(), Some(-2.5));
+        assert_eq!(amount.max(), Some(10.25));
+        assert_eq!(amount.sum(), Some(7.75));
+        assert_eq!(amount.max_length(), None);
+
+        let count = &stats[2];
+        assert_eq!((count.num_values(), count.num_nulls()), (3, 0));
+        assert_eq!((count.min(), count.max()), (Some(-7.0), Some(10.0)));
+        assert_eq!(count.sum(), Some(6.0));
+
+        let flag = &stats[3];
+        assert_eq!((flag.num_values(), flag.num_nulls()), (2, 1));
+        assert_eq!((flag.min(), flag.sum()), (None, None));
+    }
+
+    #[test]
+    fn numeric_column_without_values() {
+        let mut column = ColumnStats::new("amount", FieldType::Numeric);
+        column.add(&FieldValue::Numeric(None));
+        assert_eq!(column.num_nulls(), 1);
+        assert_eq!(column.min(), None);
+        assert_eq!(column.sum(), Some(0.0));
+
+        let mut column = ColumnStats::new("note", FieldType::Memo);
+        column.add(&FieldValue::Memo(String::new()));
+        column.add(&FieldValue::Memo("été".to_string()));
+        assert_eq!((column.num_values(), column.num_nulls()), (1, 1));
+        assert_eq!(column.max_length(), Some(3));
+    }
+}
@@ -0,0 +1,264 @@
+//! Module with the definition of the [Projection], which copies a table
+//! while selecting, renaming and reordering its fields.
+use std::collections::HashSet;
+use std::io::{Read, Seek, Write};
+
+use crate::error::{Error, ErrorKind};
+use crate::field::types::FieldType;
+use crate::field::{FieldInfo, FieldName};
+use crate::reading::{Reader, TableInfo};
+use crate::writing::TableWriterBuilder;
+use crate::Record;
+
+/// Selection of the fields of a table, in a new order and possibly with new names
+///
+/// Applying a projection to a [Reader] writes a new table that only
+/// has the selected fields, in the order they were selected.
+/// The size of the records and the position of the fields
+/// are computed again for the new table.
+///
+/// The header of the new table (version, flags, code page) is the one of the source.
+/// As memo fields cannot be written yet, they cannot be selected.
+///
+/// # Example
+///
+/// ```
+/// use std::io::Cursor;
+/// use dbase::{FieldName, Projection};
+///
+/// # fn main() -> Result<(), dbase::Error> {
+/// let mut reader = dbase::Reader::from_path("tests/data/stations.dbf")?;
+/// let projection = Projection::new()
+///     .select_as("name", FieldName::try_from("station").unwrap())
+///     .select("line");
+/// let cursor = projection.apply(&mut reader, Cursor::new(Vec::<u8>::new()))?;
+///
+/// let mut reader = dbase::Reader::new(Cursor::new(cursor.into_inner()))?;
+/// let names = reader.fields().iter().map(|f| f.name()).collect::<Vec<_>>();
+/// assert_eq!(names, vec!["station", "line"]);
+/// assert_eq!(reader.header().num_records, 86);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Projection {
+    /// Name of the field in the source, name of the field in the new table
+    fields: Vec<(String, String)>,
+}
+
+impl Projection {
+    /// Creates a projection that selects no fields
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a projection that selects all the fields of the table
+    /// except the ones in `names`, keeping their order
+    pub fn without(fields: &[FieldInfo], names: &[&str]) -> Self {
+        fields
+            .iter()
+            .filter(|info| !names.contains(&info.name()))
+            .fold(Self::new(), |projection, info| {
+                projection.select(info.name())
+            })
+    }
+
+    /// Selects the field named `name`, keeping its name
+    pub fn select(mut self, name: &str) -> Self {
+        self.fields.push((name.to_string(), name.to_string()));
+        self
+    }
+
+    /// Selects the field named `name` and renames it to `new_name`
+    pub fn select_as(mut self, name: &str, new_name: FieldName) -> Self {
+        self.fields.push((name.to_string(), new_name.0));
+        self
+    }
+
+    /// Returns the fields of the new table, in order
+    ///
+    /// Fails if a selected field does not exist in `fields`, is a memo field,
+    /// or if two selected fields would have the same name in the new table.
+    pub fn project_fields(&self, fields: &[FieldInfo]) -> Result<Vec<FieldInfo>, Error> {
+        let mut names = HashSet::new();
+        let mut projected = Vec::with_capacity(self.fields.len());
+        let mut offset = 1u32;
+        for (name, new_name) in &self.fields {
+            let mut info = fields
+                .iter()
+                .find(|info| info.name() == name)
+                .cloned()
+                .ok_or_else(|| error(ErrorKind::UnknownFieldName(name.clone())))?;
+            if matches!(info.field_type, FieldType::Memo | FieldType::Picture) {
+                return Err(error(ErrorKind::Message(format!(
+                    "The memo field '{}' cannot be written",
+                    name
+                ))));
+            }
+            if !names.insert(new_name.as_str()) {
+                return Err(error(ErrorKind::DuplicateFieldName {
+                    name: new_name.clone(),
+                }));
+            }
+            info.name = new_name.clone();
+            info.displacement_field = offset.to_le_bytes();
+            info.offset_in_record = offset as u16;
+            offset += info.record_length() as u32;
+            projected.push(info);
+        }
+        Ok(projected)
+    }
+
+    /// Writes to `dst` a new table with the selected fields of the records of `reader`
+    ///
+    /// All the records are read, from the first one. Returns `dst` once the
+    /// new table is completely written.
+    pub fn apply<T: Read + Seek, W: Write + Seek>(
+        &self,
+        reader: &mut Reader<T>,
+        dst: W,
+    ) -> Result<W, Error> {
+        let fields_info = self.project_fields(reader.fields())?;
+        let table_info = TableInfo::new(*reader.header(), fields_info, reader.encoding().clone());
+        let mut writer = TableWriterBuilder::from_table_info(table_info).build_with_dest(dst);
+        reader.seek(0)?;
+        for record in reader.iter_records() {
+            let mut record = record?;
+            let mut projected = Record::default();
+            for (name, new_name) in &self.fields {
+                if let Some(value) = record.remove(name) {
+                    projected.insert(new_name.clone(), value);
+                }
+            }
+            writer.write_record(&projected)?;
+        }
+        writer.finish()
+    }
+}
+
+fn error(kind: ErrorKind) -> Error {
+    Error {
+        record_num: 0,
+        field: None,
+        kind,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::io::Cursor;
+
+    use crate::{FieldValue, TableWriterBuilder};
+
+    fn three_columns() -> Reader<Cursor<Vec<u8>>> {
+        let mut writer = TableWriterBuilder::new()
+            .add_character_field(FieldName::try_from("first").unwrap(), 10)
+            .add_numeric_field(FieldName::try_from("second").unwrap(), 8, 2)
+            .add_logical_field(FieldName::try_from("third").unwrap())
+            .build_with_dest(Cursor::new(Vec::<u8>::new()));
+        for (text, number, flag) in [("one", 1.5, true), ("two", 2.25, false)] {
+            let mut record = Record::default();
+            record.insert(
+                "first".to_string(),
+                FieldValue::Character(Some(text.to_string())),
+            );
+            record.insert("second".to_string(), FieldValue::Numeric(Some(number)));
+            record.insert("third".to_string(), FieldValue::Logical(Some(flag)));
+            writer.write_record(&record).unwrap();
+        }
+        let mut cursor = writer.finish().unwrap();
+        cursor.set_position(0);
+        Reader::new(cursor).unwrap()
+    }
+
+    #[test]
+    fn drop_middle_column() {
+        let mut reader = three_columns();
+        let projection = Projection::without(reader.fields(), &["second"]);
+        let cursor = projection
+            .apply(&mut reader, Cursor::new(Vec::new()))
+            .unwrap();
+
+        let mut reader = Reader::new(Cursor::new(cursor.into_inner())).unwrap();
+        let names = reader.fields().iter().map(|f| f.name()).collect::<Vec<_>>();
+        assert_eq!(names, vec!["first", "third"]);
+        assert_eq!(reader.header().size_of_record, 1 + 10 + 1);
+        assert_eq!(reader.header().num_records, 2);
+
+        let records = reader.read().unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[1].as_ref().len(), 2);
+        assert_eq!(
+            records[1].get("first"),
+            Some(&FieldValue::Character(Some("two".to_string())))
+        );
+        assert_eq!(
+            records[1].get("third"),
+            Some(&FieldValue::Logical(Some(false)))
+        );
+    }
+
+    #[test]
+    fn rename_and_reorder() {
+        let mut reader = three_columns();
+        let projection = Projection::new()
+            .select("third")
+            .select_as("first", FieldName::try_from("renamed").unwrap());
+        let fields = projection.project_fields(reader.fields()).unwrap();
+        assert_eq!(fields[0].name(), "third");
+        assert_eq!(fields[1].displacement_field, 2u32.to_le_bytes());
+
+        let cursor = projection
+            .apply(&mut reader, Cursor::new(Vec::new()))
+            .unwrap();
+        let mut reader = Reader::new(Cursor::new(cursor.into_inner())).unwrap();
+        let records = reader.read().unwrap();
+        assert_eq!(
+            records[0].get("renamed"),
+            Some(&FieldValue::Character(Some("one".to_string())))
+        );
+        assert_eq!(
+            records[0].get("third"),
+            Some(&FieldValue::Logical(Some(true)))
+        );
+    }
+
+    #[test]
+    fn invalid_selections() {
+        let reader = three_columns();
+        let error = Projection::new()
+            .select("fourth")
+            .project_fields(reader.fields())
+            .unwrap_err();
+        assert!(matches!(error.kind(), ErrorKind::UnknownFieldName(name) if name == "fourth"));
+
+        let error = Projection::new()
+            .select("first")
+            .select_as("third", FieldName::try_from("first").unwrap())
+            .project_fields(reader.fields())
+            .unwrap_err();
+        assert!(matches!(error.kind(), ErrorKind::DuplicateFieldName { name } if name == "first"));
+    }
+
+    #[test]
+    fn apply_to_partly_read_reader() {
+        let mut reader = Reader::from_path("tests/data/stations.dbf").unwrap();
+        let mut records = reader.iter_records();
+        records.next().unwrap().unwrap();
+        records.next().unwrap().unwrap();
+
+        let cursor = Projection::new()
+            .select("name")
+            .apply(&mut reader, Cursor::new(Vec::new()))
+            .unwrap();
+        let mut reader = Reader::new(Cursor::new(cursor.into_inner())).unwrap();
+        assert_eq!(reader.header().num_records, 86);
+        let records = reader.read().unwrap();
+        assert_eq!(records.len(), 86);
+        assert_eq!(
+            records[0].get("name"),
+            Some(&FieldValue::Character(Some("Van Dorn Street".to_string())))
+        );
+    }
+}
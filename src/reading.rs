@@ -1,24 +1,31 @@
 //! Module with the definition of fn's and struct's to read .dbf files
 
+use std::collections::HashMap;
 use std::convert::TryFrom;
+#[cfg(not(target_arch = "wasm32"))]
 use std::fs::File;
-use std::io::{BufReader, Read, Seek, SeekFrom};
+#[cfg(not(target_arch = "wasm32"))]
+use std::io::BufReader;
+use std::io::{Cursor, Read, Seek, SeekFrom, Write};
 use std::iter::FusedIterator;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 
+use crate::dbc::DatabaseContainer;
 use crate::encoding::DynEncoding;
 use crate::error::{Error, ErrorKind, FieldIOError};
-use crate::field::types::{FieldType, FieldValue, TrimOption};
-use crate::field::{DeletionFlag, FieldInfo};
-use crate::header::Header;
-use crate::memo::{MemoFileType, MemoReader};
+use crate::field::types::{
+    CharacterTrim, FieldType, FieldValue, OverflowPolicy, TrimOption, TrimSet,
+};
+use crate::field::{
+    computed_record_size, field_name_matches, validate_record_size, DeletionFlag, FieldInfo,
+    DELETION_FLAG_SIZE,
+};
+use crate::header::{Dialect, Header, BACKLINK_SIZE};
+use crate::memo::MemoReader;
+use crate::writing::{TableWriter, TableWriterBuilder, FILE_TERMINATOR};
 use crate::{Encoding, FieldConversionError, Record};
 
-/// Value of the byte between the last RecordFieldInfo and the first record
-pub(crate) const TERMINATOR_VALUE: u8 = 0x0D;
-
-pub(crate) const BACKLINK_SIZE: u16 = 263;
-
 /// Trait to be implemented by structs that represent records read from a
 /// dBase file.
 ///
@@ -41,47 +48,498 @@ pub trait ReadableRecord: Sized {
 /// create a new TableWriter which would write file
 /// with the same record structure as another dbase file.
 ///
-/// You can get this by using [Reader::into_table_info].
+/// You can get this by using [Reader::into_table_info],
+/// or [TableInfo::from_path] when the records are not needed.
 #[derive(Clone)]
 pub struct TableInfo {
     pub(crate) header: Header,
     pub(crate) fields_info: Vec<FieldInfo>,
     pub(crate) encoding: DynEncoding,
+    /// Index of the fields by name, built on the first lookup
+    field_indices: OnceLock<HashMap<String, usize>>,
 }
 
-/// Options related to reading
-#[derive(Copy, Clone, Debug)]
-pub struct ReadingOptions {
-    pub(crate) character_trim: TrimOption,
-}
-
-impl Default for ReadingOptions {
-    fn default() -> Self {
+impl TableInfo {
+    pub(crate) fn new(header: Header, fields_info: Vec<FieldInfo>, encoding: DynEncoding) -> Self {
         Self {
-            character_trim: TrimOption::BeginEnd,
+            header,
+            fields_info,
+            encoding,
+            field_indices: OnceLock::new(),
         }
     }
+
+    /// Reads only the header and the fields information of the file at `path`,
+    /// the file is closed afterwards.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let table_info = dbase::TableInfo::from_path("tests/data/stations.dbf")?;
+    /// assert_eq!(table_info.fields().len(), 4);
+    /// assert_eq!(table_info.header().num_records, 86);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let file = File::open(path).map_err(|error| Error::io_error(error, 0))?;
+        let file = crate::File::open(BufReader::new(file))?;
+        Ok(Self::new(
+            file.header,
+            file.fields_info.inner,
+            file.encoding,
+        ))
+    }
+
+    /// Returns the header of the file
+    pub fn header(&self) -> &Header {
+        &self.header
+    }
+
+    /// Returns the fields of the records
+    pub fn fields(&self) -> &[FieldInfo] {
+        &self.fields_info
+    }
+
+    /// Returns the fields of the records, to change them
+    ///
+    /// As the table info is [Clone], a copy can be changed while the original
+    /// is kept, for example to write a new version of a table.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let original = dbase::TableInfo::from_path("tests/data/stations.dbf")?;
+    /// let mut renamed = original.clone();
+    /// renamed.fields_mut()[0].set_name(dbase::FieldName::try_from("station").unwrap());
+    /// assert_eq!(renamed.fields()[0].name(), "station");
+    /// assert_eq!(original.fields()[0].name(), "name");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn fields_mut(&mut self) -> &mut [FieldInfo] {
+        // The fields may be renamed
+        self.field_indices.take();
+        &mut self.fields_info
+    }
+
+    /// Returns the index of the field named `name`, `None` if there is no such field
+    ///
+    /// Like [Record::get], the exact name is looked up first, then the name
+    /// is compared ignoring the ASCII case.
+    /// The index of the names is built on the first call,
+    /// so looking up fields is then fast even for tables with many fields.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let table_info = dbase::TableInfo::from_path("tests/data/stations.dbf")?;
+    /// assert_eq!(table_info.index_of("line"), Some(3));
+    /// assert_eq!(table_info.index_of("LINE"), Some(3));
+    /// assert_eq!(table_info.index_of("missing"), None);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn index_of(&self, name: &str) -> Option<usize> {
+        let indices = self.field_indices.get_or_init(|| {
+            let mut indices = HashMap::with_capacity(self.fields_info.len());
+            for (index, info) in self.fields_info.iter().enumerate() {
+                indices.entry(info.name().to_string()).or_insert(index);
+            }
+            indices
+        });
+        indices.get(name).copied().or_else(|| {
+            self.fields_info
+                .iter()
+                .position(|info| info.name().eq_ignore_ascii_case(name))
+        })
+    }
+
+    /// Returns the field named `name`, see [TableInfo::index_of]
+    pub fn field_by_name(&self, name: &str) -> Option<&FieldInfo> {
+        self.index_of(name).map(|index| &self.fields_info[index])
+    }
+
+    /// Returns the size of a record as computed from the fields,
+    /// that is the sum of all the field lengths plus
+    /// one byte for the deletion flag.
+    pub fn computed_record_size(&self) -> usize {
+        computed_record_size(&self.fields_info)
+    }
+}
+
+/// Information about the structural (production) index of a file
+///
+/// The index itself is not read, this only tells that
+/// a .cdx file exists next to the .dbf file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexInfo {
+    path: PathBuf,
+}
+
+impl IndexInfo {
+    /// Returns the path to the .cdx file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Opens the index file
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn open(&self) -> Result<crate::CdxReader<BufReader<File>>, Error> {
+        crate::CdxReader::from_path(&self.path)
+    }
+}
+
+/// Options related to reading
+#[derive(Copy, Clone, Debug, Default)]
+pub struct ReadingOptions {
+    pub(crate) character_trim: CharacterTrim,
+    pub(crate) numeric_overflow: OverflowPolicy,
+    pub(crate) lenient: bool,
+    pub(crate) ignore_num_records: bool,
+    pub(crate) limit: Option<usize>,
+    pub(crate) skip: Option<usize>,
 }
 
 impl ReadingOptions {
     /// Customize how spaces ` ` are trimmed within [FieldValue::Character]
     ///
-    /// By default they are trimmed at the begining and the end
+    /// By default they are trimmed at the begining and the end.
+    /// The trimmed bytes are set by [Self::character_trim_bytes].
     pub fn character_trim(mut self, trim_option: TrimOption) -> Self {
-        self.character_trim = trim_option;
+        self.character_trim.option = trim_option;
+        self
+    }
+
+    /// Customize which bytes are trimmed within [FieldValue::Character],
+    /// on the sides set by [Self::character_trim]
+    ///
+    /// By default spaces ` ` and NUL bytes (`0x00`) are trimmed.
+    /// When NUL is in the set, the value ends at the first NUL byte,
+    /// as some programs pad the fields with them instead of spaces.
+    /// The bytes are trimmed before the text is decoded.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// // Some programs pad with underscores
+    /// let options = dbase::ReadingOptions::default().character_trim_bytes(b" _");
+    /// ```
+    pub fn character_trim_bytes(mut self, bytes: &[u8]) -> Self {
+        self.character_trim.bytes = TrimSet::new(bytes);
+        self
+    }
+
+    /// Customize how Numeric and Float fields only made of `*`,
+    /// which dBase writes when a number does not fit in the field, are read
+    ///
+    /// By default they are read as empty (`None`), see [OverflowPolicy].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dbase::OverflowPolicy;
+    ///
+    /// let options = dbase::ReadingOptions::default().on_numeric_overflow(OverflowPolicy::ReturnError);
+    /// ```
+    pub fn on_numeric_overflow(mut self, policy: OverflowPolicy) -> Self {
+        self.numeric_overflow = policy;
+        self
+    }
+
+    /// Makes the reader accept some malformed files instead of returning an error
+    ///
+    /// By default the reader is strict. In lenient mode:
+    ///
+    /// - a last record that is cut by the end of the file is read: the fields that
+    ///   are complete are read, the others are read as if they were blank
+    ///   (`None` for the types that can be empty, `0` for binary numbers)
+    /// - when the terminator of the field descriptors is missing, the number of fields
+    ///   is computed from the offset to the first record, this only applies when
+    ///   the options are given to the [ReaderBuilder](ReaderBuilder::with_options)
+    pub fn lenient(mut self, lenient: bool) -> Self {
+        self.lenient = lenient;
+        self
+    }
+
+    /// Makes the reader ignore the number of records written in the header
+    ///
+    /// Some programs write `0xFFFFFFFF` or do not update the number of records.
+    /// When ignored, records are read until the file terminator (`0x1A`)
+    /// or the end of the data. A last record that is cut by the end of the file
+    /// is handled as set by [Self::lenient].
+    ///
+    /// By default the number of records of the header is used.
+    pub fn ignore_num_records(mut self, ignore: bool) -> Self {
+        self.ignore_num_records = ignore;
+        self
+    }
+
+    /// Makes the record iterators of a [Reader] stop after `limit` records,
+    /// even if the header says the file has more
+    ///
+    /// Deleted records are not counted. By default there is no limit.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let mut reader = dbase::Reader::from_path("tests/data/stations.dbf")?;
+    /// reader.set_options(dbase::ReadingOptions::default().limit(10));
+    /// let records = reader.read()?;
+    /// assert_eq!(records.len(), 10);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Makes the record iterators of a [Reader] start at the record at index `skip`
+    ///
+    /// The iterators seek to the record instead of reading the previous ones,
+    /// so they start from there even if the reader was elsewhere.
+    /// Unlike [Self::limit], deleted records are counted.
+    /// With a source that cannot seek, such as [ForwardOnly], the skipped
+    /// records are read and discarded.
+    ///
+    /// Combined with [Self::limit], this allows reading a file page by page.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let mut reader = dbase::Reader::from_path("tests/data/stations.dbf")?;
+    /// reader.set_options(dbase::ReadingOptions::default().skip(80).limit(10));
+    /// let records = reader.read()?;
+    /// // There are 86 records
+    /// assert_eq!(records.len(), 6);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn skip(mut self, skip: usize) -> Self {
+        self.skip = Some(skip);
         self
     }
 }
 
+/// Adapter to read from sources that cannot seek, such as an entry of a ZIP archive,
+/// a decompressor or a network stream
+///
+/// Seeking forward is done by reading and discarding bytes, seeking backward
+/// or from the end is not possible and returns an error.
+///
+/// This is enough to read all the records in order (e.g. using [Reader::iter_records]
+/// or [Reader::read]), features that need random access (e.g. [Reader::read_record]
+/// on a previous record, [Reader::count_records]) fail.
+/// For them, extract the file first.
+///
+/// # Example
+///
+/// ```
+/// use std::io::Read;
+///
+/// # fn main() -> Result<(), dbase::Error> {
+/// let data = std::fs::read("tests/data/line.dbf").unwrap();
+/// // A Chain cannot seek
+/// let source = data[..100].chain(&data[100..]);
+///
+/// let mut reader = dbase::Reader::new(dbase::ForwardOnly::new(source))?;
+/// let records = reader.read()?;
+/// assert_eq!(records.len(), 1);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct ForwardOnly<R> {
+    inner: R,
+    position: u64,
+}
+
+impl<R: Read> ForwardOnly<R> {
+    /// Wraps the source, its current position is considered to be the start of the file
+    pub fn new(inner: R) -> Self {
+        Self { inner, position: 0 }
+    }
+
+    /// Returns the wrapped source
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Read> Read for ForwardOnly<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let num_read = self.inner.read(buf)?;
+        self.position += num_read as u64;
+        Ok(num_read)
+    }
+}
+
+impl<R: Read> Seek for ForwardOnly<R> {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        let target = match pos {
+            SeekFrom::Start(offset) => Some(offset),
+            SeekFrom::Current(offset) => self.position.checked_add_signed(offset),
+            SeekFrom::End(_) => None,
+        };
+        let target = match target {
+            Some(target) if target >= self.position => target,
+            _ => {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::Unsupported,
+                    "the source can only seek forward",
+                ))
+            }
+        };
+
+        let to_skip = target - self.position;
+        let skipped = std::io::copy(&mut self.by_ref().take(to_skip), &mut std::io::sink())?;
+        if skipped != to_skip {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::UnexpectedEof,
+                "cannot seek past the end of the source",
+            ));
+        }
+        Ok(self.position)
+    }
+}
+
+/// Wrapper of a source whose first bytes are not part of the dBase file
+///
+/// Some files are prefixed with stray bytes, like a UTF-8 BOM or artifacts
+/// of the program that exported them. This wrapper hides the prefix,
+/// so that the file appears to start at the header.
+///
+/// # Example
+///
+/// ```
+/// # fn main() -> Result<(), dbase::Error> {
+/// let mut data = vec![0xEF, 0xBB, 0xBF];
+/// data.extend(std::fs::read("tests/data/line.dbf").unwrap());
+///
+/// let source = dbase::SkipPrefix::scan_for_header(std::io::Cursor::new(data), 16)?;
+/// assert_eq!(source.prefix_len(), 3);
+/// let mut reader = dbase::Reader::new(source)?;
+/// assert_eq!(reader.read()?.len(), 1);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct SkipPrefix<R> {
+    inner: R,
+    prefix_len: u64,
+}
+
+impl<R: Read + Seek> SkipPrefix<R> {
+    /// Wraps the source, its first `prefix_len` bytes are skipped
+    pub fn new(mut inner: R, prefix_len: u64) -> std::io::Result<Self> {
+        inner.seek(SeekFrom::Start(prefix_len))?;
+        Ok(Self { inner, prefix_len })
+    }
+
+    /// Searches the first `max_prefix_len + 1` positions of the source
+    /// for a plausible header, and wraps the source so that it starts there
+    ///
+    /// A header is plausible when its version is known, its date of last update
+    /// is a valid date, and the sizes of the header and of the records it gives
+    /// fit in the source. The first plausible header is chosen.
+    ///
+    /// When none is plausible, the source is used from its start if a header
+    /// can be read there, otherwise the error of reading the header at the start
+    /// is returned, as if the source was not scanned.
+    pub fn scan_for_header(mut inner: R, max_prefix_len: u64) -> Result<Self, Error> {
+        let source_len = inner
+            .seek(SeekFrom::End(0))
+            .map_err(|error| Error::io_error(error, 0))?;
+        let mut header_at_start = None;
+        for prefix_len in 0..=max_prefix_len.min(source_len) {
+            inner
+                .seek(SeekFrom::Start(prefix_len))
+                .map_err(|error| Error::io_error(error, 0))?;
+            match Header::read_from(&mut inner) {
+                Ok(header) if is_plausible_header(&header, source_len - prefix_len) => {
+                    return Self::new(inner, prefix_len).map_err(|error| Error::io_error(error, 0));
+                }
+                result if prefix_len == 0 => header_at_start = Some(result),
+                _ => {}
+            }
+        }
+        match header_at_start {
+            Some(Err(error)) => Err(error),
+            _ => Self::new(inner, 0).map_err(|error| Error::io_error(error, 0)),
+        }
+    }
+
+    /// Returns the number of bytes skipped at the start of the source
+    pub fn prefix_len(&self) -> u64 {
+        self.prefix_len
+    }
+
+    /// Returns the wrapped source
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+fn is_plausible_header(header: &Header, file_len: u64) -> bool {
+    let date = &header.last_update;
+    let min_header_size = if header.file_type.is_visual_fox_pro() {
+        Header::SIZE + 1 + usize::from(BACKLINK_SIZE)
+    } else {
+        Header::SIZE + 1
+    };
+    (1..=12).contains(&date.month())
+        && (1..=31).contains(&date.day())
+        && usize::from(header.offset_to_first_record) >= min_header_size
+        && header.size_of_record > DELETION_FLAG_SIZE as u16
+        && u64::from(header.offset_to_first_record) <= file_len
+}
+
+impl<R: Read> Read for SkipPrefix<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.inner.read(buf)
+    }
+}
+
+impl<R: Seek> Seek for SkipPrefix<R> {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        let pos = match pos {
+            SeekFrom::Start(offset) => SeekFrom::Start(
+                offset
+                    .checked_add(self.prefix_len)
+                    .ok_or_else(|| std::io::Error::from(std::io::ErrorKind::InvalidInput))?,
+            ),
+            pos => pos,
+        };
+        let position = self.inner.seek(pos)?;
+        position.checked_sub(self.prefix_len).ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "cannot seek before the end of the skipped prefix",
+            )
+        })
+    }
+}
+
 /// Convenience builder to create a reader directly from file sources
 ///
+/// The sources are read in small chunks, so they should be buffered
+/// (e.g. using a `BufReader`), unless they are already in memory.
+///
 /// # Example
 ///
 /// ```
 /// use std::fs::File;
+/// use std::io::BufReader;
 ///
 /// # fn main() -> Result<(), dbase::Error> {
-/// let dbf_file = File::open("tests/data/line.dbf").unwrap();
+/// let dbf_file = BufReader::new(File::open("tests/data/line.dbf").unwrap());
 /// let options = dbase::ReadingOptions::default()
 ///     .character_trim(dbase::TrimOption::BeginEnd);
 ///
@@ -132,7 +590,8 @@ impl<T: Read + Seek, E: Encoding + 'static> ReaderBuilder<T, E> {
     }
 
     pub fn build(self) -> Result<Reader<T>, Error> {
-        let file = crate::File::open(self.source)?;
+        let file =
+            crate::File::open_with_reading_options(self.source, self.options.unwrap_or_default())?;
 
         let memo_reader = if let Some(memo_source) = self.memo_source {
             let memo_type = file.header.file_type.supported_memo_type();
@@ -157,6 +616,7 @@ impl<T: Read + Seek, E: Encoding + 'static> ReaderBuilder<T, E> {
                 .encoding
                 .map_or_else(|| file.encoding, DynEncoding::new),
             options: self.options.unwrap_or_default(),
+            structural_index: None,
         })
     }
 }
@@ -173,6 +633,7 @@ pub struct Reader<T: Read + Seek> {
     fields_info: Vec<FieldInfo>,
     encoding: DynEncoding,
     options: ReadingOptions,
+    structural_index: Option<IndexInfo>,
 }
 
 impl<T: Read + Seek> Reader<T> {
@@ -183,6 +644,11 @@ impl<T: Read + Seek> Reader<T> {
     /// Creating a reader from a file path using the [from_path](struct.Reader.html#method.from_path) is the prefered
     /// way of doing it as it wraps the file in a BufReader for performance.
     ///
+    /// The source is read in small chunks, so if it is not already in memory,
+    /// it should be buffered by the caller (e.g. using a `BufReader`).
+    ///
+    /// Sources that cannot seek can be wrapped in a [ForwardOnly].
+    ///
     /// # Example
     ///
     /// ```
@@ -211,6 +677,7 @@ impl<T: Read + Seek> Reader<T> {
             fields_info: file.fields_info.inner,
             encoding: file.encoding,
             options: ReadingOptions::default(),
+            structural_index: None,
         })
     }
 
@@ -231,70 +698,1019 @@ impl<T: Read + Seek> Reader<T> {
         self.options = options;
     }
 
-    /// Returns the header of the file
-    pub fn header(&self) -> &Header {
-        &self.header
+    /// Returns the header of the file
+    pub fn header(&self) -> &Header {
+        &self.header
+    }
+
+    /// Returns the fields contained in the opened file
+    pub fn fields(&self) -> &[FieldInfo] {
+        &self.fields_info
+    }
+
+    /// Returns the number of fields of the records
+    pub fn num_fields(&self) -> usize {
+        self.fields_info.len()
+    }
+
+    /// Returns an iterator over the names of the fields, in order
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dbase::FieldType;
+    ///
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let reader = dbase::Reader::from_path("tests/data/stations.dbf")?;
+    /// assert_eq!(reader.num_fields(), 4);
+    /// let names = reader.field_names().collect::<Vec<_>>();
+    /// assert_eq!(names, vec!["name", "marker-col", "marker-sym", "line"]);
+    /// assert!(reader.field_types().all(|field_type| field_type == FieldType::Character));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn field_names(&self) -> impl Iterator<Item = &str> {
+        self.fields_info.iter().map(FieldInfo::name)
+    }
+
+    /// Returns an iterator over the types of the fields, in order
+    pub fn field_types(&self) -> impl Iterator<Item = FieldType> + '_ {
+        self.fields_info.iter().map(FieldInfo::field_type)
+    }
+
+    /// Returns an iterator over the descriptors of the fields, in order
+    ///
+    /// Same as iterating over [Self::fields].
+    pub fn field_descriptors(&self) -> impl Iterator<Item = &FieldInfo> {
+        self.fields_info.iter()
+    }
+
+    pub(crate) fn encoding(&self) -> &DynEncoding {
+        &self.encoding
+    }
+
+    /// Returns whether the header says that the file has a structural (production) index
+    pub fn has_structural_index(&self) -> bool {
+        self.header.table_flags.has_structural_cdx()
+    }
+
+    /// Returns the path of the [database container](DatabaseContainer) (.dbc)
+    /// the table belongs to, relative to the table
+    ///
+    /// Only Visual FoxPro tables can belong to a database,
+    /// the path is read from the backlink that follows their field descriptors.
+    /// Returns `None` for other tables and free tables.
+    pub fn database_container_path(&mut self) -> Result<Option<String>, Error> {
+        if !self.header.file_type.is_visual_fox_pro() {
+            return Ok(None);
+        }
+        let position = self
+            .source
+            .stream_position()
+            .map_err(|error| Error::io_error(error, 0))?;
+        let mut backlink = [0u8; BACKLINK_SIZE as usize];
+        let backlink_start = u64::from(
+            self.header
+                .offset_to_first_record
+                .saturating_sub(BACKLINK_SIZE),
+        );
+        self.source
+            .seek(SeekFrom::Start(backlink_start))
+            .and_then(|_| self.source.read_exact(&mut backlink))
+            .and_then(|_| self.source.seek(SeekFrom::Start(position)))
+            .map_err(|error| Error::io_error(error, 0))?;
+
+        let length = backlink
+            .iter()
+            .position(|byte| *byte == 0)
+            .unwrap_or(backlink.len());
+        if length == 0 {
+            return Ok(None);
+        }
+        let path = self
+            .encoding
+            .decode(&backlink[..length])
+            .map_err(|error| Error {
+                record_num: 0,
+                field: None,
+                kind: error.into(),
+            })?;
+        Ok(Some(path.into_owned()))
+    }
+
+    /// Sets the [long names](FieldInfo::long_name) of the fields to the names
+    /// the database container has for the table
+    ///
+    /// Returns false, and does not change the fields, if the table is not in the
+    /// database or if the database does not have as many fields as the table.
+    pub fn set_long_field_names(&mut self, database: &DatabaseContainer, table_name: &str) -> bool {
+        match database.field_names(table_name) {
+            Some(names) if names.len() == self.fields_info.len() => {
+                for (info, name) in self.fields_info.iter_mut().zip(names) {
+                    info.long_name = Some(name.clone());
+                }
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns the structural index found next to the file
+    ///
+    /// This is only known when the reader was created with [Reader::from_path],
+    /// and the file [has a structural index](Reader::has_structural_index)
+    /// and a .cdx file with the same name exists.
+    pub fn structural_index(&self) -> Option<&IndexInfo> {
+        self.structural_index.as_ref()
+    }
+
+    /// Checks that the size of records written in the header
+    /// matches the size computed from the fields.
+    ///
+    /// A mismatch means the file is most likely corrupted.
+    ///
+    /// This is already checked when the reader is created.
+    pub fn validate_record_size(&self) -> Result<(), Error> {
+        validate_record_size(&self.header, &self.fields_info).map_err(|kind| Error {
+            record_num: 0,
+            field: None,
+            kind,
+        })
+    }
+
+    /// Creates an iterator of records of the type you want
+    pub fn iter_records_as<R: ReadableRecord>(&mut self) -> RecordIterator<T, R> {
+        let record_size = computed_record_size(&self.fields_info);
+        let current_record = self.current_record_index();
+        RecordIterator {
+            reader: self,
+            record_type: std::marker::PhantomData,
+            current_record,
+            num_returned: 0,
+            skipped: false,
+            record_data_buffer: std::io::Cursor::new(vec![0u8; record_size]),
+            field_data_buffer: [0u8; 255],
+        }
+    }
+
+    /// Shortcut function to get an iterator over the [Records](struct.Record.html) in the file
+    pub fn iter_records(&mut self) -> RecordIterator<T, Record> {
+        self.iter_records_as::<Record>()
+    }
+
+    /// Creates an iterator of records of the type you want,
+    /// along with the bytes of each record as they are in the file
+    ///
+    /// The bytes are the `size_of_record` bytes of the record, deletion flag included,
+    /// so that records that were not modified can be written back byte for byte.
+    /// Deleted records are skipped, as with [Self::iter_records_as].
+    ///
+    /// With a [lenient](ReadingOptions::lenient) reader, the bytes of the fields
+    /// that are missing from a truncated last record are zeroes.
+    pub fn iter_records_with_bytes_as<R: ReadableRecord>(
+        &mut self,
+    ) -> RecordBytesIterator<'_, T, R> {
+        RecordBytesIterator {
+            inner: self.iter_records_as::<R>(),
+        }
+    }
+
+    /// Shortcut function to get an iterator over the [Records](struct.Record.html)
+    /// in the file, along with their bytes
+    ///
+    /// See [Self::iter_records_with_bytes_as].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let mut reader = dbase::Reader::from_path("tests/data/line.dbf")?;
+    /// let record_size = reader.header().size_of_record as usize;
+    /// for result in reader.iter_records_with_bytes() {
+    ///     let (bytes, record) = result?;
+    ///     assert_eq!(bytes.len(), record_size);
+    ///     assert_eq!(bytes[0], b' ');
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn iter_records_with_bytes(&mut self) -> RecordBytesIterator<'_, T, Record> {
+        self.iter_records_with_bytes_as::<Record>()
+    }
+
+    /// Creates an iterator over the [Records](struct.Record.html) in the file,
+    /// where each value is replaced by `f(field_name, value)`
+    ///
+    /// Useful to normalize the values while reading, e.g. to trim
+    /// or uppercase strings. Deleted records are skipped and the options
+    /// of the reader are applied, as with [Self::iter_records].
+    ///
+    /// `f` is called on each value as soon as it is decoded, so the mapped
+    /// value is the one stored in the record, without a second pass over it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dbase::FieldValue;
+    ///
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let mut reader = dbase::Reader::from_path("tests/data/stations.dbf")?;
+    /// let mut records = reader.map_fields(|_name, value| match value {
+    ///     FieldValue::Character(text) => FieldValue::Character(text.map(|t| t.to_uppercase())),
+    ///     value => value,
+    /// });
+    /// let record = records.next().unwrap()?;
+    /// assert_eq!(
+    ///     record["name"],
+    ///     FieldValue::Character(Some("VAN DORN STREET".to_string()))
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn map_fields<F>(&mut self, f: F) -> MappedReader<'_, T, F>
+    where
+        F: Fn(&str, FieldValue) -> FieldValue,
+    {
+        MappedReader {
+            inner: self.iter_records(),
+            f,
+        }
+    }
+
+    /// Writes the records read by this reader with `writer`,
+    /// returns the number of records copied
+    ///
+    /// The values are decoded with the encoding of the reader and encoded with
+    /// the one of the writer, so a file can be transcoded by giving the writer
+    /// another encoding (see [TableWriterBuilder::set_encoding](crate::TableWriterBuilder::set_encoding)).
+    /// The version of the header can also be changed on the writer's side.
+    ///
+    /// The values are written to the fields of the writer that have the same name.
+    /// As memo fields cannot be written yet, the writer cannot have any.
+    /// Deleted records are not copied.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::io::Cursor;
+    ///
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let table_info = dbase::TableInfo::from_path("tests/data/stations.dbf")?;
+    /// let mut reader = dbase::Reader::from_path("tests/data/stations.dbf")?;
+    /// let mut writer = dbase::TableWriterBuilder::from_table_info(table_info)
+    ///     .set_encoding(dbase::Unicode)
+    ///     .build_with_dest(Cursor::new(Vec::<u8>::new()));
+    /// assert_eq!(reader.write_copy_to(&mut writer)?, 86);
+    ///
+    /// let cursor = writer.finish()?;
+    /// let mut copy = dbase::Reader::new(Cursor::new(cursor.into_inner()))?;
+    /// assert_eq!(copy.header().num_records, 86);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn write_copy_to<W: Write + Seek>(
+        &mut self,
+        writer: &mut TableWriter<W>,
+    ) -> Result<u32, Error> {
+        check_no_memo_fields(&writer.fields_info)?;
+        let mut num_copied = 0u32;
+        for record in self.iter_records() {
+            writer.write_record(&record?)?;
+            num_copied += 1;
+        }
+        Ok(num_copied)
+    }
+
+    /// Returns an iterator over the [Records](struct.Record.html) for which `predicate` returns true
+    ///
+    /// Records are read one by one, so records that do not match
+    /// are dropped as soon as they are read.
+    /// Errors are returned without calling `predicate`.
+    ///
+    /// See [Reader::filter_with] to filter with a [RecordFilter](crate::RecordFilter).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let mut reader = dbase::Reader::from_path("tests/data/stations.dbf")?;
+    /// let red_line = reader
+    ///     .filter(|record| {
+    ///         record.get("line") == Some(&dbase::FieldValue::Character(Some("red".to_string())))
+    ///     })
+    ///     .collect::<Result<Vec<_>, _>>()?;
+    /// assert!(!red_line.is_empty());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn filter<'a, F>(
+        &'a mut self,
+        predicate: F,
+    ) -> impl Iterator<Item = Result<Record, Error>> + 'a
+    where
+        F: Fn(&Record) -> bool + 'a,
+    {
+        self.iter_records().filter(move |record| match record {
+            Ok(record) => predicate(record),
+            Err(_) => true,
+        })
+    }
+
+    /// Returns an iterator over the [Records](struct.Record.html) that stops
+    /// at the first record for which `predicate` returns false
+    ///
+    /// The records after it are not read, which is useful on files sorted
+    /// by a field when only the first records are needed.
+    /// Errors are returned without calling `predicate`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let mut reader = dbase::Reader::from_path("tests/data/stations.dbf")?;
+    /// let blue_line = reader
+    ///     .take_while(|record| {
+    ///         record.get("line") == Some(&dbase::FieldValue::Character(Some("blue".to_string())))
+    ///     })
+    ///     .collect::<Result<Vec<_>, _>>()?;
+    /// // The fourth station is on the red line
+    /// assert_eq!(blue_line.len(), 3);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn take_while<'a, F>(
+        &'a mut self,
+        predicate: F,
+    ) -> impl Iterator<Item = Result<Record, Error>> + 'a
+    where
+        F: Fn(&Record) -> bool + 'a,
+    {
+        self.iter_records().take_while(move |record| match record {
+            Ok(record) => predicate(record),
+            Err(_) => true,
+        })
+    }
+
+    /// Reads all the records and folds them into an accumulator, like [Iterator::fold]
+    ///
+    /// Stops at the first record that cannot be read and returns its error.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let mut reader = dbase::Reader::from_path("tests/data/stations.dbf")?;
+    /// let num_red = reader.fold(0, |count, record| {
+    ///     match record.get("line") {
+    ///         Some(dbase::FieldValue::Character(Some(line))) if line == "red" => count + 1,
+    ///         _ => count,
+    ///     }
+    /// })?;
+    /// assert_eq!(num_red, 27);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn fold<B, F>(&mut self, init: B, mut f: F) -> Result<B, Error>
+    where
+        F: FnMut(B, Record) -> B,
+    {
+        self.try_fold(init, |accumulator, record| {
+            Ok::<B, Error>(f(accumulator, record))
+        })
+    }
+
+    /// Reads all the records and folds them with a fallible function, like [Iterator::try_fold]
+    ///
+    /// Stops at the first error, either returned by `f`
+    /// or converted from the error of a record that cannot be read.
+    pub fn try_fold<B, E, F>(&mut self, init: B, mut f: F) -> Result<B, E>
+    where
+        E: From<Error>,
+        F: FnMut(B, Record) -> Result<B, E>,
+    {
+        let mut accumulator = init;
+        for record in self.iter_records() {
+            accumulator = f(accumulator, record?)?;
+        }
+        Ok(accumulator)
+    }
+
+    /// Reads all the records and returns the values of the field named `field_name`
+    ///
+    /// The name is matched case-insensitively, as dBase field names are usually
+    /// uppercase, see [Self::column_values_exact] to match it exactly.
+    /// Fails with [ErrorKind::UnknownFieldName] if there is no such field.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let mut reader = dbase::Reader::from_path("tests/data/stations.dbf")?;
+    /// let lines = reader.column_values("LINE")?;
+    /// assert_eq!(lines.len(), 86);
+    /// assert_eq!(lines[0], dbase::FieldValue::Character(Some("blue".to_string())));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn column_values(&mut self, field_name: &str) -> Result<Vec<FieldValue>, Error> {
+        let name = self
+            .fields_info
+            .iter()
+            .find(|info| info.name().eq_ignore_ascii_case(field_name))
+            .map(|info| info.name().to_string());
+        self.read_column(name, field_name)
+    }
+
+    /// Same as [Self::column_values], but the name of the field must match exactly
+    pub fn column_values_exact(&mut self, field_name: &str) -> Result<Vec<FieldValue>, Error> {
+        let name = self
+            .fields_info
+            .iter()
+            .find(|info| info.name() == field_name)
+            .map(|info| info.name().to_string());
+        self.read_column(name, field_name)
+    }
+
+    fn read_column(
+        &mut self,
+        name: Option<String>,
+        field_name: &str,
+    ) -> Result<Vec<FieldValue>, Error> {
+        let name = name.ok_or_else(|| Error {
+            record_num: 0,
+            field: None,
+            kind: ErrorKind::UnknownFieldName(field_name.to_string()),
+        })?;
+        self.iter_records()
+            .map(|record| {
+                let mut record = record?;
+                Ok(record.remove(&name).unwrap_or(FieldValue::Character(None)))
+            })
+            .collect()
+    }
+
+    /// Returns an iterator over the [Records](struct.Record.html) grouped in batches
+    ///
+    /// Each batch has `batch_size` records, except the last one which may have fewer.
+    /// Records are read into a buffer that is reused for all the batches.
+    ///
+    /// When reading a record fails, the error is returned and the records
+    /// read before it are returned in the next batch.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `batch_size` is 0
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let mut reader = dbase::Reader::from_path("tests/data/stations.dbf")?;
+    /// for batch in reader.batch(4) {
+    ///     let batch = batch?;
+    ///     assert!(!batch.is_empty() && batch.len() <= 4);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn batch(
+        &mut self,
+        batch_size: usize,
+    ) -> impl Iterator<Item = Result<Vec<Record>, Error>> + '_ {
+        assert!(batch_size > 0, "batch_size must be greater than 0");
+        let mut records = self.iter_records();
+        let mut buffer = Vec::with_capacity(batch_size);
+        std::iter::from_fn(move || {
+            while buffer.len() < batch_size {
+                match records.next() {
+                    Some(Ok(record)) => buffer.push(record),
+                    Some(Err(error)) => return Some(Err(error)),
+                    None => break,
+                }
+            }
+            if buffer.is_empty() {
+                return None;
+            }
+            // drain empties the buffer but keeps its capacity for the next batch,
+            // unlike the mem::take suggested by clippy
+            #[allow(clippy::drain_collect)]
+            let batch = buffer.drain(..).collect();
+            Some(Ok(batch))
+        })
+    }
+
+    /// Returns an iterator over the [Records](struct.Record.html) that match the `filter`
+    ///
+    /// This is the same as [Reader::filter], with the predicate given as data.
+    pub fn filter_with(
+        &mut self,
+        filter: crate::RecordFilter,
+    ) -> impl Iterator<Item = Result<Record, Error>> + '_ {
+        self.filter(move |record| filter.matches(record))
+    }
+
+    /// Reads all the records of the file inside a `Vec`
+    pub fn read_as<R: ReadableRecord>(&mut self) -> Result<Vec<R>, Error> {
+        // We don't read the file terminator
+        self.iter_records_as::<R>()
+            .collect::<Result<Vec<R>, Error>>()
+    }
+
+    /// Make the `Reader` read the [Records](struct.Record.html)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let mut reader = dbase::Reader::from_path("tests/data/line.dbf")?;
+    /// let records = reader.read()?;
+    /// assert_eq!(records.len(), 1);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn read(&mut self) -> Result<Vec<Record>, Error> {
+        // We don't read the file terminator
+        self.iter_records().collect::<Result<Vec<Record>, Error>>()
+    }
+
+    /// Reads the values of the field named `name` in all the records
+    ///
+    /// The field is found as by [TableInfo::index_of], so the case of `name` may differ.
+    /// Null values (e.g. Character or Numeric fields only made of pad bytes)
+    /// are returned as `None`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let mut reader = dbase::Reader::from_path("tests/data/stations.dbf")?;
+    /// let names = reader.collect_column::<String>("name")?;
+    /// assert_eq!(names[0].as_deref(), Some("Van Dorn Street"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn collect_column<F>(&mut self, name: &str) -> Result<Vec<Option<F>>, Error>
+    where
+        F: TryFrom<FieldValue, Error = FieldConversionError>,
+    {
+        let table_info =
+            TableInfo::new(self.header, self.fields_info.clone(), self.encoding.clone());
+        let field_info = table_info
+            .field_by_name(name)
+            .cloned()
+            .ok_or_else(|| Error {
+                record_num: 0,
+                field: None,
+                kind: ErrorKind::UnknownFieldName(name.to_string()),
+            })?;
+
+        self.iter_records()
+            .enumerate()
+            .map(|(record_num, record)| {
+                let value = record?.remove(field_info.name()).ok_or_else(|| Error {
+                    record_num,
+                    field: Some(field_info.clone()),
+                    kind: ErrorKind::UnknownFieldName(name.to_string()),
+                })?;
+                match F::try_from(value) {
+                    Ok(v) => Ok(Some(v)),
+                    Err(FieldConversionError::NoneValue) => Ok(None),
+                    Err(error) => Err(Error {
+                        record_num,
+                        field: Some(field_info.clone()),
+                        kind: ErrorKind::BadConversion(error),
+                    }),
+                }
+            })
+            .collect()
+    }
+
+    /// Moves the reader to the record at index `n`, without reading the records before it
+    ///
+    /// The position is computed from the offset of the first record and the size
+    /// of the records given by the header. The record iterators created afterwards
+    /// start at this record, so `skip(n)` followed by [Self::iter_records]
+    /// reads the records from index `n` to the end.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let mut reader = dbase::Reader::from_path("tests/data/stations.dbf")?;
+    /// reader.skip(80)?;
+    /// let records = reader.read()?;
+    /// assert_eq!(records.len(), 86 - 80);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn skip(&mut self, n: u32) -> Result<(), Error> {
+        let offset = u64::from(self.header.offset_to_first_record)
+            + u64::from(n) * u64::from(self.header.size_of_record);
+        self.source
+            .seek(SeekFrom::Start(offset))
+            .map_err(|error| Error::io_error(error, n as usize))?;
+        Ok(())
+    }
+
+    /// Returns the index of the record at the current position of the source,
+    /// 0 if the position is not the start of a record
+    fn current_record_index(&mut self) -> u32 {
+        let first_record = u64::from(self.header.offset_to_first_record);
+        let record_size = u64::from(self.header.size_of_record);
+        match self.source.stream_position() {
+            Ok(position) if position >= first_record && record_size != 0 => {
+                let index = (position - first_record) / record_size;
+                if first_record + index * record_size == position {
+                    u32::try_from(index).unwrap_or(u32::MAX)
+                } else {
+                    0
+                }
+            }
+            _ => 0,
+        }
+    }
+
+    /// Seek to the start of the record at `index`
+    pub fn seek(&mut self, index: usize) -> Result<(), Error> {
+        let offset = self.header.offset_to_first_record as usize
+            + (index * self.header.size_of_record as usize);
+        self.source
+            .seek(SeekFrom::Start(offset as u64))
+            .map_err(|err| Error::io_error(err, 0))?;
+        Ok(())
+    }
+
+    /// Reads the record at `index` as the type you want
+    ///
+    /// Returns `None` if there is no record at `index`, or if it is marked as deleted.
+    ///
+    /// The reader is left positioned after the record.
+    pub fn read_record_as<R: ReadableRecord>(&mut self, index: usize) -> Result<Option<R>, Error> {
+        if index >= self.num_records_to_read() as usize {
+            return Ok(None);
+        }
+        self.skip(index as u32)?;
+
+        let mut record_data = vec![0u8; computed_record_size(&self.fields_info)];
+        if !self.read_record_data(&mut record_data, index)? {
+            return Ok(None);
+        }
+        if DeletionFlag::from_byte(record_data[0]) == DeletionFlag::Deleted {
+            return Ok(None);
+        }
+        self.decode_record(&record_data, index).map(Some)
+    }
+
+    fn decode_record<R: ReadableRecord>(&mut self, bytes: &[u8], index: usize) -> Result<R, Error> {
+        decode_record(
+            bytes,
+            &self.fields_info,
+            &mut self.memo_reader,
+            &self.encoding,
+            Dialect::from(self.header.file_type),
+            self.options,
+        )
+        .map_err(|error| Error::new(error, index))
+    }
+
+    /// Returns the number of records given by the header,
+    /// or `u32::MAX` if the [options](ReadingOptions::ignore_num_records) say to ignore it
+    fn num_records_to_read(&self) -> u32 {
+        if self.options.ignore_num_records {
+            u32::MAX
+        } else {
+            self.header.num_records
+        }
+    }
+
+    /// Reads the data of a record (deletion flag included) from the current position
+    ///
+    /// When the file ends inside the record, [ErrorKind::TruncatedRecord] is returned,
+    /// unless the reader is [lenient](ReadingOptions::lenient): the fields that were not
+    /// completely read are then zeroed. Returns false if the record is completely missing.
+    fn read_record_data(&mut self, buffer: &mut [u8], index: usize) -> Result<bool, Error> {
+        let mut num_read = 0;
+        while num_read < buffer.len() {
+            match self.source.read(&mut buffer[num_read..]) {
+                Ok(0) => break,
+                Ok(n) => num_read += n,
+                Err(error) if error.kind() == std::io::ErrorKind::Interrupted => {}
+                Err(error) => return Err(Error::io_error(error, index)),
+            }
+        }
+        complete_record_data(buffer, num_read, &self.fields_info, self.options, index)
+    }
+
+    /// Reads the [Record](struct.Record.html) at `index`
+    ///
+    /// Returns `None` if there is no record at `index`, or if it is marked as deleted.
+    ///
+    /// The reader is left positioned after the record.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let mut reader = dbase::Reader::from_path("tests/data/stations_with_deleted.dbf")?;
+    /// // This record is marked as deleted
+    /// assert!(reader.read_record(0)?.is_none());
+    /// assert!(reader.read_record(1)?.is_some());
+    /// assert!(reader.read_record(100)?.is_none());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn read_record(&mut self, index: usize) -> Result<Option<Record>, Error> {
+        self.read_record_as::<Record>(index)
+    }
+
+    /// Reads the bytes of the record at `index` as they are in the file, without decoding them
+    ///
+    /// The `size_of_record` bytes of the record are returned, deletion flag included,
+    /// whether the record is deleted or not. They can be decoded afterwards
+    /// with [Self::parse_record_from_bytes].
+    ///
+    /// Fails with [ErrorKind::RecordIndexOutOfRange] if there is no record at `index`
+    /// and with [ErrorKind::TruncatedRecord] if the file ends inside the record.
+    /// The reader is left positioned after the record.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let mut reader = dbase::Reader::from_path("tests/data/stations.dbf")?;
+    /// let bytes = reader.read_record_raw(0)?;
+    /// assert_eq!(bytes.len(), reader.header().size_of_record as usize);
+    /// assert!(bytes[1..].starts_with(b"Van Dorn Street"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn read_record_raw(&mut self, index: u32) -> Result<Vec<u8>, Error> {
+        let num_records = self.num_records_to_read();
+        if index >= num_records {
+            return Err(Error {
+                record_num: index as usize,
+                field: None,
+                kind: ErrorKind::RecordIndexOutOfRange {
+                    index: index as usize,
+                    num_records,
+                },
+            });
+        }
+        self.skip(index)?;
+
+        let mut bytes = vec![0u8; self.header.size_of_record as usize];
+        self.source.read_exact(&mut bytes).map_err(|error| {
+            if error.kind() == std::io::ErrorKind::UnexpectedEof {
+                Error {
+                    record_num: index as usize,
+                    field: None,
+                    kind: ErrorKind::TruncatedRecord {
+                        index: index as usize,
+                    },
+                }
+            } else {
+                Error::io_error(error, index as usize)
+            }
+        })?;
+        Ok(bytes)
+    }
+
+    /// Decodes the bytes of a record, deletion flag included, as the type you want
+    ///
+    /// The bytes are decoded with the fields, encoding, options and memo file of the reader,
+    /// as they would be when reading the record. The deletion flag is not looked at,
+    /// so deleted records are decoded as well.
+    ///
+    /// Fails with an [ErrorKind::IoError] if there are less bytes than in a record.
+    pub fn parse_record_from_bytes_as<R: ReadableRecord>(
+        &mut self,
+        bytes: &[u8],
+    ) -> Result<R, Error> {
+        if bytes.len() < computed_record_size(&self.fields_info) {
+            return Err(Error::io_error(std::io::ErrorKind::UnexpectedEof.into(), 0));
+        }
+        self.decode_record(bytes, 0)
     }
 
-    /// Returns the fields contained in the opened file
-    pub fn fields(&self) -> &[FieldInfo] {
-        &self.fields_info
+    /// Decodes the bytes of a record, deletion flag included, as a [Record]
+    ///
+    /// See [Self::parse_record_from_bytes_as].
+    pub fn parse_record_from_bytes(&mut self, bytes: &[u8]) -> Result<Record, Error> {
+        self.parse_record_from_bytes_as::<Record>(bytes)
     }
 
-    /// Creates an iterator of records of the type you want
-    pub fn iter_records_as<R: ReadableRecord>(&mut self) -> RecordIterator<T, R> {
-        let record_size: usize = self
-            .fields_info
-            .iter()
-            .map(|i| i.field_length as usize)
-            .sum();
-        RecordIterator {
-            reader: self,
-            record_type: std::marker::PhantomData,
-            current_record: 0,
-            record_data_buffer: std::io::Cursor::new(vec![0u8; record_size]),
-            field_data_buffer: [0u8; 255],
+    /// Counts the records of the file, returns `(live, deleted)`
+    ///
+    /// Only the deletion flag of each record is read, the fields are skipped,
+    /// which makes this much faster than reading the records.
+    ///
+    /// The position of the reader is restored afterwards.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let mut reader = dbase::Reader::from_path("tests/data/stations_with_deleted.dbf")?;
+    /// let (live, deleted) = reader.count_records()?;
+    /// assert_eq!(live, 5);
+    /// assert_eq!(deleted, 1);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn count_records(&mut self) -> Result<(u32, u32), Error> {
+        let current_pos = self
+            .source
+            .stream_position()
+            .map_err(|error| Error::io_error(error, 0))?;
+        self.seek(0)?;
+
+        let fields_size = self.header.size_of_record as i64 - DELETION_FLAG_SIZE as i64;
+        let mut live = 0u32;
+        let mut deleted = 0u32;
+        for record_num in 0..self.header.num_records {
+            let deletion_flag = DeletionFlag::read_from(&mut self.source)
+                .map_err(|error| Error::io_error(error, record_num as usize))?;
+            match deletion_flag {
+                DeletionFlag::NotDeleted => live += 1,
+                DeletionFlag::Deleted => deleted += 1,
+            }
+            self.source
+                .seek(SeekFrom::Current(fields_size))
+                .map_err(|error| Error::io_error(error, record_num as usize))?;
         }
-    }
 
-    /// Shortcut function to get an iterator over the [Records](struct.Record.html) in the file
-    pub fn iter_records(&mut self) -> RecordIterator<T, Record> {
-        self.iter_records_as::<Record>()
+        self.source
+            .seek(SeekFrom::Start(current_pos))
+            .map_err(|error| Error::io_error(error, 0))?;
+        Ok((live, deleted))
     }
 
-    /// Reads all the records of the file inside a `Vec`
-    pub fn read_as<R: ReadableRecord>(&mut self) -> Result<Vec<R>, Error> {
-        // We don't read the file terminator
-        self.iter_records_as::<R>()
-            .collect::<Result<Vec<R>, Error>>()
+    /// Returns a human readable description of the layout of the file
+    ///
+    /// The description lists the version, date of last update, number of records,
+    /// offset of the first record and size of the records found in the header,
+    /// then, for each field, its name, type, offset within the record, length,
+    /// number of decimal places and its value in the first record.
+    ///
+    /// The position of the reader is restored afterwards.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let mut reader = dbase::Reader::from_path("tests/data/stations.dbf")?;
+    /// let description = reader.explain();
+    /// assert!(description.contains("Number of records: 86"));
+    /// assert!(description.contains("Van Dorn Street"));
+    /// println!("{}", description);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn explain(&mut self) -> String {
+        use std::fmt::Write;
+
+        let first_record = match self.source.stream_position() {
+            Ok(position) => {
+                let record = self.read_record(0);
+                self.source
+                    .seek(SeekFrom::Start(position))
+                    .map_err(|error| Error::io_error(error, 0))
+                    .and(record)
+            }
+            Err(error) => Err(Error::io_error(error, 0)),
+        };
+
+        let date = self.header.last_update;
+        let mut description = String::new();
+        // Writing to a String cannot fail
+        let _ = writeln!(description, "Version: {:?}", self.header.file_type);
+        let _ = writeln!(
+            description,
+            "Last update: {:04}-{:02}-{:02}",
+            date.year(),
+            date.month(),
+            date.day()
+        );
+        let _ = writeln!(
+            description,
+            "Number of records: {}",
+            self.header.num_records
+        );
+        let _ = writeln!(
+            description,
+            "Offset to first record: {}",
+            self.header.offset_to_first_record
+        );
+        let _ = writeln!(description, "Record size: {}", self.header.size_of_record);
+        match &first_record {
+            Ok(Some(_)) => {}
+            Ok(None) => {
+                let _ = writeln!(description, "First record: missing or deleted");
+            }
+            Err(error) => {
+                let _ = writeln!(description, "First record: {}", error);
+            }
+        }
+
+        let _ = writeln!(
+            description,
+            "{:<11} {:<4} {:>6} {:>6} {:>8}  First value",
+            "Field", "Type", "Offset", "Length", "Decimals"
+        );
+        let mut offset = DELETION_FLAG_SIZE;
+        for field_info in &self.fields_info {
+            let example = match &first_record {
+                Ok(Some(record)) => record
+                    .get(&field_info.name)
+                    .map(|value| value.as_display().to_string())
+                    .unwrap_or_default(),
+                _ => String::new(),
+            };
+            let _ = writeln!(
+                description,
+                "{:<11} {:<4} {:>6} {:>6} {:>8}  {}",
+                field_info.name,
+                u8::from(field_info.field_type) as char,
+                offset,
+                field_info.record_length(),
+                field_info.num_decimal_places,
+                example
+            );
+            offset += field_info.record_length();
+        }
+        description
     }
 
-    /// Make the `Reader` read the [Records](struct.Record.html)
+    /// Reads a random sample of at most `n` (non-deleted) records
     ///
-    /// # Examples
+    /// The records are chosen using reservoir sampling, with a pseudo random
+    /// generator initialized with `seed`, so the same seed always gives the same sample
+    /// for the same file. The records are returned in the order they appear in the file.
+    ///
+    /// Only the deletion flags are read to choose the records, then the chosen records
+    /// are read by seeking to them: this takes O(num_records) time and O(n) space.
+    ///
+    /// The position of the reader is restored afterwards.
+    ///
+    /// # Example
     ///
     /// ```
-    /// use std::fs::File;
     /// # fn main() -> Result<(), dbase::Error> {
-    /// let mut reader = dbase::Reader::from_path("tests/data/line.dbf")?;
-    /// let records = reader.read()?;
-    /// assert_eq!(records.len(), 1);
+    /// let mut reader = dbase::Reader::from_path("tests/data/stations.dbf")?;
+    /// let sample = reader.sample(10, 42)?;
+    /// assert_eq!(sample.len(), 10);
+    /// assert_eq!(sample, reader.sample(10, 42)?);
     /// # Ok(())
     /// # }
     /// ```
-    pub fn read(&mut self) -> Result<Vec<Record>, Error> {
-        // We don't read the file terminator
-        self.iter_records().collect::<Result<Vec<Record>, Error>>()
-    }
+    pub fn sample(&mut self, n: u32, seed: u64) -> Result<Vec<Record>, Error> {
+        let current_pos = self
+            .source
+            .stream_position()
+            .map_err(|error| Error::io_error(error, 0))?;
+        self.skip(0)?;
+
+        let mut rng_state = seed;
+        let fields_size = self.header.size_of_record as i64 - DELETION_FLAG_SIZE as i64;
+        let mut reservoir = Vec::<u32>::with_capacity(n.min(self.header.num_records) as usize);
+        let mut num_live = 0u64;
+        for record_num in 0..self.header.num_records {
+            let deletion_flag = DeletionFlag::read_from(&mut self.source)
+                .map_err(|error| Error::io_error(error, record_num as usize))?;
+            self.source
+                .seek(SeekFrom::Current(fields_size))
+                .map_err(|error| Error::io_error(error, record_num as usize))?;
+            if deletion_flag == DeletionFlag::Deleted {
+                continue;
+            }
+
+            if reservoir.len() < n as usize {
+                reservoir.push(record_num);
+            } else {
+                // Random index in 0..=num_live
+                let j =
+                    ((splitmix64(&mut rng_state) as u128 * (num_live as u128 + 1)) >> 64) as usize;
+                if j < reservoir.len() {
+                    reservoir[j] = record_num;
+                }
+            }
+            num_live += 1;
+        }
+        reservoir.sort_unstable();
+
+        let mut records = Vec::with_capacity(reservoir.len());
+        for record_num in reservoir {
+            let record = self
+                .read_record(record_num as usize)?
+                .expect("Internal error: the record should not be deleted");
+            records.push(record);
+        }
 
-    /// Seek to the start of the record at `index`
-    pub fn seek(&mut self, index: usize) -> Result<(), Error> {
-        let offset = self.header.offset_to_first_record as usize
-            + (index * self.header.size_of_record as usize);
         self.source
-            .seek(SeekFrom::Start(offset as u64))
-            .map_err(|err| Error::io_error(err, 0))?;
-        Ok(())
+            .seek(SeekFrom::Start(current_pos))
+            .map_err(|error| Error::io_error(error, 0))?;
+        Ok(records)
     }
 
     /// Consumes the reader, and returns the info that
@@ -316,14 +1732,11 @@ impl<T: Read + Seek> Reader<T> {
     ///
     /// ```
     pub fn into_table_info(self) -> TableInfo {
-        TableInfo {
-            header: self.header,
-            fields_info: self.fields_info,
-            encoding: self.encoding,
-        }
+        TableInfo::new(self.header, self.fields_info, self.encoding)
     }
 }
 
+#[cfg(not(target_arch = "wasm32"))]
 impl Reader<BufReader<File>> {
     /// Creates a new dbase Reader from a path
     ///
@@ -343,15 +1756,12 @@ impl Reader<BufReader<File>> {
         let at_least_one_field_is_memo = reader
             .fields_info
             .iter()
-            .any(|f_info| f_info.field_type == FieldType::Memo);
+            .any(|f_info| matches!(f_info.field_type, FieldType::Memo | FieldType::Picture));
 
         if at_least_one_field_is_memo {
             let memo_type = reader.header.file_type.supported_memo_type();
             if let Some(mt) = memo_type {
-                let memo_path = match mt {
-                    MemoFileType::DbaseMemo | MemoFileType::DbaseMemo4 => p.with_extension("dbt"),
-                    MemoFileType::FoxBaseMemo => p.with_extension("fpt"),
-                };
+                let memo_path = p.with_extension(mt.extension());
 
                 let memo_file = File::open(memo_path).map_err(|error| Error {
                     record_num: 0,
@@ -364,6 +1774,23 @@ impl Reader<BufReader<File>> {
                 reader.memo_reader = Some(memo_reader);
             }
         }
+
+        if let Some(database_path) = reader.database_container_path()? {
+            let database_path = p.with_file_name(database_path);
+            if database_path.is_file() {
+                let database = DatabaseContainer::from_path(database_path)?;
+                if let Some(table_name) = p.file_stem().and_then(|stem| stem.to_str()) {
+                    reader.set_long_field_names(&database, table_name);
+                }
+            }
+        }
+
+        if reader.has_structural_index() {
+            let index_path = p.with_extension("cdx");
+            if index_path.is_file() {
+                reader.structural_index = Some(IndexInfo { path: index_path });
+            }
+        }
         Ok(reader)
     }
 
@@ -378,6 +1805,46 @@ impl Reader<BufReader<File>> {
     }
 }
 
+impl<'a> Reader<Cursor<&'a [u8]>> {
+    /// Creates a new dbase Reader over the bytes of a .dbf file held in memory
+    ///
+    /// As there is no file next to it, memo fields cannot be read.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let bytes = std::fs::read("tests/data/line.dbf")?;
+    /// let mut reader = dbase::Reader::from_bytes(&bytes)?;
+    /// assert_eq!(reader.read()?.len(), 1);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_bytes(bytes: &'a [u8]) -> Result<Self, Error> {
+        Reader::new(Cursor::new(bytes))
+    }
+}
+
+impl Reader<Cursor<Vec<u8>>> {
+    /// Creates a new dbase Reader that owns the bytes of a .dbf file
+    ///
+    /// As there is no file next to it, memo fields cannot be read.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let bytes = std::fs::read("tests/data/line.dbf")?;
+    /// let mut reader = dbase::Reader::from_vec(bytes)?;
+    /// assert_eq!(reader.read()?.len(), 1);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_vec(bytes: Vec<u8>) -> Result<Self, Error> {
+        Reader::new(Cursor::new(bytes))
+    }
+}
+
 /// Simple struct to wrap together the value with the name
 /// of the field it belongs to
 pub struct NamedValue<'a, T> {
@@ -401,9 +1868,11 @@ pub struct FieldIterator<'a, Source: Read + Seek, MemoSource: Read + Seek> {
     /// The source where the Memo field data is read
     pub(crate) memo_reader: &'a mut Option<MemoReader<MemoSource>>,
     /// Buffer where field data is stored
-    pub(crate) field_data_buffer: &'a mut [u8; 255],
+    pub(crate) field_data_buffer: &'a mut [u8],
     /// The string encoding
     pub(crate) encoding: &'a DynEncoding,
+    /// How the values are stored, given by the version of the file
+    pub(crate) dialect: Dialect,
     pub(crate) options: ReadingOptions,
 }
 
@@ -442,6 +1911,18 @@ impl<'a, Source: Read + Seek, MemoSource: Read + Seek> FieldIterator<'a, Source,
             })
     }
 
+    /// Returns the name of the next field to read, `None` at the end of the record
+    pub fn next_field_name(&mut self) -> Option<&'a str> {
+        self.fields_info.peek().map(|info| info.name())
+    }
+
+    /// Returns true if the next field to read is the one of the struct field `field_name`,
+    /// the names are compared like [FieldWriter::next_field_matches](crate::FieldWriter::next_field_matches) does
+    pub fn next_field_matches(&mut self, field_name: &str) -> bool {
+        self.next_field_name()
+            .is_some_and(|name| field_name_matches(name, field_name))
+    }
+
     /// Skips the next field of the record, useful if the field does not interest you
     /// but the ones after do.
     ///
@@ -473,7 +1954,7 @@ impl<'a, Source: Read + Seek, MemoSource: Read + Seek> FieldIterator<'a, Source,
             .fields_info
             .next()
             .ok_or(FieldIOError::end_of_record())?;
-        let mut buf = vec![0u8; field_info.field_length as usize];
+        let mut buf = vec![0u8; field_info.record_length()];
         self.source.read_exact(&mut buf).map_err(|error| {
             FieldIOError::new(ErrorKind::IoError(error), Some(field_info.to_owned()))
         })?;
@@ -488,7 +1969,7 @@ impl<'a, Source: Read + Seek, MemoSource: Read + Seek> FieldIterator<'a, Source,
         })?;
         let value = self.read_field(field_info)?;
         self.source
-            .seek(SeekFrom::Current(-i64::from(field_info.field_length)))
+            .seek(SeekFrom::Current(-(field_info.record_length() as i64)))
             .map_err(|error| {
                 FieldIOError::new(ErrorKind::IoError(error), Some(field_info.to_owned()))
             })?;
@@ -502,7 +1983,7 @@ impl<'a, Source: Read + Seek, MemoSource: Read + Seek> FieldIterator<'a, Source,
     /// Advance the source to skip the field
     fn skip_field(&mut self, field_info: &FieldInfo) -> Result<(), FieldIOError> {
         self.source
-            .seek(SeekFrom::Current(i64::from(field_info.field_length)))
+            .seek(SeekFrom::Current(field_info.record_length() as i64))
             .map_err(|error| {
                 FieldIOError::new(ErrorKind::IoError(error), Some(field_info.to_owned()))
             })?;
@@ -511,14 +1992,23 @@ impl<'a, Source: Read + Seek, MemoSource: Read + Seek> FieldIterator<'a, Source,
 
     /// read the next field using the given info
     fn read_field(&mut self, field_info: &'a FieldInfo) -> Result<FieldValue, FieldIOError> {
-        let field_data_buffer = &mut self.field_data_buffer[..field_info.length() as usize];
+        let mut wide_field_data;
+        let field_data_buffer = match self.field_data_buffer.get_mut(..field_info.record_length()) {
+            Some(field_data_buffer) => field_data_buffer,
+            None => {
+                wide_field_data = vec![0u8; field_info.record_length()];
+                &mut wide_field_data[..]
+            }
+        };
         self.source.read_exact(field_data_buffer).unwrap();
         match FieldValue::read_from(
             field_data_buffer,
             self.memo_reader,
             field_info,
+            self.dialect,
             &*self.encoding,
             self.options.character_trim,
+            self.options.numeric_overflow,
         ) {
             Ok(value) => Ok(value),
             Err(kind) => Err(FieldIOError {
@@ -550,42 +2040,144 @@ impl<'a, Source: Read + Seek, MemoSource: Read + Seek> FusedIterator
 {
 }
 
+/// Checks the data of a record of which `num_read` bytes were read,
+/// see [Reader::read_record_data]
+///
+/// Shared by the readers, whatever the way they read the data.
+pub(crate) fn complete_record_data(
+    buffer: &mut [u8],
+    num_read: usize,
+    fields_info: &[FieldInfo],
+    options: ReadingOptions,
+    index: usize,
+) -> Result<bool, Error> {
+    if options.ignore_num_records && (num_read == 0 || buffer[0] == FILE_TERMINATOR) {
+        return Ok(false);
+    }
+    if num_read == buffer.len() {
+        return Ok(true);
+    }
+    if !options.lenient {
+        return Err(Error {
+            record_num: index,
+            field: None,
+            kind: ErrorKind::TruncatedRecord { index },
+        });
+    }
+    if num_read == 0 {
+        return Ok(false);
+    }
+
+    let mut complete_fields_end = DELETION_FLAG_SIZE;
+    for field_info in fields_info {
+        let field_end = complete_fields_end + field_info.record_length();
+        if field_end > num_read {
+            break;
+        }
+        complete_fields_end = field_end;
+    }
+    buffer[complete_fields_end..].fill(0);
+    Ok(true)
+}
+
+/// Decodes the bytes of a record, deletion flag included
+///
+/// Shared by the readers, whatever the way they read the bytes.
+pub(crate) fn decode_record<R: ReadableRecord, MemoSource: Read + Seek>(
+    bytes: &[u8],
+    fields_info: &[FieldInfo],
+    memo_reader: &mut Option<MemoReader<MemoSource>>,
+    encoding: &DynEncoding,
+    dialect: Dialect,
+    options: ReadingOptions,
+) -> Result<R, FieldIOError> {
+    let mut record_data = std::io::Cursor::new(bytes);
+    record_data.set_position(DELETION_FLAG_SIZE as u64);
+    let mut field_data_buffer = [0u8; 255];
+    let mut iter = FieldIterator {
+        source: &mut record_data,
+        fields_info: fields_info.iter().peekable(),
+        memo_reader,
+        field_data_buffer: &mut field_data_buffer,
+        encoding,
+        dialect,
+        options,
+    };
+
+    R::read_using(&mut iter).and_then(|record| iter.skip_remaining_fields().and(Ok(record)))
+}
+
 /// Iterator over records contained in the dBase
 pub struct RecordIterator<'a, T: Read + Seek, R: ReadableRecord> {
     reader: &'a mut Reader<T>,
     record_type: std::marker::PhantomData<R>,
     current_record: u32,
+    /// Number of records returned, to stop at the limit of the options
+    num_returned: usize,
+    /// Whether the records to skip, given by the options, were skipped
+    skipped: bool,
     record_data_buffer: std::io::Cursor<Vec<u8>>,
     /// Non-Memo field length is stored on a u8,
     /// so fields cannot exceed 255 bytes
     field_data_buffer: [u8; 255],
 }
 
-impl<'a, T: Read + Seek, R: ReadableRecord> Iterator for RecordIterator<'a, T, R> {
-    type Item = Result<R, Error>;
-
-    fn next(&mut self) -> Option<Self::Item> {
+impl<'a, T: Read + Seek, R: ReadableRecord> RecordIterator<'a, T, R> {
+    /// Reads the next record that is not deleted, decoding its fields with `read`
+    fn read_next_with<V, F>(&mut self, read: F) -> Option<Result<V, Error>>
+    where
+        F: FnOnce(&mut FieldIterator<std::io::Cursor<Vec<u8>>, T>) -> Result<V, FieldIOError>,
+    {
+        if self
+            .reader
+            .options
+            .limit
+            .is_some_and(|limit| self.num_returned >= limit)
+        {
+            return None;
+        }
+        let num_records = self.reader.num_records_to_read();
+        if !self.skipped {
+            self.skipped = true;
+            if let Some(skip) = self.reader.options.skip {
+                if skip >= num_records as usize {
+                    self.current_record = num_records;
+                    return None;
+                }
+                if let Err(error) = self.reader.seek(skip) {
+                    self.current_record = num_records;
+                    return Some(Err(error));
+                }
+                self.current_record = skip as u32;
+            }
+        }
         loop {
-            if self.current_record >= self.reader.header.num_records {
+            if self.current_record >= num_records {
                 return None;
             } else {
-                let deletion_flag = DeletionFlag::read_from(&mut self.reader.source).ok()?;
+                // Read the whole record (deletion flag included) at once,
+                // fields are then decoded from memory
+                let index = self.current_record as usize;
+                match self
+                    .reader
+                    .read_record_data(self.record_data_buffer.get_mut(), index)
+                {
+                    Ok(true) => {}
+                    Ok(false) => return None,
+                    Err(error) => {
+                        // Do not try to read the following records
+                        self.current_record = num_records;
+                        return Some(Err(error));
+                    }
+                }
 
+                let deletion_flag = DeletionFlag::from_byte(self.record_data_buffer.get_ref()[0]);
                 if deletion_flag == DeletionFlag::Deleted {
-                    self.reader
-                        .source
-                        .seek(SeekFrom::Current(
-                            self.record_data_buffer.get_ref().len() as i64
-                        ))
-                        .ok()?;
+                    self.current_record += 1;
                     continue;
                 }
-
-                self.reader
-                    .source
-                    .read_exact(self.record_data_buffer.get_mut())
-                    .ok()?;
-                self.record_data_buffer.set_position(0);
+                self.record_data_buffer
+                    .set_position(DELETION_FLAG_SIZE as u64);
 
                 let mut iter = FieldIterator {
                     source: &mut self.record_data_buffer,
@@ -593,19 +2185,112 @@ impl<'a, T: Read + Seek, R: ReadableRecord> Iterator for RecordIterator<'a, T, R
                     memo_reader: &mut self.reader.memo_reader,
                     field_data_buffer: &mut self.field_data_buffer,
                     encoding: &self.reader.encoding,
+                    dialect: Dialect::from(self.reader.header.file_type),
                     options: self.reader.options,
                 };
 
-                let record = R::read_using(&mut iter)
+                let record = read(&mut iter)
                     .and_then(|record| iter.skip_remaining_fields().and(Ok(record)))
                     .map_err(|error| Error::new(error, self.current_record as usize));
                 self.current_record += 1;
+                self.num_returned += 1;
                 return Some(record);
             }
         }
     }
 }
 
+impl<'a, T: Read + Seek, R: ReadableRecord> Iterator for RecordIterator<'a, T, R> {
+    type Item = Result<R, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_next_with(R::read_using)
+    }
+
+    /// The upper bound is the number of records left to read, given by the header
+    /// and the options, the lower bound is 0, as deleted records are skipped
+    /// and the iteration stops at the first error
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let options = &self.reader.options;
+        let start = match options.skip {
+            Some(skip) if !self.skipped => skip,
+            _ => self.current_record as usize,
+        };
+        let num_left = if options.ignore_num_records {
+            None
+        } else {
+            Some((self.reader.header.num_records as usize).saturating_sub(start))
+        };
+        let num_left_in_limit = options
+            .limit
+            .map(|limit| limit.saturating_sub(self.num_returned));
+        let upper = match (num_left, num_left_in_limit) {
+            (Some(num_left), Some(limit)) => Some(num_left.min(limit)),
+            (num_left, limit) => num_left.or(limit),
+        };
+        (0, upper)
+    }
+}
+
+/// Iterator over the records of a file along with their bytes
+///
+/// Created by [Reader::iter_records_with_bytes_as].
+pub struct RecordBytesIterator<'a, T: Read + Seek, R: ReadableRecord> {
+    inner: RecordIterator<'a, T, R>,
+}
+
+impl<'a, T: Read + Seek, R: ReadableRecord> Iterator for RecordBytesIterator<'a, T, R> {
+    type Item = Result<(Vec<u8>, R), Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let record = self.inner.next()?;
+        // The buffer holds the data of the record that was just read
+        Some(record.map(|record| (self.inner.record_data_buffer.get_ref().clone(), record)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+/// Iterator over the records of a file, whose values are transformed
+/// by a function
+///
+/// Created by [Reader::map_fields].
+pub struct MappedReader<'a, T: Read + Seek, F> {
+    inner: RecordIterator<'a, T, Record>,
+    f: F,
+}
+
+impl<'a, T, F> Iterator for MappedReader<'a, T, F>
+where
+    T: Read + Seek,
+    F: Fn(&str, FieldValue) -> FieldValue,
+{
+    type Item = Result<Record, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let f = &self.f;
+        // Each value is mapped as soon as it is decoded, before going in the record
+        self.inner
+            .read_next_with(|field_iterator| Record::read_mapped(field_iterator, f))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+/// SplitMix64 pseudo random number generator, used to have reproducible samples
+/// without depending on an external crate
+fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
 /// One liner to read the content of a .dbf file
 ///
 /// # Example
@@ -614,11 +2299,124 @@ impl<'a, T: Read + Seek, R: ReadableRecord> Iterator for RecordIterator<'a, T, R
 /// let records = dbase::read("tests/data/line.dbf").unwrap();
 /// assert_eq!(records.len(), 1);
 /// ```
+#[cfg(not(target_arch = "wasm32"))]
 pub fn read<P: AsRef<Path>>(path: P) -> Result<Vec<Record>, Error> {
     let mut reader = Reader::from_path(path).unwrap();
     reader.read()
 }
 
+/// Reads only the 32 bytes of the header from `source`, without the field descriptors
+///
+/// Useful to look at the number of records, the date of the last update
+/// or the version of many files. See [Header::read_from].
+///
+/// # Example
+///
+/// ```
+/// # fn main() -> Result<(), dbase::Error> {
+/// let mut file = std::fs::File::open("tests/data/stations.dbf").unwrap();
+/// let header = dbase::read_header_only(&mut file)?;
+/// assert_eq!(header.num_records, 86);
+/// assert_eq!(header.file_type.major(), 3);
+/// # Ok(())
+/// # }
+/// ```
+pub fn read_header_only<T: Read>(source: &mut T) -> Result<Header, Error> {
+    Header::read_from(source)
+}
+
+/// Writes to `dst` a copy of the table of `reader`, where the strings
+/// of the Character fields are encoded with `encoding`
+///
+/// The strings are decoded with the encoding of the reader, and the code page mark
+/// of the new header is the one of `encoding`. The fields keep their length,
+/// so copying fails with [ErrorKind::StringEncodeError] if a string has
+/// characters that `encoding` cannot represent, and with [ErrorKind::InvalidFieldValue]
+/// if the encoded string does not fit in its field anymore,
+/// instead of writing a truncated string.
+///
+/// All the records are read, from the first one, deleted records are not copied.
+/// As memo fields cannot be written yet, the table cannot have any.
+/// Returns `dst` once the new table is completely written.
+///
+/// # Example
+///
+/// ```
+/// use std::io::Cursor;
+///
+/// # fn main() -> Result<(), dbase::Error> {
+/// let mut reader = dbase::Reader::from_path("tests/data/stations.dbf")?;
+/// let cursor = dbase::transcode(&mut reader, Cursor::new(Vec::<u8>::new()), dbase::Unicode)?;
+///
+/// let mut copy = dbase::Reader::new(Cursor::new(cursor.into_inner()))?;
+/// assert!(matches!(copy.header().code_page_mark, dbase::CodePageMark::Utf8));
+/// assert_eq!(copy.read()?.len(), 86);
+/// # Ok(())
+/// # }
+/// ```
+pub fn transcode<T, W, E>(reader: &mut Reader<T>, dst: W, encoding: E) -> Result<W, Error>
+where
+    T: Read + Seek,
+    W: Write + Seek,
+    E: Encoding + 'static,
+{
+    let encoding = DynEncoding::new(encoding);
+    let table_info = TableInfo::new(
+        *reader.header(),
+        reader.fields().to_vec(),
+        reader.encoding().clone(),
+    );
+    let mut writer = TableWriterBuilder::from_table_info(table_info)
+        .set_encoding(encoding.clone())
+        .build_with_dest(dst);
+    check_no_memo_fields(&writer.fields_info)?;
+
+    reader.seek(0)?;
+    for (index, record) in reader.iter_records().enumerate() {
+        let record = record?;
+        for field in writer
+            .fields_info
+            .iter()
+            .filter(|field| field.field_type == FieldType::Character)
+        {
+            let Some(FieldValue::Character(Some(text))) = record.get_exact(field.name()) else {
+                continue;
+            };
+            let field_error = |kind| Error {
+                record_num: index,
+                field: Some(field.clone()),
+                kind,
+            };
+            let encoded = encoding
+                .encode(text)
+                .map_err(|error| field_error(error.into()))?;
+            if encoded.len() > field.record_length() {
+                return Err(field_error(ErrorKind::InvalidFieldValue(text.clone())));
+            }
+        }
+        writer.write_record(&record)?;
+    }
+    writer.finish()
+}
+
+/// Memo fields cannot be written yet, so tables that have some cannot be copied
+fn check_no_memo_fields(fields_info: &[FieldInfo]) -> Result<(), Error> {
+    match fields_info
+        .iter()
+        .find(|field| matches!(field.field_type, FieldType::Memo | FieldType::Picture))
+    {
+        Some(field) => Err(Error {
+            record_num: 0,
+            field: Some(field.clone()),
+            kind: ErrorKind::Message(format!(
+                "The memo field '{}' cannot be written",
+                field.name()
+            )),
+        }),
+        None => Ok(()),
+    }
+}
+
 #[cfg(test)]
 mod test {
     use std::fs::File;
@@ -638,4 +2436,165 @@ mod test {
 
         assert_eq!(pos_after_reading, expected_pos as u64);
     }
+
+    #[test]
+    fn record_size_mismatch_is_detected() {
+        let mut bytes = std::fs::read("tests/data/line.dbf").unwrap();
+        // size_of_record is stored at bytes 10..12
+        bytes[10] += 1;
+
+        let error = Reader::new(std::io::Cursor::new(bytes)).err().unwrap();
+        assert!(matches!(
+            error.kind(),
+            ErrorKind::RecordSizeMismatch {
+                header: 52,
+                computed: 51
+            }
+        ));
+    }
+
+    #[test]
+    fn sample_is_reproducible() {
+        let mut reader = Reader::from_path("tests/data/stations.dbf").unwrap();
+        let num_records = reader.header().num_records as usize;
+
+        let first = reader.sample(10, 1234).unwrap();
+        let second = reader.sample(10, 1234).unwrap();
+        assert_eq!(first.len(), 10);
+        assert_eq!(first, second);
+        assert_ne!(first, reader.sample(10, 4321).unwrap());
+
+        let all = reader.sample(u32::MAX, 0).unwrap();
+        assert_eq!(all.len(), num_records);
+        assert_eq!(all, reader.read().unwrap());
+    }
+
+    #[test]
+    fn sample_skips_deleted_records() {
+        let mut reader = Reader::from_path("tests/data/stations_with_deleted.dbf").unwrap();
+        let all = reader.sample(100, 7).unwrap();
+        assert_eq!(all, reader.read().unwrap());
+    }
+
+    /// ISO 8859-1, whose bytes are the first 256 Unicode code points
+    #[derive(Copy, Clone, Debug)]
+    struct Latin1;
+
+    impl crate::encoding::AsCodePageMark for Latin1 {
+        fn code_page_mark(&self) -> crate::CodePageMark {
+            crate::CodePageMark::CP1252
+        }
+    }
+
+    impl Encoding for Latin1 {
+        fn decode<'a>(
+            &self,
+            bytes: &'a [u8],
+        ) -> Result<std::borrow::Cow<'a, str>, crate::error::DecodeError> {
+            Ok(bytes.iter().map(|b| char::from(*b)).collect())
+        }
+
+        fn encode<'a>(
+            &self,
+            s: &'a str,
+        ) -> Result<std::borrow::Cow<'a, [u8]>, crate::error::EncodeError> {
+            s.chars()
+                .map(u8::try_from)
+                .collect::<Result<Vec<u8>, _>>()
+                .map(std::borrow::Cow::Owned)
+                .map_err(|_| crate::error::EncodeError::Message("not latin 1".to_string()))
+        }
+    }
+
+    #[test]
+    fn write_copy_to_transcodes() {
+        use crate::{FieldName, TableWriterBuilder};
+        use std::io::Cursor;
+
+        let mut writer = TableWriterBuilder::with_encoding(Latin1)
+            .add_character_field(FieldName::try_from("name").unwrap(), 10)
+            .add_numeric_field(FieldName::try_from("count").unwrap(), 4, 0)
+            .build_with_dest(Cursor::new(Vec::<u8>::new()));
+        let mut records = vec![];
+        for (name, count) in [("Café", 1.0), ("Señor", 2.0)] {
+            let mut record = Record::default();
+            record.insert(
+                "name".to_string(),
+                FieldValue::Character(Some(name.to_string())),
+            );
+            record.insert("count".to_string(), FieldValue::Numeric(Some(count)));
+            writer.write_record(&record).unwrap();
+            records.push(record);
+        }
+        let latin1 = writer.finish().unwrap().into_inner();
+        assert!(latin1.windows(4).any(|bytes| bytes == b"Caf\xE9"));
+
+        let mut reader = Reader::new_with_encoding(Cursor::new(latin1), Latin1).unwrap();
+        let table_info = TableInfo::new(
+            reader.header,
+            reader.fields_info.clone(),
+            reader.encoding.clone(),
+        );
+        let mut writer = TableWriterBuilder::from_table_info(table_info)
+            .set_encoding(crate::Unicode)
+            .build_with_dest(Cursor::new(Vec::<u8>::new()));
+        assert_eq!(reader.write_copy_to(&mut writer).unwrap(), 2);
+        let utf8 = writer.finish().unwrap().into_inner();
+        assert!(utf8.windows(5).any(|bytes| bytes == "Café".as_bytes()));
+
+        let mut reader = Reader::new(Cursor::new(utf8)).unwrap();
+        assert!(matches!(
+            reader.header().code_page_mark,
+            crate::CodePageMark::Utf8
+        ));
+        assert_eq!(reader.read().unwrap(), records);
+    }
+
+    #[test]
+    fn write_copy_to_rejects_memo_fields() {
+        use crate::{FieldName, TableWriterBuilder};
+        use std::io::Cursor;
+
+        let mut reader = Reader::from_path("tests/data/line.dbf").unwrap();
+        let mut table_info = TableWriterBuilder::new()
+            .add_character_field(FieldName::try_from("name").unwrap(), 10)
+            .build_table_info();
+        table_info.fields_info.push(FieldInfo::new(
+            FieldName::try_from("note").unwrap(),
+            FieldType::Memo,
+            10,
+        ));
+        let mut writer = TableWriterBuilder::from_table_info(table_info)
+            .build_with_dest(Cursor::new(Vec::<u8>::new()));
+        let error = reader.write_copy_to(&mut writer).unwrap_err();
+        assert!(matches!(error.kind(), ErrorKind::Message(_)));
+        assert_eq!(
+            error.field().as_ref().map(|field| field.name()),
+            Some("note")
+        );
+    }
+
+    #[test]
+    fn index_of_field_names() {
+        use crate::{FieldName, TableWriterBuilder};
+
+        let mut table_info = TableWriterBuilder::new()
+            .add_character_field(FieldName::try_from("name").unwrap(), 10)
+            .add_integer_field(FieldName::try_from("count").unwrap())
+            .add_integer_field(FieldName::try_from("COUNT").unwrap())
+            .build_table_info();
+        assert_eq!(table_info.index_of("name"), Some(0));
+        assert_eq!(table_info.index_of("count"), Some(1));
+        assert_eq!(table_info.index_of("COUNT"), Some(2));
+        assert_eq!(table_info.index_of("Count"), Some(1));
+        assert_eq!(table_info.index_of("id"), None);
+        assert_eq!(
+            table_info.field_by_name("name").map(FieldInfo::field_type),
+            Some(FieldType::Character)
+        );
+
+        table_info.fields_mut()[0].set_name(FieldName::try_from("id").unwrap());
+        assert_eq!(table_info.index_of("name"), None);
+        assert_eq!(table_info.index_of("id"), Some(0));
+    }
 }
@@ -1,4 +1,6 @@
-use crate::{FieldIOError, FieldIterator, FieldValue, NamedValue, ReadableRecord};
+use crate::{
+    Error, ErrorKind, FieldIOError, FieldIterator, FieldValue, NamedValue, ReadableRecord,
+};
 use std::collections::hash_map::RandomState;
 use std::collections::HashMap;
 use std::io::{Read, Seek};
@@ -41,10 +43,102 @@ impl Record {
     }
 
     /// Returns the [FieldValue](enum.FieldValue.html) for the given field name
+    ///
+    /// The name is matched case-insensitively when the record has no field with exactly
+    /// this name, as dBase field names are usually uppercase.
+    /// See [Record::get_exact] to only match the exact name.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dbase::FieldValue;
+    ///
+    /// let mut record = dbase::Record::default();
+    /// record.insert("NAME".to_owned(), FieldValue::Character(Some("Yoshi".to_owned())));
+    /// assert_eq!(record.get("name"), record.get("NAME"));
+    /// assert_eq!(record.get_exact("name"), None);
+    /// ```
     pub fn get(&self, field_name: &str) -> Option<&FieldValue> {
+        self.map.get(field_name).or_else(|| {
+            self.map
+                .iter()
+                .find(|(name, _)| name.eq_ignore_ascii_case(field_name))
+                .map(|(_, value)| value)
+        })
+    }
+
+    /// Returns the [FieldValue](enum.FieldValue.html) for the given field name,
+    /// or an [ErrorKind::UnknownFieldName] error if the record has no such field
+    ///
+    /// The name is matched like [Record::get] does.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dbase::{ErrorKind, FieldValue};
+    ///
+    /// let mut record = dbase::Record::default();
+    /// record.insert("NAME".to_owned(), FieldValue::Character(Some("Yoshi".to_owned())));
+    /// assert!(record.get_required("name").is_ok());
+    /// let error = record.get_required("age").unwrap_err();
+    /// assert!(matches!(error.kind(), ErrorKind::UnknownFieldName(name) if name == "age"));
+    /// ```
+    pub fn get_required(&self, field_name: &str) -> Result<&FieldValue, Error> {
+        self.get(field_name).ok_or_else(|| Error {
+            record_num: 0,
+            field: None,
+            kind: ErrorKind::UnknownFieldName(field_name.to_string()),
+        })
+    }
+
+    /// Returns the [FieldValue](enum.FieldValue.html) for the field with exactly the given name
+    pub fn get_exact(&self, field_name: &str) -> Option<&FieldValue> {
         self.map.get(field_name)
     }
 
+    /// Replaces the value of the given field, the name is matched like [Record::get] does
+    ///
+    /// A record only knows the values it was read or built with, so the new value
+    /// must have the same [type](FieldValue::field_type) as the current one.
+    /// Fails with [ErrorKind::UnknownFieldName] if the record has no such field,
+    /// or with [ErrorKind::IncompatibleType] if the types differ.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dbase::{ErrorKind, FieldValue};
+    ///
+    /// let mut record = dbase::Record::default();
+    /// record.insert("AGE".to_owned(), FieldValue::Numeric(None));
+    /// record.set("age", FieldValue::Numeric(Some(42.0))).unwrap();
+    /// assert_eq!(record.get("AGE"), Some(&FieldValue::Numeric(Some(42.0))));
+    ///
+    /// let error = record.set("age", FieldValue::Logical(Some(true))).unwrap_err();
+    /// assert!(matches!(error.kind(), ErrorKind::IncompatibleType));
+    /// ```
+    pub fn set(&mut self, field_name: &str, value: FieldValue) -> Result<(), Error> {
+        let error = |kind| Error {
+            record_num: 0,
+            field: None,
+            kind,
+        };
+        let current = if self.map.contains_key(field_name) {
+            self.map.get_mut(field_name)
+        } else {
+            self.map
+                .iter_mut()
+                .find(|(name, _)| name.eq_ignore_ascii_case(field_name))
+                .map(|(_, value)| value)
+        };
+        let current =
+            current.ok_or_else(|| error(ErrorKind::UnknownFieldName(field_name.to_string())))?;
+        if current.field_type() != value.field_type() {
+            return Err(error(ErrorKind::IncompatibleType));
+        }
+        *current = value;
+        Ok(())
+    }
+
     /// Returns the mutable [FieldValue](enum.FieldValue.html) for the given field name
     pub fn get_mut(&mut self, field_name: &str) -> Option<&mut FieldValue> {
         self.map.get_mut(field_name)
@@ -54,6 +148,68 @@ impl Record {
     pub fn remove(&mut self, field_name: &str) -> Option<FieldValue> {
         self.map.remove(field_name)
     }
+
+    /// Reads a record where each value is replaced by `f(field_name, value)`
+    /// as it is decoded
+    pub(crate) fn read_mapped<Source, MemoSource, F>(
+        field_iterator: &mut FieldIterator<Source, MemoSource>,
+        f: &F,
+    ) -> Result<Self, FieldIOError>
+    where
+        Source: Read + Seek,
+        MemoSource: Read + Seek,
+        F: Fn(&str, FieldValue) -> FieldValue,
+    {
+        let mut map = HashMap::<String, FieldValue>::new();
+        for result in field_iterator {
+            let NamedValue { name, value } = result?;
+            map.insert(name.to_owned(), f(name, value));
+        }
+        Ok(Self { map })
+    }
+}
+
+/// Returns the [FieldValue](enum.FieldValue.html) for the given field name
+///
+/// # Panics
+///
+/// Panics if the record has no field with this name, unlike [Record::get]
+/// which returns `None`. Use [Record::get] when the record may not have the field.
+/// The name is matched like [Record::get] does.
+///
+/// As the values of a record are not stored in the order of the fields,
+/// there is no positional access, use the name of the field.
+///
+/// # Example
+///
+/// ```
+/// use dbase::FieldValue;
+///
+/// # fn main() -> Result<(), dbase::Error> {
+/// let mut reader = dbase::Reader::from_path("tests/data/stations.dbf")?;
+/// let records = reader.read()?;
+/// assert_eq!(
+///     records[0]["name"],
+///     FieldValue::Character(Some("Van Dorn Street".to_string()))
+/// );
+/// # Ok(())
+/// # }
+/// ```
+///
+/// ```should_panic
+/// let record = dbase::Record::default();
+/// // panics with "field 'name' not found in record"
+/// let _ = &record["name"];
+/// ```
+impl std::ops::Index<&str> for Record {
+    type Output = FieldValue;
+
+    fn index(&self, field_name: &str) -> &FieldValue {
+        match self.get(field_name) {
+            Some(value) => value,
+            None => panic!("field '{}' not found in record", field_name),
+        }
+    }
 }
 
 impl IntoIterator for Record {
@@ -0,0 +1,234 @@
+//! Module with the fn's generating SQL `CREATE TABLE` statements
+//! from the structure of a table.
+use std::fmt::Write;
+
+use crate::field::types::FieldType;
+use crate::field::FieldInfo;
+use crate::reading::TableInfo;
+
+/// SQL dialects for which a `CREATE TABLE` statement can be generated
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum SqlDialect {
+    Standard,
+    Postgres,
+    Sqlite,
+    Mysql,
+}
+
+impl SqlDialect {
+    fn quote_identifier(self, name: &str) -> String {
+        match self {
+            SqlDialect::Mysql => format!("`{}`", name.replace('`', "``")),
+            _ => format!("\"{}\"", name.replace('"', "\"\"")),
+        }
+    }
+
+    fn column_type(self, field: &FieldInfo) -> String {
+        let length = field.record_length();
+        let decimals = usize::from(field.num_decimal_places).min(length);
+        match (self, field.field_type) {
+            (SqlDialect::Sqlite, FieldType::Character) => "TEXT".to_string(),
+            (_, FieldType::Character) => format!("VARCHAR({})", length),
+            (SqlDialect::Sqlite, FieldType::Numeric | FieldType::Float) => "NUMERIC".to_string(),
+            (SqlDialect::Mysql, FieldType::Numeric | FieldType::Float) => {
+                format!("DECIMAL({},{})", length, decimals)
+            }
+            (_, FieldType::Numeric | FieldType::Float) => {
+                format!("NUMERIC({},{})", length, decimals)
+            }
+            (SqlDialect::Sqlite, FieldType::Currency) => "NUMERIC".to_string(),
+            (SqlDialect::Mysql, FieldType::Currency) => "DECIMAL(19,4)".to_string(),
+            (_, FieldType::Currency) => "NUMERIC(19,4)".to_string(),
+            (SqlDialect::Mysql, FieldType::Integer | FieldType::AutoIncrement) => "INT".to_string(),
+            (_, FieldType::Integer | FieldType::AutoIncrement) => "INTEGER".to_string(),
+            (SqlDialect::Sqlite, FieldType::Date) => "TEXT".to_string(),
+            (_, FieldType::Date) => "DATE".to_string(),
+            (SqlDialect::Sqlite, FieldType::Logical) => "INTEGER".to_string(),
+            (_, FieldType::Logical) => "BOOLEAN".to_string(),
+            (SqlDialect::Sqlite, FieldType::Double | FieldType::DBase7Double) => "REAL".to_string(),
+            (SqlDialect::Mysql, FieldType::Double | FieldType::DBase7Double) => {
+                "DOUBLE".to_string()
+            }
+            (_, FieldType::Double | FieldType::DBase7Double) => "DOUBLE PRECISION".to_string(),
+            (SqlDialect::Sqlite, FieldType::DateTime | FieldType::Timestamp) => "TEXT".to_string(),
+            (SqlDialect::Mysql, FieldType::DateTime | FieldType::Timestamp) => {
+                "DATETIME".to_string()
+            }
+            (_, FieldType::DateTime | FieldType::Timestamp) => "TIMESTAMP".to_string(),
+            (SqlDialect::Mysql, FieldType::Memo) => "LONGTEXT".to_string(),
+            (_, FieldType::Memo) => "TEXT".to_string(),
+            (SqlDialect::Postgres, FieldType::Picture) => "BYTEA".to_string(),
+            (SqlDialect::Mysql, FieldType::Picture) => "LONGBLOB".to_string(),
+            (_, FieldType::Picture) => "BLOB".to_string(),
+        }
+    }
+}
+
+impl TableInfo {
+    /// Returns a `CREATE TABLE` statement, in standard SQL, creating a table
+    /// named `table_name` with one column per field
+    ///
+    /// The column types are:
+    ///
+    /// | Field type              | Column type        |
+    /// |-------------------------|--------------------|
+    /// | Character(n)            | `VARCHAR(n)`       |
+    /// | Numeric(n, d), Float    | `NUMERIC(n,d)`     |
+    /// | Currency                | `NUMERIC(19,4)`    |
+    /// | Integer, AutoIncrement  | `INTEGER`          |
+    /// | Date                    | `DATE`             |
+    /// | Logical                 | `BOOLEAN`          |
+    /// | Double, DBase7Double    | `DOUBLE PRECISION` |
+    /// | DateTime, Timestamp     | `TIMESTAMP`        |
+    /// | Memo                    | `TEXT`             |
+    /// | Picture                 | `BLOB`             |
+    ///
+    /// Identifiers are quoted with double quotes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let info = dbase::TableInfo::from_path("tests/data/stations.dbf")?;
+    /// let ddl = info.to_sql_ddl("stations");
+    /// assert!(ddl.starts_with("CREATE TABLE \"stations\" (\n"));
+    /// assert!(ddl.contains("    \"name\" VARCHAR(254),\n"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn to_sql_ddl(&self, table_name: &str) -> String {
+        self.to_ddl(table_name, SqlDialect::Standard)
+    }
+
+    /// Returns a `CREATE TABLE` statement for PostgreSQL
+    ///
+    /// Same as [TableInfo::to_sql_ddl], except that Picture fields are `BYTEA` columns.
+    pub fn to_postgres_ddl(&self, table_name: &str) -> String {
+        self.to_ddl(table_name, SqlDialect::Postgres)
+    }
+
+    /// Returns a `CREATE TABLE` statement for SQLite
+    ///
+    /// Columns use SQLite's storage classes: Character, Date, DateTime
+    /// and Timestamp fields are `TEXT`, Numeric, Float and Currency fields are `NUMERIC`,
+    /// Logical fields are `INTEGER` and Double fields are `REAL`.
+    pub fn to_sqlite_ddl(&self, table_name: &str) -> String {
+        self.to_ddl(table_name, SqlDialect::Sqlite)
+    }
+
+    /// Returns a `CREATE TABLE` statement for MySQL
+    ///
+    /// Identifiers are quoted with backticks, Numeric fields are `DECIMAL(n,d)` columns,
+    /// Integer fields are `INT`, Double fields are `DOUBLE`, DateTime fields are `DATETIME`,
+    /// Memo fields are `LONGTEXT` and Picture fields are `LONGBLOB`.
+    pub fn to_mysql_ddl(&self, table_name: &str) -> String {
+        self.to_ddl(table_name, SqlDialect::Mysql)
+    }
+
+    fn to_ddl(&self, table_name: &str, dialect: SqlDialect) -> String {
+        let columns = self
+            .fields_info
+            .iter()
+            .map(|field| {
+                format!(
+                    "    {} {}",
+                    dialect.quote_identifier(field.name()),
+                    dialect.column_type(field)
+                )
+            })
+            .collect::<Vec<_>>();
+        let mut ddl = String::new();
+        let _ = writeln!(
+            ddl,
+            "CREATE TABLE {} (",
+            dialect.quote_identifier(table_name)
+        );
+        let _ = writeln!(ddl, "{}", columns.join(",\n"));
+        ddl.push_str(");");
+        ddl
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::io::Cursor;
+
+    use crate::{FieldName, Reader, TableWriterBuilder};
+
+    fn table_info() -> TableInfo {
+        let writer = TableWriterBuilder::new()
+            .add_character_field(FieldName::try_from("name").unwrap(), 20)
+            .add_numeric_field(FieldName::try_from("price").unwrap(), 8, 2)
+            .add_integer_field(FieldName::try_from("count").unwrap())
+            .add_date_field(FieldName::try_from("sold").unwrap())
+            .add_logical_field(FieldName::try_from("in_stock").unwrap())
+            .add_double_field(FieldName::try_from("weight").unwrap())
+            .build_with_dest(Cursor::new(Vec::<u8>::new()));
+        let mut cursor = writer.finish().unwrap();
+        cursor.set_position(0);
+        let mut info = Reader::new(cursor).unwrap().into_table_info();
+        let memo = FieldInfo::new(FieldName::try_from("notes").unwrap(), FieldType::Memo, 10);
+        info.fields_info.push(memo);
+        info
+    }
+
+    #[test]
+    fn standard_ddl() {
+        assert_eq!(
+            table_info().to_sql_ddl("items"),
+            "CREATE TABLE \"items\" (\n\
+             \x20   \"name\" VARCHAR(20),\n\
+             \x20   \"price\" NUMERIC(8,2),\n\
+             \x20   \"count\" INTEGER,\n\
+             \x20   \"sold\" DATE,\n\
+             \x20   \"in_stock\" BOOLEAN,\n\
+             \x20   \"weight\" DOUBLE PRECISION,\n\
+             \x20   \"notes\" TEXT\n\
+             );"
+        );
+    }
+
+    #[test]
+    fn postgres_ddl() {
+        let info = table_info();
+        assert_eq!(info.to_postgres_ddl("items"), info.to_sql_ddl("items"));
+        assert_eq!(
+            info.to_postgres_ddl("my \"items\""),
+            info.to_sql_ddl("items")
+                .replace("\"items\"", "\"my \"\"items\"\"\"")
+        );
+    }
+
+    #[test]
+    fn sqlite_ddl() {
+        assert_eq!(
+            table_info().to_sqlite_ddl("items"),
+            "CREATE TABLE \"items\" (\n\
+             \x20   \"name\" TEXT,\n\
+             \x20   \"price\" NUMERIC,\n\
+             \x20   \"count\" INTEGER,\n\
+             \x20   \"sold\" TEXT,\n\
+             \x20   \"in_stock\" INTEGER,\n\
+             \x20   \"weight\" REAL,\n\
+             \x20   \"notes\" TEXT\n\
+             );"
+        );
+    }
+
+    #[test]
+    fn mysql_ddl() {
+        assert_eq!(
+            table_info().to_mysql_ddl("items"),
+            "CREATE TABLE `items` (\n\
+             \x20   `name` VARCHAR(20),\n\
+             \x20   `price` DECIMAL(8,2),\n\
+             \x20   `count` INT,\n\
+             \x20   `sold` DATE,\n\
+             \x20   `in_stock` BOOLEAN,\n\
+             \x20   `weight` DOUBLE,\n\
+             \x20   `notes` LONGTEXT\n\
+             );"
+        );
+    }
+}
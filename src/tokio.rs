@@ -0,0 +1,214 @@
+//! Module with the definition of the [AsyncReader], which reads records
+//! from a [tokio] source.
+use std::future::poll_fn;
+use std::io::Cursor;
+use std::pin::Pin;
+
+use tokio::io::{AsyncRead, ReadBuf};
+
+use crate::encoding::DynEncoding;
+use crate::error::Error;
+use crate::field::{DeletionFlag, FieldInfo};
+use crate::header::{Dialect, Header};
+use crate::memo::MemoReader;
+use crate::reading::{complete_record_data, decode_record, ReadableRecord, ReadingOptions};
+use crate::{Encoding, Record};
+
+/// Reads the records of a dBase file from an asynchronous source
+///
+/// The records are read one after the other, from the first one, with
+/// [AsyncReader::read_record]. The header and the records are decoded
+/// the same way as with a [Reader](crate::Reader).
+///
+/// Memo files are not supported, reading a record of a table with memo fields
+/// fails with [ErrorKind::MissingMemoFile](crate::ErrorKind::MissingMemoFile).
+///
+/// # Example
+///
+/// ```
+/// # fn main() -> Result<(), dbase::Error> {
+/// # let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+/// # runtime.block_on(async {
+/// let file = tokio::fs::File::open("tests/data/line.dbf").await.unwrap();
+/// let mut reader = dbase::AsyncReader::new(file).await?;
+/// while let Some(record) = reader.read_record().await? {
+///     assert!(record.get("name").is_some());
+/// }
+/// # Ok(())
+/// # })
+/// # }
+/// ```
+pub struct AsyncReader<T> {
+    source: T,
+    header: Header,
+    fields_info: Vec<FieldInfo>,
+    encoding: DynEncoding,
+    options: ReadingOptions,
+    /// Index of the next record to read
+    current_record: u32,
+    /// Number of records returned, to stop at the limit of the options
+    num_returned: usize,
+    record_data: Vec<u8>,
+}
+
+impl<T: AsyncRead + Unpin> AsyncReader<T> {
+    /// Creates a new reader from the source, reading the header and fields information
+    ///
+    /// The source is read in small chunks, so it should be buffered
+    /// (e.g. using a `tokio::io::BufReader`), unless it is already in memory.
+    pub async fn new(mut source: T) -> Result<Self, Error> {
+        let mut header_data = vec![0u8; Header::SIZE];
+        read_all(&mut source, &mut header_data).await?;
+        // The whole header, field descriptors included, is read before being decoded
+        let offset_to_first_record = u16::from_le_bytes([header_data[8], header_data[9]]);
+        if usize::from(offset_to_first_record) > header_data.len() {
+            header_data.resize(usize::from(offset_to_first_record), 0);
+            read_all(&mut source, &mut header_data[Header::SIZE..]).await?;
+        }
+        let file = crate::File::open(Cursor::new(header_data))?;
+
+        Ok(Self {
+            source,
+            header: file.header,
+            record_data: vec![0u8; usize::from(file.header.size_of_record)],
+            fields_info: file.fields_info.inner,
+            encoding: file.encoding,
+            options: ReadingOptions::default(),
+            current_record: 0,
+            num_returned: 0,
+        })
+    }
+
+    /// Sets the encoding used to decode the strings of the records
+    pub fn set_encoding<E: Encoding + 'static>(&mut self, encoding: E) {
+        self.encoding = DynEncoding::new(encoding);
+    }
+
+    /// Sets the options used to read the next records
+    ///
+    /// The records to [skip](ReadingOptions::skip) are skipped before
+    /// the next record is read, if no record was read yet.
+    pub fn set_options(&mut self, options: ReadingOptions) {
+        self.options = options;
+    }
+
+    /// Returns the header of the file
+    pub fn header(&self) -> &Header {
+        &self.header
+    }
+
+    /// Returns the fields contained in the opened file
+    pub fn fields(&self) -> &[FieldInfo] {
+        &self.fields_info
+    }
+
+    /// Reads the next record that is not deleted as the type you want,
+    /// `None` when all the records were read
+    pub async fn read_record_as<R: ReadableRecord>(&mut self) -> Result<Option<R>, Error> {
+        if self
+            .options
+            .limit
+            .is_some_and(|limit| self.num_returned >= limit)
+        {
+            return Ok(None);
+        }
+        let num_records = if self.options.ignore_num_records {
+            u32::MAX
+        } else {
+            self.header.num_records
+        };
+        if self.current_record == 0 {
+            let skip = self.options.skip.unwrap_or(0).min(num_records as usize);
+            for _ in 0..skip {
+                if !self.read_record_data().await? {
+                    self.current_record = num_records;
+                    return Ok(None);
+                }
+            }
+        }
+
+        loop {
+            if self.current_record >= num_records {
+                return Ok(None);
+            }
+            let index = self.current_record as usize;
+            if !self.read_record_data().await? {
+                self.current_record = num_records;
+                return Ok(None);
+            }
+            if DeletionFlag::from_byte(self.record_data[0]) == DeletionFlag::Deleted {
+                continue;
+            }
+
+            let record = decode_record(
+                &self.record_data,
+                &self.fields_info,
+                &mut None::<MemoReader<Cursor<Vec<u8>>>>,
+                &self.encoding,
+                Dialect::from(self.header.file_type),
+                self.options,
+            )
+            .map_err(|error| Error::new(error, index))?;
+            self.num_returned += 1;
+            return Ok(Some(record));
+        }
+    }
+
+    /// Reads the next [Record] that is not deleted, `None` when all the records were read
+    pub async fn read_record(&mut self) -> Result<Option<Record>, Error> {
+        self.read_record_as::<Record>().await
+    }
+
+    /// Returns the source, positioned after the last record read
+    pub fn into_inner(self) -> T {
+        self.source
+    }
+
+    /// Reads the data of the current record, returns false if it is missing
+    async fn read_record_data(&mut self) -> Result<bool, Error> {
+        let index = self.current_record as usize;
+        let num_read = read_until_full(&mut self.source, &mut self.record_data)
+            .await
+            .map_err(|error| Error::io_error(error, index))?;
+        let result = complete_record_data(
+            &mut self.record_data,
+            num_read,
+            &self.fields_info,
+            self.options,
+            index,
+        );
+        if result.is_err() {
+            // Do not try to read the following records
+            self.current_record = u32::MAX;
+        } else {
+            self.current_record += 1;
+        }
+        result
+    }
+}
+
+/// Reads until `buffer` is full or the source ends, returns the number of bytes read
+async fn read_until_full<T: AsyncRead + Unpin>(
+    source: &mut T,
+    buffer: &mut [u8],
+) -> std::io::Result<usize> {
+    let mut buffer = ReadBuf::new(buffer);
+    while buffer.remaining() > 0 {
+        let num_filled = buffer.filled().len();
+        poll_fn(|cx| Pin::new(&mut *source).poll_read(cx, &mut buffer)).await?;
+        if buffer.filled().len() == num_filled {
+            break;
+        }
+    }
+    Ok(buffer.filled().len())
+}
+
+async fn read_all<T: AsyncRead + Unpin>(source: &mut T, buffer: &mut [u8]) -> Result<(), Error> {
+    let num_read = read_until_full(source, buffer)
+        .await
+        .map_err(|error| Error::io_error(error, 0))?;
+    if num_read < buffer.len() {
+        return Err(Error::io_error(std::io::ErrorKind::UnexpectedEof.into(), 0));
+    }
+    Ok(())
+}
@@ -0,0 +1,174 @@
+//! Module with the definition of the [TableView], which decodes a dBase file
+//! already loaded in memory, and is available without the `std` feature.
+use alloc::vec::Vec;
+
+use crate::encoding::DynEncoding;
+use crate::error::{Error, ErrorKind, FieldIOError};
+use crate::field::types::{CharacterTrim, FieldValue, OverflowPolicy, TrimOption};
+use crate::field::{
+    computed_record_size, validate_record_size, DeletionFlag, FieldInfo, FieldsInfo,
+};
+use crate::header::{Dialect, Header};
+use crate::Encoding;
+
+/// Read only view over the bytes of a whole dBase file
+///
+/// The header and the field descriptors are parsed when the view is created,
+/// the records are decoded on demand, directly from the bytes.
+/// As nothing is read from a stream, this does not need the `std` feature,
+/// only `alloc`.
+///
+/// The data of Memo and Picture fields is in a separate file, so decoding
+/// a record where they are not empty fails with [ErrorKind::MissingMemoFile].
+///
+/// # Example
+///
+/// ```
+/// # fn main() -> Result<(), dbase::Error> {
+/// let bytes = include_bytes!("../tests/data/line.dbf");
+/// let view = dbase::TableView::new(bytes)?;
+/// assert_eq!(view.num_records(), 1);
+///
+/// let values = view.read_record(0)?.unwrap();
+/// assert_eq!(values[0], dbase::FieldValue::Character(Some("linestring1".to_string())));
+/// # Ok(())
+/// # }
+/// ```
+pub struct TableView<'a> {
+    data: &'a [u8],
+    header: Header,
+    fields_info: Vec<FieldInfo>,
+    encoding: DynEncoding,
+    character_trim: CharacterTrim,
+}
+
+impl<'a> TableView<'a> {
+    /// Parses the header and the field descriptors at the beginning of `data`
+    ///
+    /// Fails with [ErrorKind::TruncatedHeader] if `data` ends before
+    /// the terminator of the field descriptors.
+    pub fn new(data: &'a [u8]) -> Result<Self, Error> {
+        let header_bytes = data
+            .get(..Header::SIZE)
+            .and_then(|bytes| <&[u8; Header::SIZE]>::try_from(bytes).ok())
+            .ok_or_else(|| error(ErrorKind::TruncatedHeader))?;
+        let mut header = Header::parse(header_bytes)?;
+
+        let max_num_fields = header.max_num_fields(true).map_err(error)?;
+        let descriptors = data
+            .get(header.size_before_descriptors()..)
+            .ok_or_else(|| error(ErrorKind::TruncatedHeader))?;
+        let fields_info =
+            FieldsInfo::from_bytes(descriptors, max_num_fields, header.file_type, true)
+                .map_err(error)?
+                .inner;
+
+        let encoding = header
+            .code_page_mark
+            .to_encoding()
+            .ok_or_else(|| error(ErrorKind::UnsupportedCodePage(header.code_page_mark)))?;
+
+        validate_record_size(&header, &fields_info).map_err(error)?;
+        // Some files do not include the deletion flag in the record size,
+        // but the position of the records is computed with it
+        header.size_of_record = computed_record_size(&fields_info) as u16;
+
+        Ok(Self {
+            data,
+            header,
+            fields_info,
+            encoding,
+            character_trim: CharacterTrim::default(),
+        })
+    }
+
+    /// Sets the encoding used to decode the strings of the records
+    pub fn set_encoding<E: Encoding + 'static>(&mut self, encoding: E) {
+        self.encoding = DynEncoding::new(encoding);
+    }
+
+    /// Sets how spaces are trimmed within [FieldValue::Character],
+    /// see [ReadingOptions::character_trim](crate::ReadingOptions::character_trim)
+    pub fn set_character_trim(&mut self, trim_option: TrimOption) {
+        self.character_trim.option = trim_option;
+    }
+
+    /// Returns the header of the file
+    pub fn header(&self) -> &Header {
+        &self.header
+    }
+
+    /// Returns the fields of the table
+    pub fn fields(&self) -> &[FieldInfo] {
+        &self.fields_info
+    }
+
+    /// Returns the number of records written in the header,
+    /// deleted records included
+    pub fn num_records(&self) -> usize {
+        self.header.num_records as usize
+    }
+
+    /// Returns the bytes of the record at `index`, deletion flag included
+    ///
+    /// Fails with [ErrorKind::RecordIndexOutOfRange] if there is no such record,
+    /// or [ErrorKind::TruncatedRecord] if the data ends inside it.
+    pub fn record_bytes(&self, index: usize) -> Result<&'a [u8], Error> {
+        let position = self.header.record_position(index).ok_or(Error {
+            record_num: index,
+            field: None,
+            kind: ErrorKind::RecordIndexOutOfRange {
+                index,
+                num_records: self.header.num_records,
+            },
+        })?;
+        let end = position + usize::from(self.header.size_of_record);
+        self.data.get(position..end).ok_or(Error {
+            record_num: index,
+            field: None,
+            kind: ErrorKind::TruncatedRecord { index },
+        })
+    }
+
+    /// Decodes the values of the record at `index`, in the order of the fields,
+    /// `None` if the record is deleted
+    pub fn read_record(&self, index: usize) -> Result<Option<Vec<FieldValue>>, Error> {
+        let bytes = self.record_bytes(index)?;
+        if DeletionFlag::from_byte(bytes[0]) == DeletionFlag::Deleted {
+            return Ok(None);
+        }
+        let dialect = Dialect::from(self.header.file_type);
+        self.fields_info
+            .iter()
+            .map(|field_info| {
+                let start = usize::from(field_info.offset_within_record());
+                let field_bytes = &bytes[start..start + field_info.record_length()];
+                FieldValue::decode(
+                    field_bytes,
+                    field_info,
+                    dialect,
+                    &self.encoding,
+                    self.character_trim,
+                    OverflowPolicy::default(),
+                )
+                .map_err(|kind| {
+                    Error::new(FieldIOError::new(kind, Some(field_info.clone())), index)
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map(Some)
+    }
+
+    /// Returns an iterator over the values of the records that are not deleted
+    pub fn records(&self) -> impl Iterator<Item = Result<Vec<FieldValue>, Error>> + '_ {
+        (0..self.num_records()).filter_map(|index| self.read_record(index).transpose())
+    }
+}
+
+fn error(kind: ErrorKind) -> Error {
+    Error {
+        record_num: 0,
+        field: None,
+        kind,
+    }
+}
@@ -1,19 +1,19 @@
 //! Module with all structs & functions charged of writing .dbf file content
-use std::fs::File;
-use std::io::{BufWriter, Cursor, Seek, SeekFrom, Write};
-use std::path::Path;
+use std::io::{Cursor, Seek, SeekFrom, Write};
 
 use byteorder::WriteBytesExt;
 
 use crate::encoding::{AsCodePageMark, DynEncoding};
-use crate::field::{types::FieldType, DeletionFlag, FieldInfo, FieldName};
-use crate::header::Header;
-use crate::reading::TERMINATOR_VALUE;
-use crate::reading::{TableInfo, BACKLINK_SIZE};
-use crate::{Encoding, Error, ErrorKind, FieldIOError, Record, UnicodeLossy};
+use crate::field::{
+    compute_offsets_in_record, field_name_matches, types::FieldType, DeletionFlag, FieldInfo,
+    FieldName, DELETION_FLAG_SIZE, FIELD_NAME_LENGTH, LEVEL7_FIELD_NAME_LENGTH, TERMINATOR_VALUE,
+};
+use crate::header::{Header, Version, BACKLINK_SIZE};
+use crate::reading::TableInfo;
+use crate::{Encoding, Error, ErrorKind, FieldIOError, FieldValue, Record, UnicodeLossy};
 
 /// A dbase file ends with this byte
-const FILE_TERMINATOR: u8 = 0x1A;
+pub(crate) const FILE_TERMINATOR: u8 = 0x1A;
 
 pub(crate) fn write_header_parts<W>(
     dst: &mut W,
@@ -23,14 +23,98 @@ pub(crate) fn write_header_parts<W>(
 where
     W: Write,
 {
+    // Longer names are truncated in the descriptors, which can make two of them the same
+    let name_length = if header.file_type.is_dbase7() {
+        LEVEL7_FIELD_NAME_LENGTH
+    } else {
+        FIELD_NAME_LENGTH
+    };
+    for (i, info) in fields_info.iter().enumerate() {
+        let name = info.descriptor_name(name_length);
+        if fields_info[..i].iter().any(|other| {
+            other
+                .descriptor_name(name_length)
+                .eq_ignore_ascii_case(name)
+        }) {
+            return Err(Error {
+                record_num: 0,
+                field: Some(info.clone()),
+                kind: ErrorKind::DuplicateFieldName {
+                    name: String::from_utf8_lossy(name).into_owned(),
+                },
+            });
+        }
+    }
+
+    if let Some(info) = fields_info
+        .iter()
+        .find(|info| !header.file_type.supports_field_type(info.field_type))
+    {
+        return Err(Error {
+            record_num: 0,
+            field: Some(info.clone()),
+            kind: ErrorKind::UnsupportedFieldTypeForVersion {
+                field_type: info.field_type,
+                version: header.file_type,
+            },
+        });
+    }
+
+    if let Some(info) = fields_info
+        .iter()
+        .find(|info| !has_valid_decimal_places(info))
+    {
+        return Err(Error {
+            record_num: 0,
+            field: Some(info.clone()),
+            kind: ErrorKind::InvalidDecimalPlaces {
+                length: info.field_length,
+                num_decimal_places: info.num_decimal_places,
+            },
+        });
+    }
+
+    let size_of_record = DELETION_FLAG_SIZE
+        + fields_info
+            .iter()
+            .map(FieldInfo::record_length)
+            .sum::<usize>();
+    let offset_to_first_record = offset_to_first_record(header.file_type, fields_info.len());
+    for (name, value) in [
+        ("size of records", size_of_record),
+        ("offset of the first record", offset_to_first_record),
+    ] {
+        if value > usize::from(u16::MAX) {
+            return Err(Error {
+                record_num: 0,
+                field: None,
+                kind: ErrorKind::HeaderFieldOverflow {
+                    name,
+                    value: u32::try_from(value).unwrap_or(u32::MAX),
+                },
+            });
+        }
+    }
+
     header
         .write_to(dst)
         .map_err(|error| Error::io_error(error, 0))?;
 
-    for record_info in fields_info.iter() {
-        record_info
-            .write_to(dst)
+    if header.file_type.is_dbase7() {
+        // We don't know about language drivers, leave the name empty
+        dst.write_all(&[0u8; Header::LEVEL7_EXTENSION_SIZE])
             .map_err(|error| Error::io_error(error, 0))?;
+        for record_info in fields_info.iter() {
+            record_info
+                .write_level7_to(dst)
+                .map_err(|error| Error::io_error(error, 0))?;
+        }
+    } else {
+        for record_info in fields_info.iter() {
+            record_info
+                .write_to(dst)
+                .map_err(|error| Error::io_error(error, 0))?;
+        }
     }
     dst.write_u8(TERMINATOR_VALUE)
         .map_err(|error| Error::io_error(error, 0))?;
@@ -46,11 +130,52 @@ where
     Ok(())
 }
 
+/// Size of the header of a file of the given version with `num_fields` fields,
+/// which is where the first record starts
+pub(crate) fn offset_to_first_record(file_type: Version, num_fields: usize) -> usize {
+    let mut offset =
+        Header::SIZE + (num_fields * FieldInfo::size_for(file_type)) + std::mem::size_of::<u8>();
+    if file_type.is_dbase7() {
+        offset += Header::LEVEL7_EXTENSION_SIZE;
+    }
+    if file_type.is_visual_fox_pro() {
+        offset += BACKLINK_SIZE as usize;
+    }
+    offset
+}
+
+/// Numbers with decimal places need at least one digit and the decimal point
+/// before them, like dBase requires
+fn has_valid_decimal_places(info: &FieldInfo) -> bool {
+    match info.field_type {
+        FieldType::Numeric | FieldType::Float if info.num_decimal_places > 0 => {
+            usize::from(info.num_decimal_places) + 2 <= usize::from(info.field_length)
+        }
+        _ => true,
+    }
+}
+
+/// What to do when the text representation of a Numeric or Float value
+/// is longer than its field
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum NumericOverflowPolicy {
+    /// Writing the record fails with [ErrorKind::NumericOverflow]
+    #[default]
+    Error,
+    /// The field is filled with asterisks, as dBase does
+    FillWithAsterisks,
+}
+
 /// Builder to be used to create a [TableWriter](struct.TableWriter.html).
 ///
 /// The dBase format is akin to a database, thus you have to specify the fields
 /// of the record you are going to write
 ///
+/// Field names are not case sensitive, and are truncated to the length the
+/// descriptors of the version can hold (11 bytes, 32 for dBase Level 7):
+/// writing fails with [ErrorKind::DuplicateFieldName] if two fields end up
+/// with the same name.
+///
 /// # Example
 ///
 /// Here we will create a writer that will be able to write records with 2 character fields
@@ -71,6 +196,12 @@ pub struct TableWriterBuilder {
     v: Vec<FieldInfo>,
     hdr: Header,
     encoding: DynEncoding,
+    /// When true, the code page mark of `hdr` is written as is,
+    /// instead of the one of the encoding
+    keep_code_page_mark: bool,
+    numeric_overflow: NumericOverflowPolicy,
+    /// Version set with [Self::set_version], written as is
+    version: Option<Version>,
 }
 
 impl TableWriterBuilder {
@@ -82,6 +213,9 @@ impl TableWriterBuilder {
             v: vec![],
             hdr: Header::new(0, 0, 0),
             encoding: DynEncoding::new(UnicodeLossy),
+            keep_code_page_mark: false,
+            numeric_overflow: NumericOverflowPolicy::default(),
+            version: None,
         }
     }
 
@@ -91,6 +225,9 @@ impl TableWriterBuilder {
             v: vec![],
             hdr: Header::new(0, 0, 0),
             encoding: DynEncoding::new(encoding),
+            keep_code_page_mark: false,
+            numeric_overflow: NumericOverflowPolicy::default(),
+            version: None,
         }
     }
 
@@ -117,6 +254,11 @@ impl TableWriterBuilder {
         Self::from_table_info(reader.into_table_info())
     }
 
+    /// Gets the field definition and the header from the table info to construct the TableWriter
+    ///
+    /// The version, table flags, encryption flag and code page mark of the header are kept,
+    /// so that rewriting a file read from disk does not change them,
+    /// unless [Self::set_encoding] is used to change the encoding.
     pub fn from_table_info(table_info: TableInfo) -> Self {
         let fields_info = table_info.fields_info;
         let mut hdr = table_info.header;
@@ -126,12 +268,76 @@ impl TableWriterBuilder {
             v: fields_info,
             hdr,
             encoding: table_info.encoding,
+            keep_code_page_mark: true,
+            numeric_overflow: NumericOverflowPolicy::default(),
+            version: None,
         }
     }
 
     /// Changes the encoding of the writer.
+    ///
+    /// The code page mark written in the header is the one of the new encoding.
     pub fn set_encoding<E: Encoding + 'static>(mut self, encoding: E) -> Self {
         self.encoding = DynEncoding::new(encoding);
+        self.keep_code_page_mark = false;
+        self
+    }
+
+    /// Sets what the writer does when a Numeric or Float value
+    /// does not fit in its field, by default writing the record fails.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dbase::{FieldName, NumericOverflowPolicy, TableWriterBuilder};
+    /// use std::io::Cursor;
+    ///
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// dbase::dbase_record!(
+    ///     struct Amount {
+    ///         value: f64,
+    ///     }
+    /// );
+    ///
+    /// let mut writer = TableWriterBuilder::new()
+    ///     .add_numeric_field(FieldName::try_from("value").unwrap(), 4, 0)
+    ///     .set_numeric_overflow_policy(NumericOverflowPolicy::FillWithAsterisks)
+    ///     .build_with_dest(Cursor::new(Vec::<u8>::new()));
+    /// writer.write_record(&Amount { value: 123456.0 })?;
+    /// let data = writer.finish()?.into_inner();
+    /// assert!(data.windows(4).any(|bytes| bytes == b"****"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_numeric_overflow_policy(mut self, policy: NumericOverflowPolicy) -> Self {
+        self.numeric_overflow = policy;
+        self
+    }
+
+    /// Sets the version of the file, instead of the one chosen from the types of the fields
+    ///
+    /// By default, the version is dBase III, or the one of the table info
+    /// given to [Self::from_table_info], changed if needed to a version that
+    /// supports the types of the fields: FoxPro 2 for the FoxPro binary types
+    /// (Integer, Double, DateTime, Currency) and a version with memo support
+    /// when there are Memo fields.
+    ///
+    /// The version set here is used even if it does not support the fields,
+    /// writing the table then fails with [ErrorKind::UnsupportedFieldTypeForVersion].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dbase::{FieldName, TableWriterBuilder, Version};
+    ///
+    /// let info = TableWriterBuilder::new()
+    ///     .add_integer_field(FieldName::try_from("count").unwrap())
+    ///     .set_version(Version::VisualFoxPro)
+    ///     .build_table_info();
+    /// assert_eq!(u8::from(info.header().file_type), 0x30);
+    /// ```
+    pub fn set_version(mut self, version: Version) -> Self {
+        self.version = Some(version);
         self
     }
 
@@ -143,6 +349,34 @@ impl TableWriterBuilder {
         self
     }
 
+    /// Adds a Character field that can be longer than 255 bytes
+    ///
+    /// Like Clipper and FoxPro, lengths greater than 255 are stored using the
+    /// number of decimal places byte of the field descriptor as high byte,
+    /// see [FieldInfo::wide_record_length].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dbase::{FieldName, TableWriterBuilder};
+    ///
+    /// let info = TableWriterBuilder::new()
+    ///     .add_wide_character_field(FieldName::try_from("NOTE").unwrap(), 300)
+    ///     .build_table_info();
+    /// assert_eq!(info.fields()[0].wide_record_length(), Some(300));
+    /// assert_eq!(info.header().size_of_record, 301);
+    /// ```
+    pub fn add_wide_character_field(mut self, name: FieldName, length: u16) -> Self {
+        let [low, high] = length.to_le_bytes();
+        let mut info = FieldInfo::new(name, FieldType::Character, low);
+        if high != 0 {
+            info.num_decimal_places = high;
+            info.wide_record_length = Some(length);
+        }
+        self.v.push(info);
+        self
+    }
+
     /// Adds a [Date](struct.Date.html) field
     pub fn add_date_field(mut self, name: FieldName) -> Self {
         self.v.push(FieldInfo::new(
@@ -154,6 +388,23 @@ impl TableWriterBuilder {
     }
 
     /// Adds a [Numeric](enum.FieldValue.html#variant.Numeric)
+    ///
+    /// `length` is the total number of characters of the values, including the sign
+    /// and the decimal point, `num_decimals` the number of digits after the decimal point.
+    /// When there are decimals, `length` must be at least `num_decimals + 2`, otherwise
+    /// writing fails with [ErrorKind::InvalidDecimalPlaces].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dbase::{FieldName, TableWriterBuilder};
+    ///
+    /// let info = TableWriterBuilder::new()
+    ///     .add_numeric_field(FieldName::try_from("PRICE").unwrap(), 10, 2)
+    ///     .build_table_info();
+    /// assert_eq!(info.fields()[0].length(), 10);
+    /// assert_eq!(info.fields()[0].num_decimal_places(), 2);
+    /// ```
     pub fn add_numeric_field(mut self, name: FieldName, length: u8, num_decimals: u8) -> Self {
         let mut info = FieldInfo::new(name, FieldType::Numeric, length);
         info.num_decimal_places = num_decimals;
@@ -162,6 +413,8 @@ impl TableWriterBuilder {
     }
 
     /// Adds a [Float](enum.FieldValue.html#variant.Float)
+    ///
+    /// `length` and `num_decimals` are like for [Self::add_numeric_field].
     pub fn add_float_field(mut self, name: FieldName, length: u8, num_decimals: u8) -> Self {
         let mut info = FieldInfo::new(name, FieldType::Float, length);
         info.num_decimal_places = num_decimals;
@@ -181,6 +434,16 @@ impl TableWriterBuilder {
         self
     }
 
+    /// Adds a [Memo](enum.FieldValue.html#variant.Memo) field
+    ///
+    /// The version of the file is changed to one that supports memo fields,
+    /// as the memo values cannot be written yet, this is only useful
+    /// to describe a table with [Self::build_table_info].
+    pub fn add_memo_field(mut self, name: FieldName) -> Self {
+        self.v.push(FieldInfo::new(name, FieldType::Memo, 10));
+        self
+    }
+
     /// Adds a [Integer](enum.FieldValue.html#variant.Integer)
     pub fn add_integer_field(mut self, name: FieldName) -> Self {
         self.v.push(FieldInfo::new(
@@ -190,9 +453,6 @@ impl TableWriterBuilder {
                 .size()
                 .expect("Internal error Integer field date should be known"),
         ));
-        self.hdr.file_type = crate::header::Version::FoxPro2 {
-            supports_memo: false,
-        };
         self
     }
 
@@ -205,9 +465,6 @@ impl TableWriterBuilder {
                 .size()
                 .expect("Internal error datetime field date should be known"),
         ));
-        self.hdr.file_type = crate::header::Version::FoxPro2 {
-            supports_memo: false,
-        };
         self
     }
 
@@ -220,9 +477,6 @@ impl TableWriterBuilder {
                 .size()
                 .expect("Internal error Double field date should be known"),
         ));
-        self.hdr.file_type = crate::header::Version::FoxPro2 {
-            supports_memo: false,
-        };
         self
     }
 
@@ -235,56 +489,120 @@ impl TableWriterBuilder {
                 .size()
                 .expect("Internal error Currency field date should be known"),
         ));
-        self.hdr.file_type = crate::header::Version::FoxPro2 {
-            supports_memo: false,
-        };
         self
     }
 
-    fn sync_header(&mut self) {
-        let mut offset_to_first_record =
-            Header::SIZE + (self.v.len() * FieldInfo::SIZE) + std::mem::size_of::<u8>();
-
-        if self.hdr.file_type.is_visual_fox_pro() {
-            offset_to_first_record += BACKLINK_SIZE as usize;
+    /// Chooses a version that supports the types of the fields,
+    /// unless one was set with [Self::set_version]
+    fn select_version(&mut self) {
+        if let Some(version) = self.version {
+            self.hdr.file_type = version;
+            return;
         }
-
-        let size_of_record = self
+        let file_type = self.hdr.file_type;
+        let unsupported = |field_types: &[FieldType]| {
+            self.v.iter().any(|info| {
+                field_types.contains(&info.field_type)
+                    && !file_type.supports_field_type(info.field_type)
+            })
+        };
+        let has_memo = self
             .v
             .iter()
-            .fold(1u16, |s, info| s + info.field_length as u16);
+            .any(|info| matches!(info.field_type, FieldType::Memo | FieldType::Picture));
+        // The FoxPro binary field types (Integer, Double, ...) are only
+        // understood by some versions, switch to FoxPro 2 if needed.
+        if unsupported(&[
+            FieldType::Integer,
+            FieldType::Double,
+            FieldType::DateTime,
+            FieldType::Currency,
+            FieldType::Picture,
+        ]) {
+            self.hdr.file_type = Version::FoxPro2 {
+                supports_memo: has_memo,
+            };
+        } else if unsupported(&[
+            FieldType::AutoIncrement,
+            FieldType::Timestamp,
+            FieldType::DBase7Double,
+        ]) {
+            self.hdr.file_type = Version::DBase7 {
+                supports_memo: has_memo,
+            };
+        }
+        if has_memo && !self.hdr.file_type.supports_memo() {
+            self.hdr.file_type = match self.hdr.file_type {
+                Version::DBase4 { .. } => Version::DBase4 {
+                    supports_memo: true,
+                },
+                Version::FoxPro2 { .. } => Version::FoxPro2 {
+                    supports_memo: true,
+                },
+                Version::DBase7 { .. } => Version::DBase7 {
+                    supports_memo: true,
+                },
+                _ => Version::DBase3 {
+                    supports_memo: true,
+                },
+            };
+        }
+    }
+
+    fn sync_header(&mut self) {
+        self.select_version();
+        let offset_to_first_record = offset_to_first_record(self.hdr.file_type, self.v.len());
+
+        compute_offsets_in_record(&mut self.v);
+        let size_of_record =
+            DELETION_FLAG_SIZE + self.v.iter().map(FieldInfo::record_length).sum::<usize>();
 
-        self.hdr.offset_to_first_record = offset_to_first_record as u16;
-        self.hdr.size_of_record = size_of_record;
-        self.hdr.code_page_mark = self.encoding.code_page_mark();
+        // Values that overflow are rejected when the header is written
+        self.hdr.offset_to_first_record = u16::try_from(offset_to_first_record).unwrap_or(u16::MAX);
+        self.hdr.size_of_record = u16::try_from(size_of_record).unwrap_or(u16::MAX);
+        if !self.keep_code_page_mark {
+            self.hdr.code_page_mark = self.encoding.code_page_mark();
+        }
     }
 
     /// Builds the writer and set the dst as where the file data will be written
     pub fn build_with_dest<W: Write + Seek>(mut self, dst: W) -> TableWriter<W> {
         self.sync_header();
-        TableWriter::new(dst, self.v, self.hdr, self.encoding)
+        TableWriter::new(dst, self.v, self.hdr, self.encoding, self.numeric_overflow)
+    }
+
+    /// Builds a [PreparedWriter] writing to `dst`
+    ///
+    /// The fields are validated and the header is written right away,
+    /// so errors in the definition of the table are returned here
+    /// instead of when writing the first record.
+    pub fn build_prepared<W: Write + Seek>(self, dst: W) -> Result<PreparedWriter<W>, Error> {
+        let mut writer = self.build_with_dest(dst);
+        writer
+            .dst()
+            .seek(SeekFrom::Start(0))
+            .map_err(|error| Error::io_error(error, 0))?;
+        writer.write_header()?;
+        Ok(PreparedWriter { writer })
     }
 
     /// Helper function to set create a file at the given path
     /// and make the writer write to the newly created file.
     ///
     /// This function wraps the `File` in a `BufWriter` to increase performance.
-    pub fn build_with_file_dest<P: AsRef<Path>>(
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn build_with_file_dest<P: AsRef<std::path::Path>>(
         self,
         path: P,
-    ) -> Result<TableWriter<BufWriter<File>>, Error> {
-        let file = File::create(path).map_err(|err| Error::io_error(err, 0))?;
-        let dst = BufWriter::new(file);
+    ) -> Result<TableWriter<std::io::BufWriter<std::fs::File>>, Error> {
+        let file = std::fs::File::create(path).map_err(|err| Error::io_error(err, 0))?;
+        let dst = std::io::BufWriter::new(file);
         Ok(self.build_with_dest(dst))
     }
 
     pub fn build_table_info(mut self) -> TableInfo {
         self.sync_header();
-        TableInfo {
-            header: self.hdr,
-            fields_info: self.v,
-            encoding: self.encoding,
-        }
+        TableInfo::new(self.hdr, self.v, self.encoding)
     }
 }
 
@@ -341,15 +659,9 @@ impl WritableRecord for Record {
         field_writer: &mut FieldWriter<'a, W>,
     ) -> Result<(), FieldIOError> {
         while let Some(name) = field_writer.next_field_name() {
-            let value = self.get(name).ok_or_else(|| {
-                FieldIOError::new(
-                    ErrorKind::Message(format!(
-                        "Could not find field named '{}' in the record map",
-                        name
-                    )),
-                    None,
-                )
-            })?;
+            let value = self
+                .get_required(name)
+                .map_err(|error| FieldIOError::new(error.kind, None))?;
             field_writer.write_next_field_value(value)?;
         }
         Ok(())
@@ -366,6 +678,7 @@ pub struct FieldWriter<'a, W: Write> {
     pub(crate) fields_info: std::iter::Peekable<std::slice::Iter<'a, FieldInfo>>,
     pub(crate) field_buffer: &'a mut Cursor<&'a mut [u8]>,
     pub(crate) encoding: &'a DynEncoding,
+    pub(crate) numeric_overflow: NumericOverflowPolicy,
 }
 
 impl<'a, W: Write> FieldWriter<'a, W> {
@@ -374,6 +687,17 @@ impl<'a, W: Write> FieldWriter<'a, W> {
         self.fields_info.peek().map(|info| info.name.as_str())
     }
 
+    /// Returns true if the next field to write is the one of the struct field `field_name`
+    ///
+    /// The names are compared ignoring the case, and a `_` in `field_name`
+    /// also matches a space or a `-`, so that `first_name` matches `First Name`.
+    /// A table name as long as the descriptors allow also matches the longer
+    /// names that start with it, as it may have been truncated.
+    pub fn next_field_matches(&mut self, field_name: &str) -> bool {
+        self.next_field_name()
+            .is_some_and(|name| field_name_matches(name, field_name))
+    }
+
     /// Writes the given `field_value` to the record.
     ///
     /// # Notes
@@ -397,11 +721,24 @@ impl<'a, W: Write> FieldWriter<'a, W> {
             );
 
             self.field_buffer.set_position(0);
-            field_value
-                .write_as(field_info, self.encoding, &mut self.field_buffer)
-                .map_err(|kind| FieldIOError::new(kind, Some(field_info.clone())))?;
+            match field_value.write_as(field_info, self.encoding, &mut self.field_buffer) {
+                Ok(()) => {}
+                Err(ErrorKind::NumericOverflow { .. })
+                    if self.numeric_overflow == NumericOverflowPolicy::FillWithAsterisks
+                        && matches!(
+                            field_info.field_type,
+                            FieldType::Numeric | FieldType::Float
+                        ) =>
+                {
+                    let asterisks = vec![b'*'; field_info.record_length()];
+                    return self.dst.write_all(&asterisks).map_err(|error| {
+                        FieldIOError::new(ErrorKind::IoError(error), Some(field_info.clone()))
+                    });
+                }
+                Err(kind) => return Err(FieldIOError::new(kind, Some(field_info.clone()))),
+            }
             let value_len = self.field_buffer.position() as usize;
-            let bytes_to_pad = usize::from(field_info.field_length).saturating_sub(value_len);
+            let bytes_to_pad = field_info.record_length().saturating_sub(value_len);
 
             if bytes_to_pad > 0 && pad_before {
                 self.write_pad(bytes_to_pad, field_info)?;
@@ -409,7 +746,7 @@ impl<'a, W: Write> FieldWriter<'a, W> {
 
             // If the current field value size exceeds the one one set
             // when creating the writer, it will be cropped
-            let write_len = value_len.min(field_info.field_length as usize);
+            let write_len = value_len.min(field_info.record_length());
             let field_bytes = self.field_buffer.get_ref();
             self.dst
                 .write_all(&field_bytes[..write_len])
@@ -444,23 +781,23 @@ impl<'a, W: Write> FieldWriter<'a, W> {
                 FieldType::Numeric | FieldType::Float | FieldType::Memo
             );
 
-            if value.len() == field_info.field_length as usize {
+            if value.len() == field_info.record_length() {
                 self.dst.write_all(value).map_err(|error| {
                     FieldIOError::new(ErrorKind::IoError(error), Some(field_info.clone()))
                 })?;
-            } else if value.len() < field_info.field_length as usize {
+            } else if value.len() < field_info.record_length() {
                 if pad_before {
-                    self.write_pad(field_info.field_length as usize - value.len(), field_info)?;
+                    self.write_pad(field_info.record_length() - value.len(), field_info)?;
                 }
                 self.dst.write_all(value).map_err(|error| {
                     FieldIOError::new(ErrorKind::IoError(error), Some(field_info.clone()))
                 })?;
                 if !pad_before {
-                    self.write_pad(field_info.field_length as usize - value.len(), field_info)?;
+                    self.write_pad(field_info.record_length() - value.len(), field_info)?;
                 }
             } else {
                 self.dst
-                    .write_all(&value[..field_info.field_length as usize])
+                    .write_all(&value[..field_info.record_length()])
                     .map_err(|error| {
                         FieldIOError::new(ErrorKind::IoError(error), Some(field_info.clone()))
                     })?;
@@ -484,16 +821,32 @@ impl<'a, W: Write> FieldWriter<'a, W> {
 ///
 /// The only way to create a TableWriter is to use its
 /// [TableWriterBuilder](struct.TableWriterBuilder.html)
+///
+/// The number of records is written in the header when the writer is finished,
+/// call [TableWriter::finish] to handle the errors that can happen at that moment.
+#[must_use = "the header is only complete once the writer is finished, use `finish`"]
 pub struct TableWriter<W: Write + Seek> {
-    dst: W,
-    fields_info: Vec<FieldInfo>,
+    /// Only None once the writer is finished
+    dst: Option<W>,
+    pub(crate) fields_info: Vec<FieldInfo>,
     /// contains the header of the input file
     /// if this writer was created form a reader
     header: Header,
-    /// Buffer used by the FieldWriter
-    buffer: [u8; 255],
+    /// Buffer used by the FieldWriter, large enough for the largest field
+    buffer: Vec<u8>,
+    /// Buffer where a record is written before being written to the destination
+    record_buffer: Vec<u8>,
     closed: bool,
     encoding: DynEncoding,
+    numeric_overflow: NumericOverflowPolicy,
+}
+
+/// Size of the buffer fields are written to, Character fields can be longer than 255 bytes
+pub(crate) fn field_buffer_size(fields_info: &[FieldInfo]) -> usize {
+    fields_info
+        .iter()
+        .map(FieldInfo::record_length)
+        .fold(255, usize::max)
 }
 
 impl<W: Write + Seek> TableWriter<W> {
@@ -502,14 +855,18 @@ impl<W: Write + Seek> TableWriter<W> {
         fields_info: Vec<FieldInfo>,
         origin_header: Header,
         encoding: DynEncoding,
+        numeric_overflow: NumericOverflowPolicy,
     ) -> Self {
+        let buffer = vec![0u8; field_buffer_size(&fields_info)];
         Self {
-            dst,
+            dst: Some(dst),
             fields_info,
             header: origin_header,
-            buffer: [0u8; 255],
+            buffer,
+            record_buffer: Vec::new(),
             closed: false,
             encoding,
+            numeric_overflow,
         }
     }
 
@@ -521,41 +878,55 @@ impl<W: Write + Seek> TableWriter<W> {
     /// use std::convert::TryFrom;
     ///
     /// # fn main() -> Result<(), dbase::Error> {
+    /// let path = std::env::temp_dir().join("records.dbf");
     /// let mut writer = dbase::TableWriterBuilder::new()
     ///     .add_character_field(dbase::FieldName::try_from("First Name").unwrap(), 50)
-    ///     .build_with_file_dest("records.dbf")?;
+    ///     .build_with_file_dest(&path)?;
     ///
     /// let mut record = dbase::Record::default();
     /// record.insert("First Name".to_string(), dbase::FieldValue::Character(Some("Yoshi".to_string())));
     ///
     /// writer.write_record(&record)?;
     ///
-    /// # let ignored_result = std::fs::remove_file("record.dbf");
+    /// # drop(writer);
+    /// # let ignored_result = std::fs::remove_file(path);
     /// Ok(())
     /// # }
     /// ```
     pub fn write_record<R: WritableRecord>(&mut self, record: &R) -> Result<(), Error> {
-        if self.header.num_records == 0 {
-            // reserve the header
+        self.write_record_with(|field_writer| record.write_using(field_writer))
+    }
+
+    /// Writes a record whose fields are written by `write_fields`
+    fn write_record_with<F>(&mut self, write_fields: F) -> Result<(), Error>
+    where
+        F: FnOnce(&mut FieldWriter<'_, Vec<u8>>) -> Result<(), FieldIOError>,
+    {
+        let current_record_num = self.header.num_records as usize;
+        if current_record_num == 0 {
+            // reserve the header, it is written again if writing the first record failed
+            self.dst()
+                .seek(SeekFrom::Start(0))
+                .map_err(|error| Error::io_error(error, 0))?;
             self.write_header()?;
         }
 
+        // The record is first written in memory so that nothing is written
+        // to the destination if the record is not valid
+        self.record_buffer.clear();
         let mut field_writer = FieldWriter {
-            dst: &mut self.dst,
+            dst: &mut self.record_buffer,
             fields_info: self.fields_info.iter().peekable(),
             field_buffer: &mut Cursor::new(&mut self.buffer),
             encoding: &self.encoding,
+            numeric_overflow: self.numeric_overflow,
         };
 
-        let current_record_num = self.header.num_records as usize;
-
         field_writer
             .write_deletion_flag()
             .map_err(|error| Error::io_error(error, current_record_num))?;
 
-        record
-            .write_using(&mut field_writer)
-            .map_err(|error| Error::new(error, current_record_num))?;
+        write_fields(&mut field_writer).map_err(|error| Error::new(error, current_record_num))?;
 
         if !field_writer.all_fields_were_written() {
             return Err(Error {
@@ -565,6 +936,9 @@ impl<W: Write + Seek> TableWriter<W> {
             });
         }
 
+        let dst = self.dst.as_mut().expect("the writer is not finished");
+        dst.write_all(&self.record_buffer)
+            .map_err(|error| Error::io_error(error, current_record_num))?;
         self.header.num_records += 1;
         Ok(())
     }
@@ -614,36 +988,182 @@ impl<W: Write + Seek> TableWriter<W> {
         Ok(())
     }
 
+    /// Writes the current number of records in the header and flushes the destination
+    ///
+    /// The file terminator is not written, it is written by [TableWriter::finish].
+    pub fn flush(&mut self) -> Result<(), Error> {
+        let num_records = self.header.num_records as usize;
+        // Without records, the header is not yet reserved
+        if num_records != 0 {
+            self.update_header()?;
+        }
+        self.dst()
+            .flush()
+            .map_err(|error| Error::io_error(error, num_records))
+    }
+
+    /// Finishes writing: writes the header and the file terminator,
+    /// flushes the destination and returns it
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    /// use std::io::Cursor;
+    ///
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let mut writer = dbase::TableWriterBuilder::new()
+    ///     .add_character_field(dbase::FieldName::try_from("Name").unwrap(), 10)
+    ///     .build_with_dest(Cursor::new(Vec::<u8>::new()));
+    ///
+    /// let mut record = dbase::Record::default();
+    /// record.insert("Name".to_string(), dbase::FieldValue::Character(Some("Yoshi".to_string())));
+    /// writer.write_record(&record)?;
+    ///
+    /// let cursor = writer.finish()?;
+    /// let records = dbase::Reader::new(Cursor::new(cursor.into_inner()))?.read()?;
+    /// assert_eq!(records, vec![record]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn finish(mut self) -> Result<W, Error> {
+        self.close()?;
+        let num_records = self.header.num_records as usize;
+        let mut dst = self.dst.take().expect("the writer is not finished");
+        dst.flush()
+            .map_err(|error| Error::io_error(error, num_records))?;
+        Ok(dst)
+    }
+
     /// Close the writer
     ///
     /// Automatically closed when the writer is dropped,
-    /// use it if you want to handle error that can happen when the writer is closing
+    /// use it (or [TableWriter::finish]) if you want to handle error that can happen
+    /// when the writer is closing
     ///
     /// Calling close on an already closed writer is a no-op
     pub fn close(&mut self) -> Result<(), Error> {
         if !self.closed {
-            self.dst
-                .seek(SeekFrom::Start(0))
-                .map_err(|error| Error::io_error(error, self.header.num_records as usize))?;
-            self.write_header()?;
-            self.dst
-                .seek(SeekFrom::End(0))
-                .map_err(|error| Error::io_error(error, self.header.num_records as usize))?;
-            self.dst
+            self.update_header()?;
+            let num_records = self.header.num_records as usize;
+            self.dst()
                 .write_u8(FILE_TERMINATOR)
-                .map_err(|error| Error::io_error(error, self.header.num_records as usize))?;
+                .map_err(|error| Error::io_error(error, num_records))?;
             self.closed = true;
         }
         Ok(())
     }
 
+    /// Rewrites the header at the start of the destination,
+    /// and goes back to the end
+    fn update_header(&mut self) -> Result<(), Error> {
+        let num_records = self.header.num_records as usize;
+        self.dst()
+            .seek(SeekFrom::Start(0))
+            .map_err(|error| Error::io_error(error, num_records))?;
+        self.write_header()?;
+        self.dst()
+            .seek(SeekFrom::End(0))
+            .map_err(|error| Error::io_error(error, num_records))?;
+        Ok(())
+    }
+
     fn write_header(&mut self) -> Result<(), Error> {
-        write_header_parts(&mut self.dst, &self.header, &self.fields_info)
+        let dst = self.dst.as_mut().expect("the writer is not finished");
+        write_header_parts(dst, &self.header, &self.fields_info)
+    }
+
+    fn dst(&mut self) -> &mut W {
+        self.dst.as_mut().expect("the writer is not finished")
+    }
+}
+
+/// Writer of records given as values, in the order of the fields
+///
+/// Created by [TableWriterBuilder::build_prepared], which validates the fields
+/// and writes the header once. Records are then pushed as slices of values,
+/// which avoids looking up each field by name as [TableWriter::write_record]
+/// does with a [Record].
+///
+/// # Example
+///
+/// ```
+/// use std::convert::TryFrom;
+/// use std::io::Cursor;
+/// use dbase::{FieldName, FieldValue, TableWriterBuilder};
+///
+/// # fn main() -> Result<(), dbase::Error> {
+/// let mut writer = TableWriterBuilder::new()
+///     .add_character_field(FieldName::try_from("Name").unwrap(), 10)
+///     .add_integer_field(FieldName::try_from("Count").unwrap())
+///     .build_prepared(Cursor::new(Vec::<u8>::new()))?;
+/// for i in 0..100 {
+///     writer.push(&[
+///         FieldValue::Character(Some(format!("item {}", i))),
+///         FieldValue::Integer(i),
+///     ])?;
+/// }
+/// let cursor = writer.finish()?;
+///
+/// let mut reader = dbase::Reader::new(Cursor::new(cursor.into_inner()))?;
+/// assert_eq!(reader.header().num_records, 100);
+/// # Ok(())
+/// # }
+/// ```
+#[must_use = "the header is only complete once the writer is finished, use `finish`"]
+pub struct PreparedWriter<W: Write + Seek> {
+    writer: TableWriter<W>,
+}
+
+impl<W: Write + Seek> PreparedWriter<W> {
+    /// Writes a record made of the values, in the order of the fields
+    ///
+    /// Fails if there are not exactly as many values as there are fields,
+    /// or if a value cannot be written as the type of its field.
+    pub fn push(&mut self, values: &[FieldValue]) -> Result<(), Error> {
+        let num_fields = self.writer.fields_info.len();
+        if values.len() != num_fields {
+            return Err(Error {
+                record_num: self.num_records(),
+                field: None,
+                kind: if values.len() < num_fields {
+                    ErrorKind::NotEnoughFields
+                } else {
+                    ErrorKind::TooManyFields
+                },
+            });
+        }
+        self.writer.write_record_with(|field_writer| {
+            for value in values {
+                debug_assert_eq!(
+                    Some(field_writer.dst.len()),
+                    field_writer
+                        .fields_info
+                        .peek()
+                        .map(|info| usize::from(info.offset_within_record())),
+                    "the value is not written at the offset of its field"
+                );
+                field_writer.write_next_field_value(value)?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Returns the number of records written so far
+    pub fn num_records(&self) -> usize {
+        self.writer.header.num_records as usize
+    }
+
+    /// Finishes writing, see [TableWriter::finish]
+    pub fn finish(self) -> Result<W, Error> {
+        self.writer.finish()
     }
 }
 
 impl<T: Write + Seek> Drop for TableWriter<T> {
     fn drop(&mut self) {
-        let _ = self.close();
+        if self.dst.is_some() {
+            let _ = self.close();
+        }
     }
 }
@@ -0,0 +1,23 @@
+//! Tests that do not use the filesystem, so that they also run on `wasm32` targets,
+//! with `wasm-pack test --node -- --test in_memory`
+use dbase::{FieldValue, Reader};
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+
+const LINE_DBF: &[u8] = include_bytes!("data/line.dbf");
+
+#[test]
+fn read_from_byte_array() -> Result<(), dbase::Error> {
+    let mut reader = Reader::from_bytes(LINE_DBF)?;
+    assert_eq!(reader.fields()[0].name(), "name");
+    let records = reader.read()?;
+    assert_eq!(records.len(), 1);
+    assert_eq!(
+        records[0].get("name"),
+        Some(&FieldValue::Character(Some("linestring1".to_string())))
+    );
+
+    let mut reader = Reader::from_vec(LINE_DBF.to_vec())?;
+    assert_eq!(reader.read()?, records);
+    Ok(())
+}
@@ -0,0 +1,83 @@
+//! Tests of the parsing of files held in memory, which is what remains available
+//! without the `std` feature: `cargo test --no-default-features --test no_std`
+use dbase::{ErrorKind, FieldValue, TableView};
+
+const STATIONS_DBF: &[u8] = include_bytes!("data/stations.dbf");
+const STATIONS_WITH_DELETED_DBF: &[u8] = include_bytes!("data/stations_with_deleted.dbf");
+
+#[test]
+fn view_fields_and_records() -> Result<(), dbase::Error> {
+    let view = TableView::new(STATIONS_DBF)?;
+    let names = view.fields().iter().map(|f| f.name()).collect::<Vec<_>>();
+    assert_eq!(names, ["name", "marker-col", "marker-sym", "line"]);
+    assert_eq!(view.num_records(), 86);
+
+    let values = view.read_record(0)?.unwrap();
+    assert_eq!(
+        values,
+        [
+            FieldValue::Character(Some("Van Dorn Street".to_string())),
+            FieldValue::Character(Some("#0000ff".to_string())),
+            FieldValue::Character(Some("rail-metro".to_string())),
+            FieldValue::Character(Some("blue".to_string())),
+        ]
+    );
+    assert_eq!(view.records().count(), 86);
+    Ok(())
+}
+
+#[test]
+fn view_skips_deleted_records() -> Result<(), dbase::Error> {
+    let view = TableView::new(STATIONS_WITH_DELETED_DBF)?;
+    assert_eq!(view.num_records(), 6);
+    assert!(view.read_record(0)?.is_none());
+    assert_eq!(view.record_bytes(0)?[0], b'*');
+
+    let records = view.records().collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(records.len(), 5);
+    assert_eq!(
+        records[0][0],
+        FieldValue::Character(Some("Franconia-Springfield".to_string()))
+    );
+    Ok(())
+}
+
+#[test]
+fn view_of_truncated_data() -> Result<(), dbase::Error> {
+    let error = TableView::new(&STATIONS_DBF[..20]).err().unwrap();
+    assert!(matches!(error.kind(), ErrorKind::TruncatedHeader));
+    let error = TableView::new(&STATIONS_DBF[..100]).err().unwrap();
+    assert!(matches!(error.kind(), ErrorKind::TruncatedHeader));
+
+    // The first record starts at byte 161 and is 1017 bytes long
+    let view = TableView::new(&STATIONS_DBF[..1000])?;
+    let error = view.read_record(0).unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        ErrorKind::TruncatedRecord { index: 0 }
+    ));
+    let error = view.read_record(86).unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        ErrorKind::RecordIndexOutOfRange {
+            index: 86,
+            num_records: 86
+        }
+    ));
+    Ok(())
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn view_same_as_reader() -> Result<(), dbase::Error> {
+    let view = TableView::new(STATIONS_WITH_DELETED_DBF)?;
+    let records = dbase::Reader::from_bytes(STATIONS_WITH_DELETED_DBF)?.read()?;
+    let values = view.records().collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(values.len(), records.len());
+    for (values, record) in values.iter().zip(&records) {
+        for (value, field) in values.iter().zip(view.fields()) {
+            assert_eq!(Some(value), record.get(field.name()));
+        }
+    }
+    Ok(())
+}
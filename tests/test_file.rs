@@ -357,3 +357,229 @@ fn test_file_is_record_deleted() -> Result<(), Box<dyn std::error::Error>> {
     assert!(!is_second_record_deleted);
     Ok(())
 }
+
+#[test]
+fn test_file_pack() -> Result<(), Box<dyn std::error::Error>> {
+    dbase::dbase_record! {
+        #[derive(Clone, Debug, PartialEq)]
+        struct User {
+            first_name: String,
+        }
+    }
+
+    let users = ["Ferrys", "Alex", "Jamie", "Kim", "Sam"]
+        .iter()
+        .map(|name| User {
+            first_name: name.to_string(),
+        })
+        .collect::<Vec<_>>();
+
+    let mut cursor = std::io::Cursor::new(Vec::<u8>::new());
+    let table_info = dbase::TableWriterBuilder::new()
+        .add_character_field("First Name".try_into().unwrap(), 20)
+        .build_table_info();
+    {
+        let mut file = dbase::File::create_new(&mut cursor, table_info)?;
+        file.append_records(&users)?;
+    }
+
+    // Mark the 2nd and 4th records as deleted
+    let bytes = cursor.get_mut();
+    let offset_to_first_record = u16::from_le_bytes([bytes[8], bytes[9]]) as usize;
+    let size_of_record = u16::from_le_bytes([bytes[10], bytes[11]]) as usize;
+    for index in [1, 3].iter() {
+        bytes[offset_to_first_record + index * size_of_record] = b'*';
+    }
+    cursor.set_position(0);
+
+    {
+        let mut file = dbase::File::open(&mut cursor)?;
+        file.pack()?;
+        assert_eq!(file.num_records(), 3);
+        assert_eq!(file.record(1).unwrap().read_as::<User>()?, users[2]);
+    }
+
+    cursor.set_position(0);
+    let mut reader = dbase::Reader::new(cursor)?;
+    assert_eq!(reader.count_records()?, (3, 0));
+    let read_records = reader.read_as::<User>()?;
+    assert_eq!(
+        read_records,
+        vec![users[0].clone(), users[2].clone(), users[4].clone()]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_file_zap() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp_file = copy_to_named_tmp_file("tests/data/stations.dbf")?;
+
+    {
+        let mut file = dbase::File::open_read_write(tmp_file.path())?;
+        let fields = file.fields().to_vec();
+        file.zap()?;
+
+        assert_eq!(file.num_records(), 0);
+        assert_eq!(file.fields(), fields.as_slice());
+        assert!(file.records().next().is_none());
+    }
+
+    {
+        let mut file = dbase::File::open_read_only(tmp_file.path())?;
+        assert_eq!(file.num_records(), 0);
+        assert_eq!(file.fields().len(), 4);
+        assert!(file.records().next().is_none());
+    }
+
+    let mut reader = dbase::Reader::from_path(tmp_file.path())?;
+    assert!(reader.read()?.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_file_add_column() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp_file = copy_to_named_tmp_file("tests/data/stations.dbf")?;
+    let original_records = dbase::read("tests/data/stations.dbf")?;
+
+    {
+        let mut file = dbase::File::open_read_write(tmp_file.path())?;
+        let info = dbase::FieldInfo::new(
+            "comment".try_into().unwrap(),
+            dbase::FieldType::Character,
+            10,
+        );
+        file.add_column(info, &dbase::FieldValue::Character(None))?;
+        assert_eq!(file.fields().len(), 5);
+        assert_eq!(
+            file.record(1).unwrap().read()?.get("comment"),
+            Some(&dbase::FieldValue::Character(None))
+        );
+
+        let info = dbase::FieldInfo::new("line".try_into().unwrap(), dbase::FieldType::Logical, 1);
+        let error = file
+            .add_column(info, &dbase::FieldValue::Logical(None))
+            .unwrap_err();
+        assert!(matches!(
+            error.kind(),
+            dbase::ErrorKind::DuplicateFieldName { name } if name == "line"
+        ));
+    }
+
+    let mut reader = dbase::Reader::from_path(tmp_file.path())?;
+    let header = *reader.header();
+    assert_eq!(header.size_of_record, 1017 + 10);
+    assert_eq!(header.offset_to_first_record, 161 + 32);
+    assert_eq!(reader.fields().last().unwrap().name(), "comment");
+
+    let records = reader.read()?;
+    assert_eq!(records.len(), original_records.len());
+    for (mut record, original) in records.into_iter().zip(original_records) {
+        assert_eq!(
+            record.remove("comment"),
+            Some(dbase::FieldValue::Character(None))
+        );
+        assert_eq!(record, original);
+    }
+
+    // The new field is padded with spaces in all the records
+    let bytes = std::fs::read(tmp_file.path())?;
+    let offset = header.offset_to_first_record as usize;
+    let size = header.size_of_record as usize;
+    for index in 0..header.num_records as usize {
+        let record_end = offset + (index + 1) * size;
+        assert_eq!(&bytes[record_end - 10..record_end], b"          ");
+    }
+    assert_eq!(bytes[offset + header.num_records as usize * size], 0x1A);
+
+    Ok(())
+}
+
+#[test]
+fn test_file_add_column_keeps_the_database_backlink() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp_file = copy_to_named_tmp_file("tests/data/customers.dbf")?;
+    let original_records =
+        dbase::Reader::new(std::fs::File::open("tests/data/customers.dbf")?)?.read()?;
+
+    {
+        let mut file = dbase::File::open_read_write(tmp_file.path())?;
+        let info = dbase::FieldInfo::new("vip".try_into().unwrap(), dbase::FieldType::Logical, 1);
+        file.add_column(info, &dbase::FieldValue::Logical(Some(false)))?;
+    }
+
+    let mut reader = dbase::Reader::new(std::fs::File::open(tmp_file.path())?)?;
+    assert_eq!(
+        reader.database_container_path()?,
+        Some("sales.dbc".to_string())
+    );
+    let records = reader.read()?;
+    assert_eq!(records.len(), original_records.len());
+    for (mut record, original) in records.into_iter().zip(original_records) {
+        assert_eq!(
+            record.remove("vip"),
+            Some(dbase::FieldValue::Logical(Some(false)))
+        );
+        assert_eq!(record, original);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_file_flush_header() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp_file = copy_to_named_tmp_file(STATIONS_WITH_DELETED)?;
+    let original_records = dbase::read(tmp_file.path())?;
+    let original_size = std::fs::metadata(tmp_file.path())?.len();
+
+    {
+        let mut file = dbase::File::open_read_write(tmp_file.path())?;
+        assert!(!matches!(
+            file.header().code_page_mark,
+            dbase::CodePageMark::CP1252
+        ));
+        let header = file.header_mut();
+        header.code_page_mark = dbase::CodePageMark::CP1252;
+        header.last_update = dbase::Date::new(1, 2, 2003);
+        file.flush_header()?;
+        // The file can still be read after the header was written
+        assert!(!file.record(1).unwrap().is_deleted()?);
+    }
+
+    let mut reader = dbase::Reader::from_path(tmp_file.path())?;
+    assert!(matches!(
+        reader.header().code_page_mark,
+        dbase::CodePageMark::CP1252
+    ));
+    assert_eq!(reader.header().last_update, dbase::Date::new(1, 2, 2003));
+    assert_eq!(reader.read()?, original_records);
+    assert_eq!(std::fs::metadata(tmp_file.path())?.len(), original_size);
+
+    Ok(())
+}
+
+#[test]
+fn test_file_write_wide_character_record() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp_file = copy_to_named_tmp_file("tests/data/wide_character.dbf")?;
+    let long_note = format!("{}end", "y".repeat(287));
+    {
+        let mut file = dbase::File::open_read_write(tmp_file.path())?;
+        let mut record_ref = file.record(1).unwrap();
+        let mut record = record_ref.read()?;
+        record.insert(
+            "NOTE".to_string(),
+            dbase::FieldValue::Character(Some(long_note.clone())),
+        );
+        record_ref.write(&record)?;
+    }
+
+    let records = dbase::read(tmp_file.path())?;
+    assert_eq!(
+        records[1].get("NOTE"),
+        Some(&dbase::FieldValue::Character(Some(long_note)))
+    );
+    assert_eq!(
+        records[1].get("FLAG"),
+        Some(&dbase::FieldValue::Logical(Some(false)))
+    );
+    Ok(())
+}
@@ -214,7 +214,7 @@ mod serde_tests {
                 dbase::Date::new(12, 05, 2130),
                 dbase::Time::new(15, 52, 12),
             ),
-            currency: 79841.156846,
+            currency: 79841.1568,
             double: 976114.1846,
             integer: -15315,
         }];
@@ -17,6 +17,16 @@ const STATIONS: &str = "./tests/data/stations.dbf";
 const STATIONS_WITH_DELETED: &str = "./tests/data/stations_with_deleted.dbf";
 #[cfg(feature = "yore")]
 const CP850_DBF: &str = "tests/data/cp850.dbf";
+const LEVEL7_DBF: &str = "tests/data/level7.dbf";
+const INDEXED_DBF: &str = "tests/data/indexed.dbf";
+const INDEXED_CITY_NDX: &str = "tests/data/indexed_city.ndx";
+const INDEXED_CODE_NDX: &str = "tests/data/indexed_code.ndx";
+const INDEXED_MDX: &str = "tests/data/indexed.mdx";
+const INDEXED_CDX: &str = "tests/data/indexed.cdx";
+const PICTURES_DBF: &str = "tests/data/pictures.dbf";
+const WIDE_CHARACTER_DBF: &str = "tests/data/wide_character.dbf";
+const CUSTOMERS_DBF: &str = "tests/data/customers.dbf";
+const SALES_DBC: &str = "tests/data/sales.dbc";
 const SJIS_DBF :&str = "tests/data/N01-07L-2K-10_Road.dbf";
 
 fn write_read_compare<R>(records: &Vec<R>, writer_builder: TableWriterBuilder)
@@ -90,6 +100,24 @@ fn test_read_write_simple_file() {
     assert_eq!(records[0], expected_fields);
 }
 
+#[test]
+fn test_rewrite_preserves_header_flags() -> Result<(), Box<dyn std::error::Error>> {
+    let original = std::fs::read(LINE_DBF)?;
+
+    let mut reader = dbase::Reader::from_path(LINE_DBF)?;
+    let records = reader.read()?;
+    let mut dst = Cursor::new(Vec::<u8>::new());
+    let writer = TableWriterBuilder::from_reader(reader).build_with_dest(&mut dst);
+    writer.write_records(&records)?;
+
+    let rewritten = dst.into_inner();
+    // version, encryption flag, table flags and code page mark
+    for offset in [0, 15, 28, 29] {
+        assert_eq!(rewritten[offset], original[offset], "byte {}", offset);
+    }
+    Ok(())
+}
+
 #[test]
 fn test_read_numeric_value_null_padded() {
     let records = dbase::read(NULL_PADDED_NUMERIC_DBF).unwrap();
@@ -178,7 +206,7 @@ fn from_scratch_fox_pro_record() {
     let mut record = Record::default();
     record.insert(String::from("integer"), FieldValue::Integer(17));
     record.insert(String::from("double"), FieldValue::Double(54621.154));
-    record.insert(String::from("currency"), FieldValue::Currency(4567.134));
+    record.insert(String::from("currency"), FieldValue::Currency(45_671_340));
     record.insert(
         String::from("datetime"),
         FieldValue::DateTime(DateTime::new(
@@ -212,7 +240,7 @@ fn from_scratch_fox_pro_struct_record() {
     let records = vec![FoxProRecord {
         datetime: DateTime::new(Date::new(12, 02, 1999), Time::new(21, 20, 35)),
         double: 8649.48851,
-        currency: 3489.9612314,
+        currency: 3489.9612,
         integer: 42069,
     }];
 
@@ -297,6 +325,63 @@ fn non_unicode_codepages() {
     );
 }
 
+#[cfg(feature = "yore")]
+#[test]
+fn test_transcode_cp1251() -> Result<(), dbase::Error> {
+    let text = "Привет, мир";
+    let cp1251_table = |name_length| -> Result<Reader<Cursor<Vec<u8>>>, dbase::Error> {
+        let mut writer = TableWriterBuilder::new()
+            .set_encoding(yore::code_pages::CP1251)
+            .add_character_field(FieldName::try_from("name").unwrap(), name_length)
+            .build_with_dest(Cursor::new(Vec::<u8>::new()));
+        let mut record = dbase::Record::default();
+        record.insert(
+            "name".to_string(),
+            FieldValue::Character(Some(text.to_string())),
+        );
+        writer.write_record(&record)?;
+        let data = writer.finish()?.into_inner();
+        Reader::new(Cursor::new(data))
+    };
+
+    // Cyrillic letters are not in CP1252
+    let mut reader = cp1251_table(20)?;
+    let error = dbase::transcode(
+        &mut reader,
+        Cursor::new(Vec::<u8>::new()),
+        yore::code_pages::CP1252,
+    )
+    .unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        dbase::ErrorKind::StringEncodeError(_)
+    ));
+    assert_eq!(error.field().as_ref().map(|f| f.name()), Some("name"));
+
+    let cursor = dbase::transcode(&mut reader, Cursor::new(Vec::<u8>::new()), dbase::Unicode)?;
+    let mut reader = Reader::new(Cursor::new(cursor.into_inner()))?;
+    assert!(matches!(
+        reader.header().code_page_mark,
+        dbase::CodePageMark::Utf8
+    ));
+    let records = reader.read()?;
+    assert_eq!(records.len(), 1);
+    assert_eq!(
+        records[0].get("name"),
+        Some(&FieldValue::Character(Some(text.to_string())))
+    );
+
+    // The 11 characters are 20 bytes long in UTF-8
+    let mut reader = cp1251_table(15)?;
+    let error =
+        dbase::transcode(&mut reader, Cursor::new(Vec::<u8>::new()), dbase::Unicode).unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        dbase::ErrorKind::InvalidFieldValue(value) if value == text
+    ));
+    Ok(())
+}
+
 #[test]
 fn sjis_codepages() {
     let mut reader =
@@ -392,3 +477,2051 @@ fn test_record_marked_as_deleted_are_skipped_by_reader() -> Result<(), Box<dyn s
 
     Ok(())
 }
+
+#[test]
+fn test_char_trimming_nul_padding() -> Result<(), Box<dyn std::error::Error>> {
+    let mut writer = TableWriterBuilder::new()
+        .add_character_field(FieldName::try_from("name").unwrap(), 8)
+        .build_with_dest(Cursor::new(Vec::<u8>::new()));
+    let mut record = Record::default();
+    record.insert("name".to_string(), FieldValue::Character(None));
+    writer.write_record(&record)?;
+    writer.write_record(&record)?;
+    let mut data = writer.finish()?.into_inner();
+
+    let offset = Reader::new(Cursor::new(data.clone()))?
+        .header()
+        .offset_to_first_record as usize;
+    data[offset + 1..offset + 9].copy_from_slice(b" abc\0\0\0\0");
+    data[offset + 10..offset + 18].copy_from_slice(b" ab \0 x ");
+
+    let expected = [
+        (dbase::TrimOption::Begin, ["abc", "ab "]),
+        (dbase::TrimOption::End, [" abc", " ab"]),
+        (dbase::TrimOption::BeginEnd, ["abc", "ab"]),
+    ];
+    for (trim_option, names) in expected {
+        let mut reader = Reader::new(Cursor::new(data.clone()))?;
+        reader.set_options(dbase::ReadingOptions::default().character_trim(trim_option));
+        let records = reader.read()?;
+        for (record, name) in records.iter().zip(names) {
+            assert_eq!(
+                record.get("name"),
+                Some(&FieldValue::Character(Some(name.to_string())))
+            );
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_char_trimming_custom_bytes() -> Result<(), Box<dyn std::error::Error>> {
+    let mut writer = TableWriterBuilder::new()
+        .add_character_field(FieldName::try_from("name").unwrap(), 8)
+        .build_with_dest(Cursor::new(Vec::<u8>::new()));
+    let mut record = Record::default();
+    record.insert("name".to_string(), FieldValue::Character(None));
+    writer.write_record(&record)?;
+    writer.write_record(&record)?;
+    let mut data = writer.finish()?.into_inner();
+
+    let offset = Reader::new(Cursor::new(data.clone()))?
+        .header()
+        .offset_to_first_record as usize;
+    data[offset + 1..offset + 9].copy_from_slice(b"__a_b___");
+    data[offset + 10..offset + 18].copy_from_slice(b" ab\0\0\0\0\0");
+
+    let expected = [
+        (&b"_"[..], ["a_b", " ab\0\0\0\0\0"]),
+        (&b"_\0"[..], ["a_b", " ab"]),
+        (&b" "[..], ["__a_b___", "ab\0\0\0\0\0"]),
+    ];
+    for (trim_bytes, names) in expected {
+        let mut reader = Reader::new(Cursor::new(data.clone()))?;
+        reader.set_options(dbase::ReadingOptions::default().character_trim_bytes(trim_bytes));
+        let records = reader.read()?;
+        for (record, name) in records.iter().zip(names) {
+            assert_eq!(
+                record.get("name"),
+                Some(&FieldValue::Character(Some(name.to_string())))
+            );
+        }
+    }
+
+    let mut reader = Reader::new(Cursor::new(data))?;
+    reader.set_options(
+        dbase::ReadingOptions::default()
+            .character_trim(dbase::TrimOption::End)
+            .character_trim_bytes(b"_"),
+    );
+    let records = reader.read()?;
+    assert_eq!(
+        records[0].get("name"),
+        Some(&FieldValue::Character(Some("__a_b".to_string())))
+    );
+    Ok(())
+}
+
+#[test]
+fn test_read_dbase7_autoincrement() -> Result<(), Box<dyn std::error::Error>> {
+    let mut reader = dbase::Reader::from_path(LEVEL7_DBF)?;
+    assert_eq!(reader.header().file_type.major(), 7);
+
+    let id_field = &reader.fields()[0];
+    assert_eq!(id_field.field_type(), dbase::FieldType::AutoIncrement);
+    assert_eq!(id_field.autoincrement_next_value(), Some(4));
+    assert_eq!(reader.fields()[1].autoincrement_next_value(), None);
+
+    let records = reader.read()?;
+    assert_eq!(records.len(), 3);
+    assert_eq!(records[0].get("ID"), Some(&FieldValue::Integer(1)));
+    assert_eq!(
+        records[2].get("NAME"),
+        Some(&FieldValue::Character(Some("Charlie".to_string())))
+    );
+
+    let mut dst = Cursor::new(Vec::<u8>::new());
+    let writer = TableWriterBuilder::from_reader(reader).build_with_dest(&mut dst);
+    writer.write_records(&records)?;
+    dst.set_position(0);
+
+    let mut reader = Reader::new(dst)?;
+    assert_eq!(reader.fields()[0].autoincrement_next_value(), Some(4));
+    assert_eq!(reader.read()?, records);
+
+    Ok(())
+}
+
+#[test]
+fn test_read_dbase7_timestamp_and_double() -> Result<(), Box<dyn std::error::Error>> {
+    let mut reader = dbase::Reader::from_path(LEVEL7_DBF)?;
+    assert_eq!(reader.fields()[2].field_type(), dbase::FieldType::Timestamp);
+    assert_eq!(
+        reader.fields()[3].field_type(),
+        dbase::FieldType::DBase7Double
+    );
+
+    let records = reader.read()?;
+    assert_eq!(
+        records[0].get("CREATED"),
+        Some(&FieldValue::DateTime(DateTime::new(
+            Date::new(14, 3, 2021),
+            Time::new(15, 9, 26)
+        )))
+    );
+    assert_eq!(records[0].get("AMOUNT"), Some(&FieldValue::Double(12.5)));
+    assert_eq!(records[1].get("AMOUNT"), Some(&FieldValue::Double(-3.25)));
+    assert_eq!(records[2].get("AMOUNT"), Some(&FieldValue::Double(0.0)));
+
+    // The values expected in the file, computed by hand: dBase 7 stores
+    // the integers in big endian with their sign bit flipped
+    let data = std::fs::read(LEVEL7_DBF)?;
+    let first_record = usize::from(u16::from_le_bytes([data[8], data[9]]));
+    // Deletion flag, ID (4 bytes), NAME (10 bytes)
+    let created = &data[first_record + 15..][..8];
+    // 14 March 2021 is the julian day 2 459 288 = 0x0025_8698
+    assert_eq!(created[..4], [0x80, 0x25, 0x86, 0x98]);
+    // 15:09:26 is (15 * 3600 + 9 * 60 + 26) * 1000 = 54 566 000 = 0x0340_9C70 milliseconds
+    assert_eq!(created[4..], [0x83, 0x40, 0x9C, 0x70]);
+    // 12.5 is 0x4029_0000_0000_0000, positive doubles also have their sign bit flipped
+    let amount = &data[first_record + 23..][..8];
+    assert_eq!(amount, [0xC0, 0x29, 0, 0, 0, 0, 0, 0]);
+
+    Ok(())
+}
+
+#[test]
+fn test_count_records() -> Result<(), Box<dyn std::error::Error>> {
+    let mut reader = dbase::Reader::from_path(STATIONS_WITH_DELETED)?;
+    assert_eq!(reader.count_records()?, (5, 1));
+
+    // Counting does not change where the reader is
+    let records = reader.read_as::<StationRecord>()?;
+    assert_eq!(records.len(), 5);
+
+    let mut reader = dbase::Reader::from_path(STATIONS)?;
+    assert_eq!(reader.count_records()?, (reader.header().num_records, 0));
+
+    Ok(())
+}
+
+#[test]
+fn test_explain() -> Result<(), Box<dyn std::error::Error>> {
+    let mut reader = dbase::Reader::from_path(STATIONS)?;
+    let description = reader.explain();
+    let lines = description.lines().collect::<Vec<_>>();
+    assert_eq!(lines[0], "Version: DBase3 { supports_memo: false }");
+    assert_eq!(lines[2], "Number of records: 86");
+    assert_eq!(lines[3], "Offset to first record: 161");
+    assert_eq!(lines[4], "Record size: 1017");
+    assert_eq!(lines.len(), 6 + reader.fields().len());
+    assert!(lines[6].starts_with("name        C         1    254        0  Van Dorn Street"));
+
+    // Explaining does not change where the reader is
+    assert_eq!(reader.read()?.len(), 86);
+
+    let mut reader = dbase::Reader::from_path(STATIONS_WITH_DELETED)?;
+    let description = reader.explain();
+    assert!(description.contains("First record: missing or deleted"));
+
+    Ok(())
+}
+
+#[test]
+fn test_iter_records_with_bytes() -> Result<(), Box<dyn std::error::Error>> {
+    let original = std::fs::read(STATIONS)?;
+    let mut reader = dbase::Reader::from_path(STATIONS)?;
+    let header = *reader.header();
+    let records_with_bytes = reader
+        .iter_records_with_bytes()
+        .collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(records_with_bytes.len(), header.num_records as usize);
+
+    // Writing the bytes back gives the same record section
+    let start = header.offset_to_first_record as usize;
+    let mut rewritten = original[..start].to_vec();
+    for (bytes, _) in &records_with_bytes {
+        assert_eq!(bytes.len(), header.size_of_record as usize);
+        rewritten.extend_from_slice(bytes);
+    }
+    let end = rewritten.len();
+    assert_eq!(&rewritten[start..], &original[start..end]);
+
+    let mut reader = dbase::Reader::new(Cursor::new(rewritten))?;
+    let records = records_with_bytes
+        .into_iter()
+        .map(|(_, record)| record)
+        .collect::<Vec<_>>();
+    assert_eq!(reader.read()?, records);
+
+    Ok(())
+}
+
+#[test]
+fn test_collect_column() -> Result<(), Box<dyn std::error::Error>> {
+    let mut reader = dbase::Reader::from_path(NONE_FLOAT_DBF)?;
+    assert_eq!(reader.collect_column::<f64>("value_n")?, vec![Some(4.0)]);
+
+    let mut reader = dbase::Reader::from_path(NONE_FLOAT_DBF)?;
+    assert_eq!(reader.collect_column::<f64>("value_n_non")?, vec![None]);
+
+    let mut reader = dbase::Reader::from_path(NONE_FLOAT_DBF)?;
+    let error = reader.collect_column::<f64>("not_a_field").unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        dbase::ErrorKind::UnknownFieldName(name) if name == "not_a_field"
+    ));
+
+    let mut reader = dbase::Reader::from_path(STATIONS)?;
+    let names = reader.collect_column::<String>("name")?;
+    assert_eq!(names.len(), reader.header().num_records as usize);
+    assert_eq!(names[0].as_deref(), Some("Van Dorn Street"));
+
+    let mut reader = dbase::Reader::from_path(STATIONS)?;
+    assert_eq!(reader.collect_column::<String>("NAME")?, names);
+
+    Ok(())
+}
+
+#[test]
+fn test_structural_index_is_detected() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempfile::tempdir()?;
+    let dbf_path = dir.path().join("line.dbf");
+    let mut data = std::fs::read(LINE_DBF)?;
+    // The table flags are at byte 28 of the header
+    data[28] = dbase::TableFlags::new(true, false, false).to_byte();
+    std::fs::write(&dbf_path, &data)?;
+
+    let reader = Reader::from_path(&dbf_path)?;
+    assert!(reader.has_structural_index());
+    assert!(reader.structural_index().is_none());
+
+    let cdx_path = dir.path().join("line.cdx");
+    std::fs::write(&cdx_path, [])?;
+    let reader = Reader::from_path(&dbf_path)?;
+    assert_eq!(
+        reader.structural_index().map(|i| i.path()),
+        Some(cdx_path.as_path())
+    );
+
+    let reader = Reader::from_path(LINE_DBF)?;
+    assert!(!reader.has_structural_index());
+    assert!(reader.structural_index().is_none());
+    Ok(())
+}
+
+#[test]
+fn test_find_records_using_cdx_index() -> Result<(), Box<dyn std::error::Error>> {
+    let mut reader = Reader::from_path(INDEXED_DBF)?;
+    let records = reader.read()?;
+    let mut index = reader.structural_index().unwrap().open()?;
+
+    for (expected_index, record) in records.iter().enumerate() {
+        let code = match record.get("CODE") {
+            Some(FieldValue::Character(Some(code))) => code,
+            _ => panic!("CODE should be a non empty character field"),
+        };
+        assert_eq!(
+            index.find("CODE", code.as_bytes())?,
+            vec![expected_index as u32]
+        );
+    }
+    assert!(index.find("CODE", b"C99999")?.is_empty());
+    assert!(index.find("CODE", b"too long to be a code")?.is_empty());
+
+    // Duplicated keys span multiple leaves
+    let lyon = index.find("CITY", b"Lyon")?;
+    let expected = records
+        .iter()
+        .enumerate()
+        .filter(|(_, r)| r.get("CITY") == Some(&FieldValue::Character(Some("Lyon".to_string()))))
+        .map(|(i, _)| i as u32)
+        .collect::<Vec<_>>();
+    assert_eq!(lyon, expected);
+    assert!(index.find("CITY", b"Bordeaux")?.is_empty());
+
+    assert!(matches!(
+        index.find("NAME", b"Lyon").unwrap_err().kind(),
+        dbase::ErrorKind::UnknownIndexTag(_)
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_find_records_using_ndx_index() -> Result<(), Box<dyn std::error::Error>> {
+    let mut reader = Reader::from_path(INDEXED_DBF)?;
+    let records = reader.read()?;
+
+    let mut code_index = dbase::NdxReader::open(INDEXED_CODE_NDX)?;
+    assert!(code_index.header().is_unique());
+    for (expected_index, record) in records.iter().enumerate() {
+        let code = match record.get("CODE") {
+            Some(FieldValue::Character(Some(code))) => code,
+            _ => panic!("CODE should be a non empty character field"),
+        };
+        let index = code_index.seek_to_key(code.as_bytes())?;
+        assert_eq!(index, Some(expected_index as u32));
+        assert_eq!(reader.read_record(expected_index)?.as_ref(), Some(record));
+    }
+    assert_eq!(code_index.seek_to_key(b"C99999")?, None);
+
+    let mut city_index = dbase::NdxReader::open(INDEXED_CITY_NDX)?;
+    let city_of = |index: u32| match records[index as usize].get("CITY") {
+        Some(FieldValue::Character(Some(city))) => city.clone(),
+        _ => panic!("CITY should be a non empty character field"),
+    };
+    let cities = city_index
+        .range(b"Lille", b"Marseille")
+        .map(|index| index.map(city_of))
+        .collect::<Result<Vec<String>, dbase::Error>>()?;
+    // Lille, Lyon and Marseille
+    assert_eq!(cities.len(), 65);
+    assert!(cities.windows(2).all(|w| w[0] <= w[1]));
+    assert_eq!(cities.first().map(String::as_str), Some("Lille"));
+    assert_eq!(cities.last().map(String::as_str), Some("Marseille"));
+    Ok(())
+}
+
+fn check_index_lookups<I: dbase::Index>(
+    mut index: I,
+    records: &[Record],
+    field_name: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    for record in records {
+        let value = match record.get(field_name) {
+            Some(FieldValue::Character(Some(value))) => value,
+            _ => panic!("{} should be a non empty character field", field_name),
+        };
+        let found = index.find(value.as_bytes())?;
+        let expected = records
+            .iter()
+            .enumerate()
+            .filter(|(_, r)| r.get(field_name) == record.get(field_name))
+            .map(|(i, _)| i as u32)
+            .collect::<Vec<_>>();
+        assert_eq!(found, expected);
+    }
+    assert!(index.find(b"Not a key")?.is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_index_formats_find_the_same_records() -> Result<(), Box<dyn std::error::Error>> {
+    let records = Reader::from_path(INDEXED_DBF)?.read()?;
+
+    check_index_lookups(dbase::NdxReader::open(INDEXED_CODE_NDX)?, &records, "CODE")?;
+    check_index_lookups(dbase::NdxReader::open(INDEXED_CITY_NDX)?, &records, "CITY")?;
+
+    let mut mdx = dbase::MdxReader::from_path(INDEXED_MDX)?;
+    check_index_lookups(mdx.select_tag("CODE")?, &records, "CODE")?;
+    check_index_lookups(mdx.select_tag("CITY")?, &records, "CITY")?;
+    assert!(mdx.select_tag("NAME").is_err());
+
+    let mut cdx = dbase::CdxReader::from_path(INDEXED_CDX)?;
+    check_index_lookups(cdx.select_tag("CODE")?, &records, "CODE")?;
+    check_index_lookups(cdx.select_tag("CITY")?, &records, "CITY")?;
+    Ok(())
+}
+
+#[test]
+fn test_table_info_from_path() -> Result<(), Box<dyn std::error::Error>> {
+    let table_info = dbase::TableInfo::from_path(STATIONS)?;
+    let reader_table_info = Reader::from_path(STATIONS)?.into_table_info();
+
+    assert_eq!(table_info.fields(), reader_table_info.fields());
+    assert_eq!(
+        table_info.header().num_records,
+        reader_table_info.header().num_records
+    );
+    assert_eq!(
+        table_info.computed_record_size(),
+        reader_table_info.computed_record_size()
+    );
+    Ok(())
+}
+
+#[test]
+fn test_filter_records_on_numeric_threshold() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cursor = Cursor::new(Vec::<u8>::new());
+    let mut writer = TableWriterBuilder::new()
+        .add_character_field("Name".try_into().unwrap(), 10)
+        .add_numeric_field("Amount".try_into().unwrap(), 10, 2)
+        .build_with_dest(&mut cursor);
+    for (name, amount) in [("a", 12.5), ("b", 99.0), ("c", 100.0), ("d", 150.25)] {
+        let mut record = Record::default();
+        record.insert(
+            "Name".to_string(),
+            FieldValue::Character(Some(name.to_string())),
+        );
+        record.insert("Amount".to_string(), FieldValue::Numeric(Some(amount)));
+        writer.write_record(&record)?;
+    }
+    drop(writer);
+
+    cursor.set_position(0);
+    let mut reader = Reader::new(cursor)?;
+    let names = reader
+        .filter(|record| match record.get("Amount") {
+            Some(FieldValue::Numeric(Some(amount))) => *amount >= 100.0,
+            _ => false,
+        })
+        .map(|record| record.map(|r| r.get("Name").cloned()))
+        .collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(
+        names,
+        vec![
+            Some(FieldValue::Character(Some("c".to_string()))),
+            Some(FieldValue::Character(Some("d".to_string()))),
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_read_picture_fields() -> Result<(), Box<dyn std::error::Error>> {
+    let mut reader = dbase::Reader::from_path(PICTURES_DBF)?;
+    assert_eq!(reader.fields()[1].field_type(), dbase::FieldType::Picture);
+
+    let records = reader.read()?;
+    assert_eq!(records.len(), 3);
+
+    // The pixel row of the bmp is padded with a zero byte, which must be kept
+    let bmp = match records[0].get("PHOTO") {
+        Some(FieldValue::Picture(bytes)) => bytes,
+        value => panic!("Expected a picture, got {:?}", value),
+    };
+    assert_eq!(bmp.len(), 58);
+    assert!(bmp.starts_with(b"BM"));
+    assert_eq!(&bmp[54..], &[0x00, 0x00, 0xFF, 0x00]);
+
+    let gif = match records[1].get("PHOTO") {
+        Some(FieldValue::Picture(bytes)) => bytes,
+        value => panic!("Expected a picture, got {:?}", value),
+    };
+    assert_eq!(gif.len(), 43);
+    assert!(gif.starts_with(b"GIF89a"));
+    assert_eq!(gif.last(), Some(&b';'));
+
+    assert_eq!(records[2].get("PHOTO"), Some(&FieldValue::Picture(vec![])));
+    Ok(())
+}
+
+#[test]
+fn test_compressed_memo_block_is_reported() -> Result<(), Box<dyn std::error::Error>> {
+    let dbf = std::fs::read(PICTURES_DBF)?;
+    let mut fpt = std::fs::read(PICTURES_DBF.replace(".dbf", ".fpt"))?;
+    // The first picture is in the block at 512 bytes,
+    // give it a type that is not picture, text or object
+    fpt[512..516].copy_from_slice(&3u32.to_be_bytes());
+
+    let mut reader = dbase::ReaderBuilder::<_, dbase::UnicodeLossy>::new(Cursor::new(dbf))
+        .with_memo(Cursor::new(fpt))
+        .build()?;
+    let error = reader.read_record(0).unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        dbase::ErrorKind::UnsupportedMemoCompression(3)
+    ));
+    // The blocks that are not compressed can still be read
+    assert!(matches!(
+        reader.read_record(1)?.unwrap().get("PHOTO"),
+        Some(FieldValue::Picture(gif)) if gif.starts_with(b"GIF89a")
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_batch_records() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cursor = Cursor::new(Vec::<u8>::new());
+    let writer = TableWriterBuilder::new()
+        .add_numeric_field(FieldName::try_from("Value")?, 4, 0)
+        .build_with_dest(&mut cursor);
+    let records = (0..10)
+        .map(|i| {
+            let mut record = Record::default();
+            record.insert("Value".to_string(), FieldValue::Numeric(Some(f64::from(i))));
+            record
+        })
+        .collect::<Vec<_>>();
+    writer.write_records(&records)?;
+
+    cursor.set_position(0);
+    let mut reader = Reader::new(cursor)?;
+    let batches = reader.batch(3).collect::<Result<Vec<_>, _>>()?;
+    let sizes = batches.iter().map(Vec::len).collect::<Vec<_>>();
+    assert_eq!(sizes, vec![3, 3, 3, 1]);
+    assert_eq!(batches.concat(), records);
+    Ok(())
+}
+
+#[test]
+fn test_unsupported_version_is_rejected() -> Result<(), Box<dyn std::error::Error>> {
+    let mut data = std::fs::read(LINE_DBF)?;
+    data[0] = 0x07;
+    assert!(!dbase::Version::version_or_unknown(data[0]).is_supported());
+
+    let error = Reader::new(Cursor::new(data)).err().unwrap();
+    assert!(matches!(
+        error.kind(),
+        dbase::ErrorKind::UnsupportedVersion(0x07)
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_writer_flush_and_finish() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("flushed.dbf");
+    let mut writer = TableWriterBuilder::new()
+        .add_character_field(FieldName::try_from("Name")?, 10)
+        .build_with_file_dest(&path)?;
+
+    let mut record = Record::default();
+    record.insert(
+        "Name".to_string(),
+        FieldValue::Character(Some("Yoshi".to_string())),
+    );
+    writer.flush()?;
+    writer.write_record(&record)?;
+    writer.write_record(&record)?;
+    writer.flush()?;
+    // The header is up to date, even though the writer is not finished
+    assert_eq!(Reader::from_path(&path)?.read()?.len(), 2);
+
+    writer.write_record(&record)?;
+    // Closes the file
+    drop(writer.finish()?);
+    let data = std::fs::read(&path)?;
+    assert_eq!(data.last(), Some(&0x1A));
+    assert_eq!(Reader::from_path(&path)?.read()?, vec![record; 3]);
+    Ok(())
+}
+
+#[test]
+fn test_truncated_last_record() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cursor = Cursor::new(Vec::<u8>::new());
+    let writer = TableWriterBuilder::new()
+        .add_character_field(FieldName::try_from("Name")?, 10)
+        .add_numeric_field(FieldName::try_from("Value")?, 6, 0)
+        .add_character_field(FieldName::try_from("Note")?, 10)
+        .build_with_dest(&mut cursor);
+    let records = ["first", "second"]
+        .iter()
+        .map(|name| {
+            let mut record = Record::default();
+            record.insert(
+                "Name".to_string(),
+                FieldValue::Character(Some(name.to_string())),
+            );
+            record.insert("Value".to_string(), FieldValue::Numeric(Some(123456.0)));
+            record.insert(
+                "Note".to_string(),
+                FieldValue::Character(Some("note".to_string())),
+            );
+            record
+        })
+        .collect::<Vec<_>>();
+    writer.write_records(&records)?;
+
+    // Cut the file inside the Value field of the last record
+    let mut data = cursor.into_inner();
+    // file terminator, Note, and 3 bytes of Value
+    data.truncate(data.len() - 1 - 10 - 3);
+
+    let mut reader = Reader::new(Cursor::new(data.clone()))?;
+    let mut iter = reader.iter_records();
+    assert_eq!(iter.next().unwrap()?, records[0]);
+    let error = iter.next().unwrap().unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        dbase::ErrorKind::TruncatedRecord { index: 1 }
+    ));
+    assert!(iter.next().is_none());
+
+    let mut reader = dbase::ReaderBuilder::<_, dbase::Unicode>::new(Cursor::new(data))
+        .with_options(dbase::ReadingOptions::default().lenient(true))
+        .build()?;
+    let read_records = reader.read()?;
+    assert_eq!(read_records.len(), 2);
+    assert_eq!(read_records[0], records[0]);
+    assert_eq!(
+        read_records[1].get("Name"),
+        Some(&FieldValue::Character(Some("second".to_string())))
+    );
+    assert_eq!(
+        read_records[1].get("Value"),
+        Some(&FieldValue::Numeric(None))
+    );
+    assert_eq!(
+        read_records[1].get("Note"),
+        Some(&FieldValue::Character(None))
+    );
+    assert_eq!(reader.read_record(1)?, Some(read_records[1].clone()));
+    Ok(())
+}
+
+/// A source that can only be read, like an entry of a ZIP archive
+struct NonSeekable<'a>(&'a [u8]);
+
+impl<'a> Read for NonSeekable<'a> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        // Read in small chunks, like a decompressor would
+        let len = buf.len().min(7);
+        self.0.read(&mut buf[..len])
+    }
+}
+
+#[test]
+fn test_read_from_non_seekable_source() -> Result<(), Box<dyn std::error::Error>> {
+    for path in [STATIONS, STATIONS_WITH_DELETED, LEVEL7_DBF, INDEXED_DBF] {
+        let expected = Reader::from_path(path)?.read()?;
+
+        let data = std::fs::read(path)?;
+        let source = dbase::ForwardOnly::new(NonSeekable(&data));
+        let mut reader = Reader::new(source)?;
+        assert_eq!(reader.read()?, expected, "{}", path);
+
+        // Going back needs random access
+        assert!(reader.read_record(0).is_err());
+    }
+    Ok(())
+}
+
+#[test]
+fn test_read_from_zip_entry() -> Result<(), Box<dyn std::error::Error>> {
+    let expected = Reader::from_path(STATIONS)?.read()?;
+
+    let mut zip = zip::ZipWriter::new(Cursor::new(Vec::<u8>::new()));
+    zip.start_file("stations.dbf", zip::write::SimpleFileOptions::default())?;
+    zip.write_all(&std::fs::read(STATIONS)?)?;
+    let data = zip.finish()?.into_inner();
+
+    let mut archive = zip::ZipArchive::new(Cursor::new(data))?;
+    let entry = archive.by_name("stations.dbf")?;
+    let mut reader = Reader::new(dbase::ForwardOnly::new(entry))?;
+    assert_eq!(reader.read()?, expected);
+    Ok(())
+}
+
+#[test]
+fn test_numeric_value_in_integer_field_is_rejected() -> Result<(), Box<dyn std::error::Error>> {
+    let mut writer = TableWriterBuilder::new()
+        .add_integer_field(FieldName::try_from("Count")?)
+        .build_with_dest(Cursor::new(Vec::<u8>::new()));
+
+    let mut record = Record::default();
+    record.insert("Count".to_string(), FieldValue::Numeric(Some(3.0)));
+    let error = writer.write_record(&record).unwrap_err();
+    assert!(matches!(error.kind(), dbase::ErrorKind::IncompatibleType));
+
+    record.insert("Count".to_string(), FieldValue::Integer(i32::MIN));
+    writer.write_record(&record)?;
+    let data = writer.finish()?.into_inner();
+    assert_eq!(Reader::new(Cursor::new(data))?.read()?, vec![record]);
+    Ok(())
+}
+
+#[test]
+fn test_numeric_overflow() -> Result<(), Box<dyn std::error::Error>> {
+    let builder = || {
+        TableWriterBuilder::new()
+            .add_character_field(FieldName::try_from("Name").unwrap(), 5)
+            .add_numeric_field(FieldName::try_from("Amount").unwrap(), 4, 0)
+    };
+    let mut record = Record::default();
+    record.insert(
+        "Name".to_string(),
+        FieldValue::Character(Some("big".to_string())),
+    );
+    record.insert("Amount".to_string(), FieldValue::Numeric(Some(123456.0)));
+
+    let mut writer = builder().build_with_dest(Cursor::new(Vec::<u8>::new()));
+    let error = writer.write_record(&record).unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        dbase::ErrorKind::NumericOverflow { width: 4, .. }
+    ));
+    assert_eq!(
+        error.field().as_ref().map(|field| field.name()),
+        Some("Amount")
+    );
+    let data = writer.finish()?.into_inner();
+    assert!(Reader::new(Cursor::new(data))?.read()?.is_empty());
+
+    let mut writer = builder()
+        .set_numeric_overflow_policy(dbase::NumericOverflowPolicy::FillWithAsterisks)
+        .build_with_dest(Cursor::new(Vec::<u8>::new()));
+    writer.write_record(&record)?;
+    let data = writer.finish()?.into_inner();
+    let header = *Reader::new(Cursor::new(data.clone()))?.header();
+    assert_eq!(header.num_records, 1);
+    let offset = header.offset_to_first_record as usize;
+    assert_eq!(
+        &data[offset..offset + header.size_of_record as usize],
+        b" big  ****"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_read_numeric_filled_with_asterisks() -> Result<(), Box<dyn std::error::Error>> {
+    let mut writer = TableWriterBuilder::new()
+        .add_numeric_field(FieldName::try_from("Amount").unwrap(), 6, 2)
+        .add_float_field(FieldName::try_from("Ratio").unwrap(), 5, 1)
+        .build_with_dest(Cursor::new(Vec::<u8>::new()));
+    let mut record = Record::default();
+    record.insert("Amount".to_string(), FieldValue::Numeric(Some(1.0)));
+    record.insert("Ratio".to_string(), FieldValue::Float(Some(1.0)));
+    writer.write_record(&record)?;
+    let mut data = writer.finish()?.into_inner();
+
+    // As written by dBase, and by programs that pad the asterisks with spaces
+    let offset = Reader::new(Cursor::new(data.clone()))?
+        .header()
+        .offset_to_first_record as usize;
+    data[offset + 1..offset + 12].copy_from_slice(b"*********  ");
+
+    let read = |policy| -> Result<Vec<Record>, dbase::Error> {
+        let mut reader = Reader::new(Cursor::new(data.clone()))?;
+        reader.set_options(dbase::ReadingOptions::default().on_numeric_overflow(policy));
+        reader.read()
+    };
+    let records = Reader::new(Cursor::new(data.clone()))?.read()?;
+    assert_eq!(records[0].get("Amount"), Some(&FieldValue::Numeric(None)));
+    assert_eq!(records[0].get("Ratio"), Some(&FieldValue::Float(None)));
+
+    let records = read(dbase::OverflowPolicy::ReturnMaxValue)?;
+    assert_eq!(
+        records[0].get("Amount"),
+        Some(&FieldValue::Numeric(Some(999.99)))
+    );
+    assert_eq!(
+        records[0].get("Ratio"),
+        Some(&FieldValue::Float(Some(999.9)))
+    );
+
+    let error = read(dbase::OverflowPolicy::ReturnError).unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        dbase::ErrorKind::NumericOverflow { width: 6, .. }
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_wide_character_field() -> Result<(), Box<dyn std::error::Error>> {
+    let mut reader = Reader::from_path(WIDE_CHARACTER_DBF)?;
+    let note = &reader.fields()[1];
+    assert_eq!(note.name(), "NOTE");
+    assert_eq!(note.wide_record_length(), Some(300));
+    assert_eq!(reader.fields()[0].wide_record_length(), None);
+    assert_eq!(reader.header().size_of_record, 306);
+
+    let records = reader.read()?;
+    let long_note = format!("{}end", "x".repeat(280));
+    assert_eq!(
+        records[0].get("NOTE"),
+        Some(&FieldValue::Character(Some(long_note.clone())))
+    );
+    assert_eq!(
+        records[0].get("FLAG"),
+        Some(&FieldValue::Logical(Some(true)))
+    );
+    assert_eq!(
+        records[1].get("NOTE"),
+        Some(&FieldValue::Character(Some("short".to_string())))
+    );
+
+    let mut file = dbase::File::open_read_only(WIDE_CHARACTER_DBF)?;
+    let flag = file.field_index("FLAG").unwrap();
+    assert_eq!(
+        file.record(1).unwrap().read_field(flag)?,
+        FieldValue::Logical(Some(false))
+    );
+
+    // Transcoding checks the whole length of the field
+    let mut reader = Reader::from_path(WIDE_CHARACTER_DBF)?;
+    let cursor = dbase::transcode(&mut reader, Cursor::new(Vec::<u8>::new()), dbase::Unicode)?;
+    let mut reader = Reader::new(Cursor::new(cursor.into_inner()))?;
+    assert_eq!(reader.fields()[1].wide_record_length(), Some(300));
+    assert_eq!(reader.read()?, records);
+
+    // Writing keeps the length of the field
+    let reader = Reader::from_path(WIDE_CHARACTER_DBF)?;
+    let mut writer =
+        TableWriterBuilder::from_reader(reader).build_with_dest(Cursor::new(Vec::<u8>::new()));
+    for record in &records {
+        writer.write_record(record)?;
+    }
+    let data = writer.finish()?.into_inner();
+    let original = std::fs::read(WIDE_CHARACTER_DBF)?;
+    let descriptors = 32..32 + 3 * 32;
+    assert_eq!(&data[descriptors.clone()], &original[descriptors]);
+    let mut reader = Reader::new(Cursor::new(data))?;
+    assert_eq!(reader.read()?, records);
+
+    Ok(())
+}
+
+#[test]
+fn test_reading_limit() -> Result<(), dbase::Error> {
+    let mut reader = Reader::from_path(STATIONS)?;
+    reader.set_options(dbase::ReadingOptions::default().limit(10));
+    let records = reader.read()?;
+    assert_eq!(records.len(), 10);
+
+    let mut all = Reader::from_path(STATIONS)?.read()?;
+    all.truncate(10);
+    assert_eq!(records, all);
+
+    // Deleted records do not count
+    let expected = Reader::from_path(STATIONS_WITH_DELETED)?.read()?;
+    let mut reader = Reader::from_path(STATIONS_WITH_DELETED)?;
+    reader.set_options(dbase::ReadingOptions::default().limit(expected.len() + 1));
+    assert_eq!(reader.read()?, expected);
+    Ok(())
+}
+
+#[test]
+fn test_map_fields_trims_character_values() -> Result<(), dbase::Error> {
+    let expected = Reader::from_path(STATIONS)?.read()?;
+
+    let mut reader = Reader::from_path(STATIONS)?;
+    // The spaces that pad the values are kept
+    reader.set_options(dbase::ReadingOptions::default().character_trim(dbase::TrimOption::Begin));
+    let untrimmed = reader.read()?;
+    assert_ne!(untrimmed, expected);
+
+    reader.seek(0)?;
+    let records = reader
+        .map_fields(|_name, value| match value {
+            FieldValue::Character(text) => {
+                FieldValue::Character(text.map(|text| text.trim().to_string()))
+            }
+            value => value,
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(records, expected);
+    Ok(())
+}
+
+#[test]
+fn test_record_iterator_size_hint() -> Result<(), dbase::Error> {
+    let mut reader = Reader::from_path(STATIONS)?;
+    let num_records = reader.header().num_records as usize;
+    let mut iter = reader.iter_records();
+    assert_eq!(iter.size_hint(), (0, Some(num_records)));
+    iter.next().unwrap()?;
+    assert_eq!(iter.size_hint(), (0, Some(num_records - 1)));
+    assert_eq!(iter.count(), num_records - 1);
+
+    reader.set_options(dbase::ReadingOptions::default().skip(80));
+    assert_eq!(reader.iter_records().size_hint(), (0, Some(6)));
+    reader.set_options(dbase::ReadingOptions::default().skip(80).limit(4));
+    let mut iter = reader.iter_records();
+    assert_eq!(iter.size_hint(), (0, Some(4)));
+    iter.next().unwrap()?;
+    assert_eq!(iter.size_hint(), (0, Some(3)));
+
+    reader.set_options(dbase::ReadingOptions::default().ignore_num_records(true));
+    assert_eq!(reader.iter_records().size_hint(), (0, None));
+
+    // The deleted records are counted in the upper bound, but not returned
+    let mut reader = Reader::from_path(STATIONS_WITH_DELETED)?;
+    let iter = reader.iter_records_with_bytes();
+    assert_eq!(iter.size_hint(), (0, Some(6)));
+    assert_eq!(iter.count(), 5);
+    Ok(())
+}
+
+#[test]
+fn test_reading_pages() -> Result<(), dbase::Error> {
+    let all = Reader::from_path(STATIONS)?.read()?;
+
+    let mut reader = Reader::from_path(STATIONS)?;
+    let mut pages = vec![];
+    loop {
+        let options = dbase::ReadingOptions::default()
+            .skip(pages.len() * 5)
+            .limit(5);
+        reader.set_options(options);
+        let page = reader.read()?;
+        if page.is_empty() {
+            break;
+        }
+        pages.push(page);
+    }
+    // 86 records
+    assert_eq!(pages.len(), 18);
+    assert!(pages[..17].iter().all(|page| page.len() == 5));
+    assert_eq!(pages[17].len(), 1);
+    assert_eq!(pages.concat(), all);
+
+    // Sources that cannot seek read the skipped records
+    let data = std::fs::read(STATIONS).unwrap();
+    let mut reader = Reader::new(dbase::ForwardOnly::new(data.as_slice()))?;
+    reader.set_options(dbase::ReadingOptions::default().skip(80));
+    assert_eq!(reader.read()?, all[80..]);
+    Ok(())
+}
+
+#[test]
+fn test_reading_numeric_overflow() -> Result<(), Box<dyn std::error::Error>> {
+    let mut record = Record::default();
+    record.insert("Amount".to_string(), FieldValue::Numeric(Some(123456.0)));
+    let mut writer = TableWriterBuilder::new()
+        .add_numeric_field(FieldName::try_from("Amount").unwrap(), 4, 0)
+        .set_numeric_overflow_policy(dbase::NumericOverflowPolicy::FillWithAsterisks)
+        .build_with_dest(Cursor::new(Vec::<u8>::new()));
+    writer.write_record(&record)?;
+    let data = writer.finish()?.into_inner();
+
+    let read_with = |policy| {
+        let mut reader = Reader::new(Cursor::new(data.clone()))?;
+        reader.set_options(dbase::ReadingOptions::default().on_numeric_overflow(policy));
+        reader.read()
+    };
+
+    let error = read_with(dbase::OverflowPolicy::ReturnError).unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        dbase::ErrorKind::NumericOverflow {
+            value: None,
+            width: 4
+        }
+    ));
+    assert_eq!(
+        error.field().as_ref().map(|field| field.name()),
+        Some("Amount")
+    );
+
+    let records = read_with(dbase::OverflowPolicy::ReturnNull)?;
+    assert_eq!(records[0].get("Amount"), Some(&FieldValue::Numeric(None)));
+
+    let records = read_with(dbase::OverflowPolicy::ReturnMaxValue)?;
+    assert_eq!(
+        records[0].get("Amount"),
+        Some(&FieldValue::Numeric(Some(9999.0)))
+    );
+    Ok(())
+}
+
+#[test]
+fn test_take_while_on_sorted_file() -> Result<(), Box<dyn std::error::Error>> {
+    let mut writer = TableWriterBuilder::new()
+        .add_date_field(FieldName::try_from("Day").unwrap())
+        .add_numeric_field(FieldName::try_from("Amount").unwrap(), 6, 0)
+        .build_with_dest(Cursor::new(Vec::<u8>::new()));
+    for year in 2016..2024 {
+        for month in [1, 7] {
+            let mut record = Record::default();
+            record.insert(
+                "Day".to_string(),
+                FieldValue::Date(Some(Date::new(1, month, year))),
+            );
+            record.insert(
+                "Amount".to_string(),
+                FieldValue::Numeric(Some(f64::from(year))),
+            );
+            writer.write_record(&record)?;
+        }
+    }
+    let mut data = writer.finish()?.into_inner();
+    // Corrupt the last record, which is after the prefix that is read
+    let last = data.len() - 2;
+    data[last] = b'x';
+    assert!(Reader::new(Cursor::new(data.clone()))?.read().is_err());
+
+    let mut reader = Reader::new(Cursor::new(data))?;
+    let before_2020 = reader
+        .take_while(|record| match record.get("Day") {
+            Some(FieldValue::Date(Some(date))) => date.year() < 2020,
+            _ => false,
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(before_2020.len(), 8);
+    assert_eq!(
+        before_2020[7].get("Amount"),
+        Some(&FieldValue::Numeric(Some(2019.0)))
+    );
+    Ok(())
+}
+
+#[test]
+fn test_skip_to_last_record() -> Result<(), dbase::Error> {
+    let mut reader = dbase::Reader::from_path(STATIONS)?;
+    reader.skip(85)?;
+    let records = reader.read()?;
+    assert_eq!(records.len(), 1);
+    let last = &records[0];
+    assert_eq!(
+        last.get("name"),
+        Some(&FieldValue::Character(Some(
+            "Ronald Reagan Washington National Airport".to_string()
+        )))
+    );
+    assert_eq!(
+        last.get("marker-col"),
+        Some(&FieldValue::Character(Some("#0000ff".to_string())))
+    );
+    assert_eq!(
+        last.get("marker-sym"),
+        Some(&FieldValue::Character(Some("rail-metro".to_string())))
+    );
+    assert_eq!(
+        last.get("line"),
+        Some(&FieldValue::Character(Some("blue".to_string())))
+    );
+
+    let mut reader = dbase::Reader::from_path(STATIONS)?;
+    reader.skip(86)?;
+    assert!(reader.read()?.is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_version_chosen_from_field_types() -> Result<(), dbase::Error> {
+    let writer = TableWriterBuilder::new()
+        .add_character_field(FieldName::try_from("name").unwrap(), 10)
+        .build_with_dest(Cursor::new(Vec::<u8>::new()));
+    assert_eq!(writer.finish()?.into_inner()[0], 0x03);
+
+    let writer = TableWriterBuilder::new()
+        .add_character_field(FieldName::try_from("name").unwrap(), 10)
+        .add_memo_field(FieldName::try_from("notes").unwrap())
+        .build_with_dest(Cursor::new(Vec::<u8>::new()));
+    let data = writer.finish()?.into_inner();
+    assert_eq!(data[0], 0x83);
+    let reader = Reader::new(Cursor::new(data))?;
+    assert!(reader.header().file_type.supports_memo());
+    assert_eq!(reader.fields()[1].field_type(), dbase::FieldType::Memo);
+
+    let info = TableWriterBuilder::new()
+        .add_memo_field(FieldName::try_from("notes").unwrap())
+        .add_integer_field(FieldName::try_from("count").unwrap())
+        .build_table_info();
+    assert_eq!(u8::from(info.header().file_type), 0xf5);
+
+    let info = TableWriterBuilder::new()
+        .add_memo_field(FieldName::try_from("notes").unwrap())
+        .set_version(dbase::Version::DBase3 {
+            supports_memo: false,
+        })
+        .build_table_info();
+    assert_eq!(u8::from(info.header().file_type), 0x03);
+    Ok(())
+}
+
+#[test]
+fn test_malformed_field_descriptors() -> Result<(), dbase::Error> {
+    let writer = TableWriterBuilder::new()
+        .add_character_field(FieldName::try_from("name").unwrap(), 10)
+        .build_with_dest(Cursor::new(Vec::<u8>::new()));
+    let data = writer.finish()?.into_inner();
+    assert_eq!(u16::from_le_bytes([data[8], data[9]]), 32 + 32 + 1);
+
+    // The offset leaves room for the descriptor, but not for the terminator
+    let mut no_terminator = data.clone();
+    no_terminator[8..10].copy_from_slice(&64u16.to_le_bytes());
+    let error = Reader::new(Cursor::new(no_terminator)).err().unwrap();
+    assert!(matches!(
+        error.kind(),
+        dbase::ErrorKind::MalformedDescriptors
+    ));
+
+    // The offset is inside the header
+    let mut in_header = data.clone();
+    in_header[8..10].copy_from_slice(&16u16.to_le_bytes());
+    let error = Reader::new(Cursor::new(in_header)).err().unwrap();
+    assert!(matches!(
+        error.kind(),
+        dbase::ErrorKind::MalformedDescriptors
+    ));
+
+    // The descriptors end right away
+    let mut no_fields = data;
+    no_fields[32] = 0x0D;
+    let error = Reader::new(Cursor::new(no_fields)).err().unwrap();
+    assert!(matches!(error.kind(), dbase::ErrorKind::NoFields));
+    Ok(())
+}
+
+#[test]
+fn test_lenient_missing_descriptors_terminator() -> Result<(), dbase::Error> {
+    let data = std::fs::read(STATIONS).unwrap();
+    // 4 fields, the terminator is right after their descriptors
+    assert_eq!(data[32 + 4 * 32], 0x0D);
+    let mut zeroed = data.clone();
+    zeroed[32 + 4 * 32] = 0;
+
+    let error = Reader::new(Cursor::new(zeroed.clone())).err().unwrap();
+    assert!(matches!(
+        error.kind(),
+        dbase::ErrorKind::MalformedDescriptors
+    ));
+
+    let mut reader = dbase::ReaderBuilder::<_, dbase::Unicode>::new(Cursor::new(zeroed))
+        .with_options(dbase::ReadingOptions::default().lenient(true))
+        .build()?;
+    let names = reader.fields().iter().map(|f| f.name()).collect::<Vec<_>>();
+    assert_eq!(names, ["name", "marker-col", "marker-sym", "line"]);
+    let records = reader.read()?;
+    assert_eq!(records, Reader::new(Cursor::new(data))?.read()?);
+    assert_eq!(records.len(), 86);
+    Ok(())
+}
+
+#[test]
+fn test_long_field_names_from_database_container() -> Result<(), dbase::Error> {
+    let mut reader = dbase::Reader::from_path(CUSTOMERS_DBF)?;
+    assert_eq!(
+        reader.database_container_path()?,
+        Some("sales.dbc".to_string())
+    );
+    let names = reader
+        .fields()
+        .iter()
+        .map(|field| (field.name(), field.long_name()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        names,
+        vec![
+            ("CUSTOMER_I", Some("customer_identifier")),
+            ("NAME", Some("name")),
+            ("REGISTRATI", Some("registration_date")),
+        ]
+    );
+    let records = reader.read()?;
+    assert_eq!(records.len(), 2);
+    assert_eq!(
+        records[1].get("NAME"),
+        Some(&FieldValue::Character(Some("Alan Turing".to_string())))
+    );
+
+    // Without the path, the database has to be given
+    let file = std::fs::File::open(CUSTOMERS_DBF).unwrap();
+    let mut reader = Reader::new(file)?;
+    assert_eq!(reader.fields()[0].long_name(), None);
+    let database = dbase::DatabaseContainer::from_path(SALES_DBC)?;
+    let mut tables = database.table_names().collect::<Vec<_>>();
+    tables.sort_unstable();
+    assert_eq!(tables, vec!["customers", "orders"]);
+    assert!(!reader.set_long_field_names(&database, "orders"));
+    assert!(!reader.set_long_field_names(&database, "products"));
+    assert!(reader.set_long_field_names(&database, "CUSTOMERS"));
+    assert_eq!(reader.fields()[0].long_name(), Some("customer_identifier"));
+
+    let mut reader = dbase::Reader::from_path(PICTURES_DBF)?;
+    assert_eq!(reader.database_container_path()?, None);
+    let mut reader = dbase::Reader::from_path(STATIONS)?;
+    assert_eq!(reader.database_container_path()?, None);
+    Ok(())
+}
+
+#[test]
+fn test_cloned_table_info_is_independent() -> Result<(), dbase::Error> {
+    let original = dbase::TableInfo::from_path(STATIONS)?;
+    let mut migrated = original.clone();
+    migrated.fields_mut()[0].set_name(FieldName::try_from("station").unwrap());
+    assert_eq!(migrated.fields()[0].name(), "station");
+    assert_eq!(original.fields()[0].name(), "name");
+    assert_eq!(migrated.fields()[1..], original.fields()[1..]);
+
+    let field_type = original.fields()[0].field_type();
+    let copy = field_type;
+    assert_eq!(copy, field_type);
+
+    let mut reader = dbase::Reader::from_path(STATIONS)?;
+    let mut writer =
+        TableWriterBuilder::from_table_info(migrated).build_with_dest(Cursor::new(Vec::new()));
+    for record in reader.iter_records() {
+        let mut record = record?;
+        let name = record.remove("name").unwrap();
+        record.insert("station".to_string(), name);
+        writer.write_record(&record)?;
+    }
+    let mut cursor = writer.finish()?;
+    cursor.set_position(0);
+    let records = Reader::new(cursor)?.read()?;
+    assert_eq!(records.len(), 86);
+    assert_eq!(
+        records[0].get("station"),
+        Some(&FieldValue::Character(Some("Van Dorn Street".to_string())))
+    );
+    Ok(())
+}
+
+#[test]
+fn test_field_types_must_be_supported_by_version() -> Result<(), dbase::Error> {
+    let mut record = Record::default();
+    record.insert("price".to_string(), FieldValue::Currency(125_000));
+
+    let mut writer = TableWriterBuilder::new()
+        .add_currency_field(FieldName::try_from("price").unwrap())
+        .set_version(dbase::Version::DBase3 {
+            supports_memo: false,
+        })
+        .build_with_dest(Cursor::new(Vec::<u8>::new()));
+    let error = writer.write_record(&record).unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        dbase::ErrorKind::UnsupportedFieldTypeForVersion {
+            field_type: dbase::FieldType::Currency,
+            ..
+        }
+    ));
+    assert_eq!(
+        error.field().as_ref().map(|field| field.name()),
+        Some("price")
+    );
+
+    let mut writer = TableWriterBuilder::new()
+        .add_currency_field(FieldName::try_from("price").unwrap())
+        .set_version(dbase::Version::VisualFoxPro)
+        .build_with_dest(Cursor::new(Vec::<u8>::new()));
+    writer.write_record(&record)?;
+    let mut cursor = writer.finish()?;
+    assert_eq!(cursor.get_ref()[0], 0x30);
+    cursor.set_position(0);
+    let records = Reader::new(cursor)?.read()?;
+    assert_eq!(
+        records[0].get("price"),
+        Some(&FieldValue::Currency(125_000))
+    );
+    Ok(())
+}
+
+#[test]
+fn test_case_insensitive_field_lookup() -> Result<(), dbase::Error> {
+    let mut reader = dbase::Reader::from_path(STATIONS)?;
+    let records = reader.read()?;
+    let van_dorn = FieldValue::Character(Some("Van Dorn Street".to_string()));
+    assert_eq!(records[0].get("NAME"), Some(&van_dorn));
+    assert_eq!(records[0].get("Name"), Some(&van_dorn));
+    assert_eq!(records[0].get_exact("name"), Some(&van_dorn));
+    assert_eq!(records[0].get_exact("NAME"), None);
+    assert_eq!(records[0].get("NAMES"), None);
+
+    let mut reader = dbase::Reader::from_path(STATIONS)?;
+    let names = reader.column_values("NAME")?;
+    assert_eq!(names.len(), 86);
+    assert_eq!(names[0], van_dorn);
+
+    let mut reader = dbase::Reader::from_path(STATIONS)?;
+    let error = reader.column_values_exact("NAME").unwrap_err();
+    assert!(matches!(error.kind(), dbase::ErrorKind::UnknownFieldName(name) if name == "NAME"));
+    let error = reader.column_values("NAMES").unwrap_err();
+    assert!(matches!(error.kind(), dbase::ErrorKind::UnknownFieldName(name) if name == "NAMES"));
+    assert_eq!(reader.column_values_exact("name")?.len(), 86);
+    Ok(())
+}
+
+#[test]
+fn test_read_header_only() -> Result<(), dbase::Error> {
+    for path in [STATIONS, LINE_DBF, LEVEL7_DBF, PICTURES_DBF] {
+        let mut file = std::fs::File::open(path).unwrap();
+        let header = dbase::read_header_only(&mut file)?;
+        assert_eq!(file.stream_position().unwrap(), 32);
+
+        let info = dbase::TableInfo::from_path(path)?;
+        assert_eq!(header.num_records, info.header().num_records);
+        assert_eq!(
+            u8::from(header.file_type),
+            u8::from(info.header().file_type)
+        );
+        assert_eq!(header.last_update, info.header().last_update);
+    }
+
+    let error = dbase::read_header_only(&mut &[0x03u8, 0x7c][..]).unwrap_err();
+    assert!(matches!(error.kind(), dbase::ErrorKind::IoError(_)));
+    Ok(())
+}
+
+#[test]
+fn test_write_read_wide_table() -> Result<(), dbase::Error> {
+    let mut builder = TableWriterBuilder::new();
+    for i in 0..300 {
+        let name = format!("F{}", i);
+        builder = builder.add_character_field(FieldName::try_from(name.as_str()).unwrap(), 2);
+    }
+    let builder = builder.add_wide_character_field(FieldName::try_from("NOTE").unwrap(), 300);
+
+    let long_note = format!("{}end", "y".repeat(290));
+    let mut record = Record::default();
+    for i in 0..300 {
+        record.insert(
+            format!("F{}", i),
+            FieldValue::Character(Some((i % 100).to_string())),
+        );
+    }
+    record.insert(
+        "NOTE".to_string(),
+        FieldValue::Character(Some(long_note.clone())),
+    );
+
+    let mut writer = builder.build_with_dest(Cursor::new(Vec::<u8>::new()));
+    writer.write_record(&record)?;
+    let mut cursor = writer.finish()?;
+    cursor.set_position(0);
+
+    let mut reader = Reader::new(cursor)?;
+    assert_eq!(reader.fields().len(), 301);
+    assert_eq!(reader.fields()[300].wide_record_length(), Some(300));
+    assert_eq!(reader.header().size_of_record, 1 + 300 * 2 + 300);
+    let records = reader.read()?;
+    assert_eq!(
+        records[0].get("NOTE"),
+        Some(&FieldValue::Character(Some(long_note)))
+    );
+    assert_eq!(
+        records[0].get("F299"),
+        Some(&FieldValue::Character(Some("99".to_string())))
+    );
+    Ok(())
+}
+
+#[test]
+fn test_numeric_field_decimal_places() -> Result<(), dbase::Error> {
+    let mut record = Record::default();
+    record.insert("PRICE".to_string(), FieldValue::Numeric(Some(3.5)));
+
+    let mut writer = TableWriterBuilder::new()
+        .add_numeric_field(FieldName::try_from("PRICE").unwrap(), 10, 2)
+        .build_with_dest(Cursor::new(Vec::<u8>::new()));
+    writer.write_record(&record)?;
+    let mut cursor = writer.finish()?;
+    let data = cursor.get_ref();
+    let first_record = data.len() - 1 - 11;
+    assert_eq!(&data[first_record..data.len() - 1], b"       3.50");
+    cursor.set_position(0);
+    let mut reader = Reader::new(cursor)?;
+    assert_eq!(reader.fields()[0].num_decimal_places(), 2);
+    assert_eq!(
+        reader.read()?[0].get("PRICE"),
+        Some(&FieldValue::Numeric(Some(3.5)))
+    );
+
+    for (length, num_decimals) in [(3, 2), (2, 2), (1, 3)] {
+        let mut writer = TableWriterBuilder::new()
+            .add_numeric_field(FieldName::try_from("PRICE").unwrap(), length, num_decimals)
+            .build_with_dest(Cursor::new(Vec::<u8>::new()));
+        let error = writer.write_record(&record).unwrap_err();
+        assert!(matches!(
+            error.kind(),
+            dbase::ErrorKind::InvalidDecimalPlaces { length: l, num_decimal_places: d }
+                if *l == length && *d == num_decimals
+        ));
+    }
+    Ok(())
+}
+
+#[test]
+fn test_reader_field_introspection() -> Result<(), dbase::Error> {
+    let reader = dbase::Reader::from_path(LEVEL7_DBF)?;
+    assert_eq!(reader.num_fields(), 4);
+    assert_eq!(
+        reader.field_names().collect::<Vec<_>>(),
+        vec!["ID", "NAME", "CREATED", "AMOUNT"]
+    );
+    assert_eq!(
+        reader.field_types().collect::<Vec<_>>(),
+        vec![
+            dbase::FieldType::AutoIncrement,
+            dbase::FieldType::Character,
+            dbase::FieldType::Timestamp,
+            dbase::FieldType::DBase7Double,
+        ]
+    );
+    assert!(reader
+        .field_descriptors()
+        .zip(reader.fields())
+        .all(|(descriptor, field)| descriptor == field));
+    Ok(())
+}
+
+#[test]
+fn test_read_from_bytes_in_memory() -> Result<(), dbase::Error> {
+    let bytes = std::fs::read(STATIONS).unwrap();
+    let expected = dbase::read(STATIONS)?;
+
+    let mut reader = dbase::Reader::from_bytes(&bytes)?;
+    assert_eq!(reader.read()?, expected);
+
+    let mut reader = dbase::Reader::from_vec(bytes.clone())?;
+    assert_eq!(reader.read()?, expected);
+
+    let error = dbase::Reader::from_bytes(&bytes[..16]).err().unwrap();
+    assert!(matches!(error.kind(), dbase::ErrorKind::IoError(_)));
+    Ok(())
+}
+
+#[test]
+fn test_prepared_writer() -> Result<(), dbase::Error> {
+    let builder = || {
+        TableWriterBuilder::new()
+            .add_character_field(FieldName::try_from("name").unwrap(), 12)
+            .add_numeric_field(FieldName::try_from("amount").unwrap(), 10, 2)
+            .add_logical_field(FieldName::try_from("flag").unwrap())
+    };
+    let rows = (0..50)
+        .map(|i| {
+            vec![
+                FieldValue::Character(Some(format!("row {}", i))),
+                FieldValue::Numeric(Some(f64::from(i) / 4.0)),
+                FieldValue::Logical(Some(i % 3 == 0)),
+            ]
+        })
+        .collect::<Vec<_>>();
+
+    let mut prepared = builder().build_prepared(Cursor::new(Vec::<u8>::new()))?;
+    for row in &rows {
+        prepared.push(row)?;
+    }
+    assert_eq!(prepared.num_records(), 50);
+    let error = prepared.push(&rows[0][..2]).unwrap_err();
+    assert!(matches!(error.kind(), dbase::ErrorKind::NotEnoughFields));
+    let error = prepared
+        .push(&[rows[0].as_slice(), &rows[1]].concat())
+        .unwrap_err();
+    assert!(matches!(error.kind(), dbase::ErrorKind::TooManyFields));
+    let error = prepared
+        .push(&[
+            FieldValue::Integer(1),
+            FieldValue::Numeric(None),
+            FieldValue::Logical(None),
+        ])
+        .unwrap_err();
+    assert!(matches!(error.kind(), dbase::ErrorKind::IncompatibleType));
+    let prepared_bytes = prepared.finish()?.into_inner();
+
+    // The same table written record by record
+    let mut writer = builder().build_with_dest(Cursor::new(Vec::<u8>::new()));
+    for row in &rows {
+        let mut record = Record::default();
+        for (name, value) in ["name", "amount", "flag"].iter().zip(row) {
+            record.insert(name.to_string(), value.clone());
+        }
+        writer.write_record(&record)?;
+    }
+    assert_eq!(prepared_bytes, writer.finish()?.into_inner());
+
+    // The fields are validated when the writer is built
+    let error = TableWriterBuilder::new()
+        .add_numeric_field(FieldName::try_from("amount").unwrap(), 3, 2)
+        .build_prepared(Cursor::new(Vec::<u8>::new()))
+        .err()
+        .unwrap();
+    assert!(matches!(
+        error.kind(),
+        dbase::ErrorKind::InvalidDecimalPlaces { .. }
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_fold_records() -> Result<(), dbase::Error> {
+    let mut reader = dbase::Reader::from_path(STATIONS)?;
+    let (num_records, total_length) =
+        reader.fold((0, 0), |(count, length), record| match record.get("name") {
+            Some(FieldValue::Character(Some(name))) => (count + 1, length + name.len()),
+            _ => (count + 1, length),
+        })?;
+    assert_eq!(num_records, 86);
+    assert!(total_length > 0);
+
+    // The accumulator can stop the fold
+    #[derive(Debug)]
+    enum StopError {
+        Dbase(dbase::Error),
+        TooMany,
+    }
+    impl From<dbase::Error> for StopError {
+        fn from(error: dbase::Error) -> Self {
+            StopError::Dbase(error)
+        }
+    }
+    let mut reader = dbase::Reader::from_path(STATIONS)?;
+    let result = reader.try_fold(0, |count, _| {
+        if count == 10 {
+            Err(StopError::TooMany)
+        } else {
+            Ok(count + 1)
+        }
+    });
+    assert!(matches!(result, Err(StopError::TooMany)));
+
+    // Errors reading the records are returned
+    let mut writer = TableWriterBuilder::new()
+        .add_numeric_field(FieldName::try_from("amount").unwrap(), 5, 0)
+        .build_with_dest(Cursor::new(Vec::<u8>::new()));
+    for amount in [1.0, 2.0, 3.0] {
+        let mut record = Record::default();
+        record.insert("amount".to_string(), FieldValue::Numeric(Some(amount)));
+        writer.write_record(&record)?;
+    }
+    let mut bytes = writer.finish()?.into_inner();
+    let second_record = bytes.len() - 1 - 2 * 6;
+    bytes[second_record + 1..second_record + 6].copy_from_slice(b"  abc");
+    let mut reader = dbase::Reader::from_bytes(&bytes)?;
+    let error = reader.fold(0.0, |sum, _| sum + 1.0).unwrap_err();
+    assert_eq!(error.record_num(), 1);
+    let mut reader = dbase::Reader::from_bytes(&bytes)?;
+    let result = reader.try_fold(0, |count, _| Ok::<_, StopError>(count + 1));
+    assert!(matches!(result, Err(StopError::Dbase(error)) if error.record_num() == 1));
+    Ok(())
+}
+
+#[test]
+fn test_ignore_num_records_of_header() -> Result<(), dbase::Error> {
+    let expected = dbase::read(STATIONS)?;
+    let options = dbase::ReadingOptions::default().ignore_num_records(true);
+    let mut bytes = std::fs::read(STATIONS).unwrap();
+    let header_size = usize::from(u16::from_le_bytes([bytes[8], bytes[9]]));
+    let record_size = usize::from(u16::from_le_bytes([bytes[10], bytes[11]]));
+    bytes.truncate(header_size + 86 * record_size);
+    bytes.push(0x1A);
+
+    // A stale count
+    bytes[4..8].copy_from_slice(&10u32.to_le_bytes());
+    let mut reader = dbase::Reader::from_bytes(&bytes)?;
+    assert_eq!(reader.read()?.len(), 10);
+    let mut reader = dbase::Reader::from_bytes(&bytes)?;
+    reader.set_options(options);
+    assert_eq!(reader.read()?, expected);
+    let mut reader = dbase::Reader::from_bytes(&bytes)?;
+    reader.set_options(options);
+    assert!(reader.read_record(85)?.is_some());
+    assert!(reader.read_record(86)?.is_none());
+
+    // The sentinel, without the file terminator
+    bytes[4..8].copy_from_slice(&u32::MAX.to_le_bytes());
+    bytes.pop();
+    let mut reader = dbase::Reader::from_bytes(&bytes)?;
+    reader.set_options(options);
+    assert_eq!(reader.read()?, expected);
+
+    // Data that is not a whole record is still an error
+    bytes.extend_from_slice(b" partial");
+    let mut reader = dbase::Reader::from_bytes(&bytes)?;
+    reader.set_options(options);
+    let error = reader.read().unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        dbase::ErrorKind::TruncatedRecord { index: 86 }
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_header_field_overflow() {
+    // Records of 1 + 3 * 30000 bytes
+    let mut builder = TableWriterBuilder::new();
+    for name in ["first", "second", "third"] {
+        builder = builder.add_wide_character_field(FieldName::try_from(name).unwrap(), 30000);
+    }
+    let error = builder
+        .build_with_dest(Cursor::new(Vec::<u8>::new()))
+        .finish()
+        .unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        dbase::ErrorKind::HeaderFieldOverflow { value: 90001, .. }
+    ));
+
+    // 32 + 2100 * 32 + 1 bytes before the first record
+    let mut builder = TableWriterBuilder::new();
+    for i in 0..2100 {
+        let name = format!("F{}", i);
+        builder = builder.add_character_field(FieldName::try_from(name.as_str()).unwrap(), 1);
+    }
+    let error = builder
+        .build_prepared(Cursor::new(Vec::<u8>::new()))
+        .err()
+        .unwrap();
+    assert!(matches!(
+        error.kind(),
+        dbase::ErrorKind::HeaderFieldOverflow { value: 67233, .. }
+    ));
+}
+
+#[test]
+fn test_required_field_values() -> Result<(), dbase::Error> {
+    let mut reader = dbase::Reader::from_path(STATIONS)?;
+    let record = reader.read()?.swap_remove(0);
+    let expected = FieldValue::Character(Some("Van Dorn Street".to_string()));
+    assert_eq!(record.get("name"), Some(&expected));
+    assert_eq!(record.get_required("name")?, &expected);
+    assert_eq!(record.get("missing"), None);
+    let error = record.get_required("missing").unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        dbase::ErrorKind::UnknownFieldName(name) if name == "missing"
+    ));
+
+    // Writing a record that lacks a field of the table
+    let mut writer = TableWriterBuilder::new()
+        .add_character_field(FieldName::try_from("name").unwrap(), 10)
+        .add_integer_field(FieldName::try_from("count").unwrap())
+        .build_with_dest(Cursor::new(Vec::<u8>::new()));
+    let mut record = Record::default();
+    record.insert("name".to_string(), expected);
+    let error = writer.write_record(&record).unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        dbase::ErrorKind::UnknownFieldName(name) if name == "count"
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_set_field_values() -> Result<(), dbase::Error> {
+    let mut reader = dbase::Reader::from_path(STATIONS)?;
+    let mut record = reader.read()?.swap_remove(0);
+    let renamed = FieldValue::Character(Some("Van Dorn".to_string()));
+    record.set("NAME", renamed.clone())?;
+    assert_eq!(record.get("name"), Some(&renamed));
+
+    let error = record.set("missing", renamed).unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        dbase::ErrorKind::UnknownFieldName(name) if name == "missing"
+    ));
+    let error = record
+        .set("name", FieldValue::Numeric(Some(1.0)))
+        .unwrap_err();
+    assert!(matches!(error.kind(), dbase::ErrorKind::IncompatibleType));
+    assert_eq!(
+        record.get("name"),
+        Some(&FieldValue::Character(Some("Van Dorn".to_string())))
+    );
+    Ok(())
+}
+
+#[test]
+fn test_scan_for_header_after_garbage() -> Result<(), dbase::Error> {
+    let data = std::fs::read(STATIONS).unwrap();
+    let expected = dbase::Reader::new(Cursor::new(data.clone()))?.read()?;
+
+    let mut prefixed = b"\x00\xFF!".to_vec();
+    prefixed.extend_from_slice(&data);
+    let error = dbase::Reader::new(Cursor::new(prefixed.clone()))
+        .err()
+        .unwrap();
+    assert!(matches!(
+        error.kind(),
+        dbase::ErrorKind::UnsupportedVersion(0x00)
+    ));
+
+    let source = dbase::SkipPrefix::scan_for_header(Cursor::new(prefixed), 8)?;
+    assert_eq!(source.prefix_len(), 3);
+    let mut reader = dbase::Reader::new(source)?;
+    assert_eq!(reader.header().num_records, 86);
+    assert_eq!(reader.read()?, expected);
+    reader.seek(85)?;
+    assert_eq!(reader.iter_records().count(), 1);
+
+    let source = dbase::SkipPrefix::scan_for_header(Cursor::new(data), 8)?;
+    assert_eq!(source.prefix_len(), 0);
+
+    // Too much garbage
+    let mut prefixed = vec![0u8; 16];
+    prefixed.extend_from_slice(&std::fs::read(STATIONS).unwrap());
+    let error = dbase::SkipPrefix::scan_for_header(Cursor::new(prefixed), 8).unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        dbase::ErrorKind::UnsupportedVersion(0x00)
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_raw_record_bytes() -> Result<(), dbase::Error> {
+    let mut reader = dbase::Reader::from_path(STATIONS_WITH_DELETED)?;
+    let num_records = reader.header().num_records;
+    let record_size = reader.header().size_of_record as usize;
+    let expected = reader.read()?;
+
+    let mut parsed = vec![];
+    for index in 0..num_records {
+        let bytes = reader.read_record_raw(index)?;
+        assert_eq!(bytes.len(), record_size);
+        // Deleted records are decoded as well
+        let record = reader.parse_record_from_bytes(&bytes)?;
+        if bytes[0] == b'*' {
+            assert!(reader.read_record(index as usize)?.is_none());
+            continue;
+        }
+        assert_eq!(reader.read_record(index as usize)?.as_ref(), Some(&record));
+        parsed.push(record);
+    }
+    assert_eq!(parsed, expected);
+
+    let bytes = reader.read_record_raw(0)?;
+    let error = reader.parse_record_from_bytes(&bytes[..10]).unwrap_err();
+    assert!(matches!(error.kind(), dbase::ErrorKind::IoError(_)));
+
+    let error = reader.read_record_raw(num_records).unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        dbase::ErrorKind::RecordIndexOutOfRange { index, num_records: 6 } if *index == 6
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_write_read_adjacent_float_and_double() -> Result<(), dbase::Error> {
+    let mut writer = TableWriterBuilder::new()
+        .add_float_field("before".try_into().unwrap(), 10, 2)
+        .add_double_field("double".try_into().unwrap())
+        .add_float_field("after".try_into().unwrap(), 10, 2)
+        .build_with_dest(Cursor::new(Vec::<u8>::new()));
+    let values = [(1.5f32, 0.1f64, -2.25f32), (-3.0, -1.0e300, 4.75)];
+    for (before, double, after) in values {
+        let mut record = Record::default();
+        record.insert("before".to_string(), FieldValue::Float(Some(before)));
+        record.insert("double".to_string(), FieldValue::Double(double));
+        record.insert("after".to_string(), FieldValue::Float(Some(after)));
+        writer.write_record(&record)?;
+    }
+    let mut cursor = writer.finish()?;
+    cursor.set_position(0);
+
+    let mut reader = Reader::new(cursor)?;
+    let offsets = reader
+        .fields()
+        .iter()
+        .map(|f| (f.offset_within_record(), f.length()))
+        .collect::<Vec<_>>();
+    assert_eq!(offsets, [(1, 10), (11, 8), (19, 10)]);
+    assert_eq!(reader.header().size_of_record, 1 + 10 + 8 + 10);
+
+    // The double is stored as 8 binary bytes, between the two Float fields written as text
+    let bytes = reader.read_record_raw(1)?;
+    assert_eq!(&bytes[1..11], b"     -3.00");
+    assert_eq!(&bytes[11..19], &(-1.0e300f64).to_le_bytes());
+    assert_eq!(&bytes[19..29], b"      4.75");
+
+    let records = reader.read()?;
+    for (record, (before, double, after)) in records.iter().zip(values) {
+        assert_eq!(record.get("before"), Some(&FieldValue::Float(Some(before))));
+        assert_eq!(record.get("double"), Some(&FieldValue::Double(double)));
+        assert_eq!(record.get("after"), Some(&FieldValue::Float(Some(after))));
+    }
+    Ok(())
+}
+
+dbase_record! {
+    #[derive(Clone, Debug, PartialEq)]
+    struct ReversedRecord {
+        count: i32,
+        last_name: String,
+        first_name: String,
+    }
+}
+
+#[test]
+fn test_write_struct_fields_in_table_order() -> Result<(), dbase::Error> {
+    let mut writer = TableWriterBuilder::new()
+        .add_character_field("First Name".try_into().unwrap(), 20)
+        .add_character_field("LAST_NAME".try_into().unwrap(), 20)
+        .add_integer_field("count".try_into().unwrap())
+        .build_with_dest(Cursor::new(Vec::<u8>::new()));
+    writer.write_record(&ReversedRecord {
+        count: 3,
+        last_name: "Rust".to_string(),
+        first_name: "Ferrys".to_string(),
+    })?;
+    let mut cursor = writer.finish()?;
+    cursor.set_position(0);
+
+    let records = Reader::new(cursor)?.read()?;
+    assert_eq!(
+        records[0].get("First Name"),
+        Some(&FieldValue::Character(Some("Ferrys".to_string())))
+    );
+    assert_eq!(
+        records[0].get("LAST_NAME"),
+        Some(&FieldValue::Character(Some("Rust".to_string())))
+    );
+    assert_eq!(records[0].get("count"), Some(&FieldValue::Integer(3)));
+
+    // A field of the table that the struct does not have
+    let mut writer = TableWriterBuilder::new()
+        .add_character_field("first_name".try_into().unwrap(), 20)
+        .add_character_field("middle_name".try_into().unwrap(), 20)
+        .build_with_dest(Cursor::new(Vec::<u8>::new()));
+    let error = writer
+        .write_record(&User {
+            first_name: "Alex".to_string(),
+            last_name: "Rider".to_string(),
+        })
+        .unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        dbase::ErrorKind::UnknownFieldName(name) if name == "middle_name"
+    ));
+    let mut cursor = writer.finish()?;
+    cursor.set_position(0);
+    assert_eq!(Reader::new(cursor)?.header().num_records, 0);
+    Ok(())
+}
+
+dbase_record! {
+    #[derive(Clone, Debug, PartialEq)]
+    struct StationName {
+        marker: String,
+        station_name: String,
+    }
+}
+
+#[test]
+fn test_read_struct_fields_in_table_order() -> Result<(), dbase::Error> {
+    let mut writer = TableWriterBuilder::new()
+        .add_character_field("name".try_into().unwrap(), 20)
+        .add_character_field("line".try_into().unwrap(), 10)
+        .add_character_field("marker".try_into().unwrap(), 10)
+        .build_with_dest(Cursor::new(Vec::<u8>::new()));
+    let mut record = dbase::Record::default();
+    for (name, value) in [("name", "S"), ("line", "blue"), ("marker", "M")] {
+        let value = FieldValue::Character(Some(value.to_string()));
+        record.insert(name.to_string(), value);
+    }
+    writer.write_record(&record)?;
+    let mut cursor = writer.finish()?;
+    cursor.set_position(0);
+
+    dbase_record! {
+        #[derive(Clone, Debug, PartialEq)]
+        struct Rev {
+            marker: String,
+            name: String,
+        }
+    }
+    // The "line" field of the table is skipped
+    let records = Reader::new(cursor)?.read_as::<Rev>()?;
+    assert_eq!(
+        records,
+        [Rev {
+            marker: "M".to_string(),
+            name: "S".to_string(),
+        }]
+    );
+
+    // The name of the table is truncated to 10 characters
+    let mut writer = TableWriterBuilder::new()
+        .add_character_field("STATION_NA".try_into().unwrap(), 20)
+        .add_character_field("MARKER".try_into().unwrap(), 10)
+        .build_with_dest(Cursor::new(Vec::<u8>::new()));
+    let station = StationName {
+        marker: "M".to_string(),
+        station_name: "Van Dorn Street".to_string(),
+    };
+    writer.write_record(&station)?;
+    let mut cursor = writer.finish()?;
+    cursor.set_position(0);
+    let mut reader = Reader::new(cursor)?;
+    assert_eq!(reader.fields()[0].name(), "STATION_NA");
+    assert_eq!(reader.read_as::<StationName>()?, [station]);
+
+    // A struct field that the table does not have
+    reader.seek(0)?;
+    let error = reader.read_as::<Rev>().unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        dbase::ErrorKind::UnknownFieldName(name) if name == "name"
+    ));
+    Ok(())
+}
+
+fn write_read_records(
+    writer_builder: TableWriterBuilder,
+    records: &[Record],
+) -> Result<Vec<Record>, dbase::Error> {
+    let mut writer = writer_builder.build_with_dest(Cursor::new(Vec::<u8>::new()));
+    for record in records {
+        writer.write_record(record)?;
+    }
+    let mut cursor = writer.finish()?;
+    cursor.set_position(0);
+    Reader::new(cursor)?.read()
+}
+
+#[test]
+fn test_write_read_every_field_type() -> Result<(), dbase::Error> {
+    let record = |values: Vec<(&str, FieldValue)>| {
+        let mut record = Record::default();
+        for (name, value) in values {
+            record.insert(name.to_string(), value);
+        }
+        record
+    };
+
+    // The dBase Level 7 file has AutoIncrement, Character, Timestamp and DBase7Double fields
+    let writer_builder = TableWriterBuilder::from_reader(Reader::from_path(LEVEL7_DBF)?)
+        .add_date_field("DAY".try_into().unwrap())
+        .add_numeric_field("PRICE".try_into().unwrap(), 10, 2)
+        .add_float_field("RATIO".try_into().unwrap(), 10, 3)
+        .add_logical_field("VALID".try_into().unwrap());
+    let records = [
+        record(vec![
+            ("ID", FieldValue::Integer(7)),
+            ("NAME", FieldValue::Character(Some("Alice".to_string()))),
+            (
+                "CREATED",
+                FieldValue::DateTime(DateTime::new(Date::new(1, 2, 2023), Time::new(4, 5, 6))),
+            ),
+            ("AMOUNT", FieldValue::Double(-1234.5625)),
+            ("DAY", FieldValue::Date(Some(Date::new(29, 2, 2024)))),
+            ("PRICE", FieldValue::Numeric(Some(-12.25))),
+            ("RATIO", FieldValue::Float(Some(0.125))),
+            ("VALID", FieldValue::Logical(Some(true))),
+        ]),
+        record(vec![
+            ("ID", FieldValue::Integer(8)),
+            ("NAME", FieldValue::Character(None)),
+            (
+                "CREATED",
+                FieldValue::DateTime(DateTime::new(
+                    Date::new(31, 12, 1999),
+                    Time::new(23, 59, 59),
+                )),
+            ),
+            ("AMOUNT", FieldValue::Double(0.0)),
+            ("DAY", FieldValue::Date(None)),
+            ("PRICE", FieldValue::Numeric(None)),
+            ("RATIO", FieldValue::Float(None)),
+            ("VALID", FieldValue::Logical(None)),
+        ]),
+    ];
+    assert_eq!(write_read_records(writer_builder, &records)?, records);
+
+    let writer_builder = TableWriterBuilder::new()
+        .add_character_field("name".try_into().unwrap(), 10)
+        .add_integer_field("count".try_into().unwrap())
+        .add_double_field("ratio".try_into().unwrap())
+        .add_currency_field("price".try_into().unwrap())
+        .add_datetime_field("updated".try_into().unwrap());
+    let records = [
+        record(vec![
+            ("name", FieldValue::Character(Some("Bob".to_string()))),
+            ("count", FieldValue::Integer(i32::MIN)),
+            ("ratio", FieldValue::Double(1.0e-300)),
+            ("price", FieldValue::Currency(-9_223_372_036_854_775)),
+            (
+                "updated",
+                FieldValue::DateTime(DateTime::new(Date::new(14, 7, 1789), Time::new(12, 0, 0))),
+            ),
+        ]),
+        record(vec![
+            ("name", FieldValue::Character(Some("Eve".to_string()))),
+            ("count", FieldValue::Integer(i32::MAX)),
+            ("ratio", FieldValue::Double(f64::MAX)),
+            ("price", FieldValue::Currency(1)),
+            (
+                "updated",
+                FieldValue::DateTime(DateTime::new(Date::new(1, 1, 2000), Time::new(0, 0, 0))),
+            ),
+        ]),
+    ];
+    assert_eq!(write_read_records(writer_builder, &records)?, records);
+
+    // Bytes that could not be decoded are written back as they were
+    let writer_builder =
+        TableWriterBuilder::new().add_character_field("name".try_into().unwrap(), 4);
+    let binary = record(vec![(
+        "name",
+        FieldValue::BinCharacter(Some(b"abc".to_vec())),
+    )]);
+    let read = write_read_records(writer_builder, &[binary])?;
+    assert_eq!(
+        read[0].get("name"),
+        Some(&FieldValue::Character(Some("abc".to_string())))
+    );
+
+    // Memo files cannot be written, which is an error instead of a panic
+    let writer_builder = TableWriterBuilder::new().add_memo_field("notes".try_into().unwrap());
+    let memo = record(vec![("notes", FieldValue::Memo("text".to_string()))]);
+    let error = write_read_records(writer_builder, &[memo]).unwrap_err();
+    assert!(matches!(error.kind(), dbase::ErrorKind::Message(_)));
+    Ok(())
+}
+
+#[test]
+fn test_write_duplicate_field_names() -> Result<(), dbase::Error> {
+    // Names are not case sensitive
+    let mut writer = TableWriterBuilder::new()
+        .add_character_field("Name".try_into().unwrap(), 10)
+        .add_character_field("NAME".try_into().unwrap(), 10)
+        .build_with_dest(Cursor::new(Vec::<u8>::new()));
+    let error = writer.write_record(&Record::default()).unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        dbase::ErrorKind::DuplicateFieldName { name } if name == "NAME"
+    ));
+
+    // dBase Level 7 descriptors hold 32 bytes names, the other versions only 11
+    let mut data = std::fs::read(LEVEL7_DBF).unwrap();
+    let first_descriptor = 68;
+    for (i, name) in [b"CUSTOMER_ID_1", b"CUSTOMER_ID_2"].iter().enumerate() {
+        let start = first_descriptor + (i + 1) * 48;
+        data[start..start + 32].fill(0);
+        data[start..start + name.len()].copy_from_slice(*name);
+    }
+    let mut reader = Reader::new(Cursor::new(data))?;
+    let records = reader.read()?;
+    assert!(records[0].get("CUSTOMER_ID_2").is_some());
+
+    let writer = TableWriterBuilder::from_reader(reader.clone())
+        .build_with_dest(Cursor::new(Vec::<u8>::new()));
+    writer.write_records(&records)?;
+
+    let writer = TableWriterBuilder::from_reader(reader)
+        .set_version(dbase::Version::VisualFoxPro)
+        .build_with_dest(Cursor::new(Vec::<u8>::new()));
+    let error = writer.write_records(&records).unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        dbase::ErrorKind::DuplicateFieldName { name } if name == "CUSTOMER_ID"
+    ));
+    Ok(())
+}
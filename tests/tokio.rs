@@ -0,0 +1,69 @@
+#![cfg(feature = "tokio")]
+
+use dbase::{AsyncReader, Reader, ReadingOptions};
+
+const STATIONS: &str = "./tests/data/stations.dbf";
+const STATIONS_WITH_DELETED: &str = "./tests/data/stations_with_deleted.dbf";
+
+fn block_on<F: std::future::Future>(future: F) -> F::Output {
+    tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap()
+        .block_on(future)
+}
+
+#[test]
+fn test_async_read_same_as_sync() -> Result<(), dbase::Error> {
+    for path in [STATIONS, STATIONS_WITH_DELETED] {
+        let expected = Reader::from_path(path)?.read()?;
+
+        let records = block_on(async {
+            let file = tokio::fs::File::open(path).await.unwrap();
+            let mut reader = AsyncReader::new(file).await?;
+            assert_eq!(reader.fields(), Reader::from_path(path)?.fields());
+            let mut records = vec![];
+            while let Some(record) = reader.read_record().await? {
+                records.push(record);
+            }
+            // Reading after the end keeps returning nothing
+            assert!(reader.read_record().await?.is_none());
+            Ok::<_, dbase::Error>(records)
+        })?;
+        assert_eq!(records, expected);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_async_read_with_options() -> Result<(), dbase::Error> {
+    let expected = Reader::from_path(STATIONS)?.read()?;
+    let records = block_on(async {
+        let data = std::fs::read(STATIONS).unwrap();
+        let mut reader = AsyncReader::new(data.as_slice()).await?;
+        reader.set_options(ReadingOptions::default().skip(80).limit(3));
+        let mut records = vec![];
+        while let Some(record) = reader.read_record().await? {
+            records.push(record);
+        }
+        Ok::<_, dbase::Error>(records)
+    })?;
+    assert_eq!(records, expected[80..83]);
+
+    // The file ends inside the last record
+    let error = block_on(async {
+        let data = std::fs::read(STATIONS).unwrap();
+        let table_info = Reader::from_path(STATIONS)?.into_table_info();
+        let end = table_info.header().offset_to_first_record as usize
+            + 86 * table_info.header().size_of_record as usize
+            - 5;
+        let mut reader = AsyncReader::new(&data[..end]).await?;
+        while reader.read_record().await?.is_some() {}
+        Ok::<_, dbase::Error>(())
+    })
+    .unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        dbase::ErrorKind::TruncatedRecord { index: 85 }
+    ));
+    Ok(())
+}